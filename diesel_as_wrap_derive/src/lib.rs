@@ -0,0 +1,225 @@
+//! The `#[derive(AsWrap)]` and `#[model]` macros of [diesel_as_wrap](https://docs.rs/diesel_as_wrap).
+//!
+//! Use it through the `derive` feature of diesel_as_wrap, which re-exports them and documents their attributes.
+
+use proc_macro::TokenStream;
+use proc_macro2::{TokenStream as TokenStream2, TokenTree};
+use quote::{quote, quote_spanned, ToTokens};
+use syn::meta::ParseNestedMeta;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{
+    parse_macro_input, parse_quote, Attribute, Data, DeriveInput, Error, Fields, GenericArgument, ItemStruct, Member, Meta, Path,
+    PathArguments, Result, Token, Type,
+};
+
+#[proc_macro_derive(AsWrap, attributes(as_wrap))]
+pub fn derive_as_wrap(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input).unwrap_or_else(Error::into_compile_error).into()
+}
+
+/// The values of the `#[as_wrap(...)]` attributes.
+#[derive(Default)]
+struct Options {
+    sql_type: Option<Type>,
+    via: Option<Type>,
+    backend: Option<Type>,
+}
+
+impl Options {
+    fn parse(input: &DeriveInput) -> Result<Self> {
+        let mut options = Options::default();
+        for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("as_wrap")) {
+            attr.parse_nested_meta(|meta| {
+                let slot = if meta.path.is_ident("sql_type") {
+                    &mut options.sql_type
+                } else if meta.path.is_ident("via") {
+                    &mut options.via
+                } else if meta.path.is_ident("backend") {
+                    &mut options.backend
+                } else {
+                    return Err(meta.error("unknown `as_wrap` attribute, expected `sql_type`, `via` or `backend`"));
+                };
+                set(slot, &meta)
+            })?;
+        }
+        Ok(options)
+    }
+}
+
+fn set(slot: &mut Option<Type>, meta: &ParseNestedMeta) -> Result<()> {
+    if slot.is_some() {
+        let name = meta.path.get_ident().map(ToString::to_string).unwrap_or_default();
+        return Err(meta.error(format!("duplicate `{name}` attribute")));
+    }
+    *slot = Some(meta.value()?.parse()?);
+    Ok(())
+}
+
+fn expand(input: DeriveInput) -> Result<TokenStream2> {
+    let options = Options::parse(&input)?;
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "`AsWrap` can't be derived for generic types, use `wrap!` with a where clause on the target instead",
+        ));
+    }
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        Data::Enum(data) => return Err(Error::new(data.enum_token.span(), "`AsWrap` can only be derived for structs with a single field")),
+        Data::Union(data) => return Err(Error::new(data.union_token.span(), "`AsWrap` can only be derived for structs with a single field")),
+    };
+    let field = match fields {
+        Fields::Named(named) if named.named.len() == 1 => &named.named[0],
+        Fields::Unnamed(unnamed) if unnamed.unnamed.len() == 1 => &unnamed.unnamed[0],
+        _ => return Err(Error::new_spanned(fields, "`AsWrap` can only be derived for structs with a single field")),
+    };
+    let Some(sql_type) = options.sql_type else {
+        return Err(Error::new_spanned(&input.ident, "missing `#[as_wrap(sql_type = ...)]` attribute"));
+    };
+
+    let ident = &input.ident;
+    let backend = options.backend.map(|backend| quote! { backend = #backend; });
+    // without `via` the field is written and read as the sql type, the bounds on it point at the field
+    let conversions = match options.via {
+        Some(via) => quote! { via = #via; },
+        None => {
+            let member = match &field.ident {
+                Some(ident) => Member::Named(ident.clone()),
+                None => Member::Unnamed(0.into()),
+            };
+            let ty = &field.ty;
+            quote_spanned! {ty.span()=>
+                fn to_sql<#ty>(self, out) {
+                    let value: &#ty = &self.#member;
+                    value.to_sql(out)
+                }
+                fn from_sql<#ty>(bytes) {
+                    <#ty>::from_sql(bytes).map(|value| Self { #member: value })
+                }
+            }
+        }
+    };
+
+    Ok(quote! {
+        ::diesel_as_wrap::wrap! {
+            target = #ident;
+            sql_type = #sql_type;
+            #backend
+            impl;
+            #conversions
+        }
+    })
+}
+
+#[proc_macro_attribute]
+pub fn model(args: TokenStream, item: TokenStream) -> TokenStream {
+    let mappings = parse_macro_input!(args with Punctuated::<Mapping, Token![,]>::parse_terminated);
+    let item = parse_macro_input!(item as ItemStruct);
+    expand_model(mappings, item).unwrap_or_else(Error::into_compile_error).into()
+}
+
+/// A `Type => path::to::wrappers` argument of `#[model(...)]`.
+struct Mapping {
+    ty: Type,
+    module: Path,
+}
+
+impl Parse for Mapping {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let ty = input.parse()?;
+        input.parse::<Token![=>]>()?;
+        let module = input.parse()?;
+        Ok(Mapping { ty, module })
+    }
+}
+
+fn expand_model(mappings: Punctuated<Mapping, Token![,]>, mut item: ItemStruct) -> Result<TokenStream2> {
+    let mut used = vec![false; mappings.len()];
+    for field in item.fields.iter_mut() {
+        let mut module = None;
+        let mut error = None;
+        field.attrs.retain(|attr| {
+            if !attr.path().is_ident("as_wrap") {
+                return true;
+            }
+            match attr.parse_args::<Path>() {
+                Ok(path) if module.is_none() => module = Some(path),
+                Ok(_) => error = Some(Error::new_spanned(attr, "duplicate `as_wrap` attribute")),
+                Err(e) => error = Some(e),
+            }
+            false
+        });
+        if let Some(error) = error {
+            return Err(error);
+        }
+        // fields that already name their wrappers are left alone
+        if module.is_none() && field.attrs.iter().any(names_wrapper) {
+            continue;
+        }
+
+        let (inner, nullable) = match option_inner(&field.ty) {
+            Some(inner) => (inner, true),
+            None => (&field.ty, false),
+        };
+        let module = match module {
+            Some(module) => module,
+            None => match mappings.iter().position(|mapping| same_type(&mapping.ty, inner)) {
+                Some(index) => {
+                    used[index] = true;
+                    mappings[index].module.clone()
+                }
+                None => continue,
+            },
+        };
+        let wrapper = if nullable { quote!(AsOption) } else { quote!(As) };
+        field.attrs.push(parse_quote! {
+            #[diesel(deserialize_as = #module::#wrapper, serialize_as = #module::#wrapper)]
+        });
+    }
+
+    if let Some(index) = used.iter().position(|used| !used) {
+        return Err(Error::new_spanned(&mappings[index].ty, "no field of this type, remove it from `#[model(...)]`"));
+    }
+    Ok(quote!(#item))
+}
+
+/// Whether the attribute is a `#[diesel(...)]` with `deserialize_as` or `serialize_as`.
+fn names_wrapper(attr: &Attribute) -> bool {
+    let Meta::List(list) = &attr.meta else {
+        return false;
+    };
+    list.path.is_ident("diesel")
+        && list.tokens.clone().into_iter().any(|token| {
+            matches!(token, TokenTree::Ident(ident) if ident == "deserialize_as" || ident == "serialize_as")
+        })
+}
+
+/// The `T` of an `Option<T>`.
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.iter().collect::<Vec<_>>()[..] {
+        [GenericArgument::Type(inner)] if segment.ident == "Option" => Some(inner),
+        _ => None,
+    }
+}
+
+/// Compares paths by their last segment, so `Uuid` and `uuid::Uuid` are the same type.
+fn same_type(left: &Type, right: &Type) -> bool {
+    match (left, right) {
+        (Type::Path(left), Type::Path(right)) if left.qself.is_none() && right.qself.is_none() => {
+            let left = left.path.segments.last().map(ToTokens::to_token_stream);
+            let right = right.path.segments.last().map(ToTokens::to_token_stream);
+            left.map(|left| left.to_string()) == right.map(|right| right.to_string())
+        }
+        _ => left.to_token_stream().to_string() == right.to_token_stream().to_string(),
+    }
+}
@@ -0,0 +1,333 @@
+use std::fmt::{self, Write};
+use std::str::FromStr;
+
+use diesel::backend::Backend;
+use diesel::deserialize::{self, FromSql};
+use diesel::serialize::{self, Output, ToSql};
+use diesel::sql_types::{BigInt, Binary, Integer, SmallInt, Text};
+
+use crate::{ToSqlBuffered, ToSqlOwned};
+
+///
+/// Any type implementing `Display` and `FromStr` stored in `Text` columns, on every backend.\
+/// The value is written with its `Display` implementation and parsed back with `FromStr`,
+/// a string that doesn't parse is a deserialize error containing the string and the parse error.\
+/// Use it as type of the field, `Option<AsText<T>>` for nullable columns, no `wrap!` invocation is needed.
+/// Both implementations have to round-trip, `T::from_str(&value.to_string())` has to return the value again.\
+/// It is `repr(transparent)`, so a value can be borrowed as AsText with [`AsText::from_ref`].
+///
+/// # Example:
+#[cfg_attr(feature = "sqlite", doc = "```")]
+#[cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+/// # use diesel::prelude::*;
+/// # mod schema {
+/// #     diesel::table! {
+/// #         servers (id) {
+/// #             id -> Integer,
+/// #             address -> Text,
+/// #             fallback -> Nullable<Text>,
+/// #         }
+/// #     }
+/// # }
+/// use std::net::{IpAddr, Ipv6Addr};
+/// use diesel_as_wrap::AsText;
+///
+/// #[derive(Debug, Clone, PartialEq, Queryable, Selectable, Insertable)]
+/// #[diesel(table_name = schema::servers)]
+/// pub struct Server {
+///     id: i32,
+///     address: AsText<IpAddr>,
+///     fallback: Option<AsText<IpAddr>>,
+/// }
+///
+/// # fn main() {
+/// let mut conn = SqliteConnection::establish(":memory:").unwrap();
+/// diesel::sql_query("CREATE TABLE servers (id INTEGER PRIMARY KEY, address TEXT NOT NULL, fallback TEXT)")
+///     .execute(&mut conn)
+///     .unwrap();
+///
+/// let servers = vec![
+///     Server { id: 1, address: AsText(IpAddr::from([10, 0, 0, 1])), fallback: Some(AsText(IpAddr::from(Ipv6Addr::LOCALHOST))) },
+///     Server { id: 2, address: AsText(IpAddr::from([10, 0, 0, 2])), fallback: None },
+/// ];
+/// diesel::insert_into(schema::servers::table).values(servers.clone()).execute(&mut conn).unwrap();
+///
+/// let stored: Option<String> = schema::servers::table.select(schema::servers::fallback).first(&mut conn).unwrap();
+/// assert_eq!(stored.as_deref(), Some("::1"));
+/// let loaded: Vec<Server> = schema::servers::table.select(Server::as_select()).load(&mut conn).unwrap();
+/// assert_eq!(loaded, servers);
+///
+/// diesel::sql_query("UPDATE servers SET address = '10.0.0.256' WHERE id = 2").execute(&mut conn).unwrap();
+/// let error = schema::servers::table.select(Server::as_select()).load(&mut conn).unwrap_err();
+/// let error = error.to_string();
+/// assert!(error.contains(r#""10.0.0.256" is not a valid `"#) && error.ends_with("IpAddr`: invalid IP address syntax"));
+/// # }
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, diesel::deserialize::FromSqlRow, diesel::expression::AsExpression)]
+#[diesel(sql_type = Text)]
+#[repr(transparent)]
+pub struct AsText<T>(pub T);
+
+impl<T> AsText<T> {
+    /// Borrows the value as AsText without moving or cloning it.
+    pub fn from_ref(value: &T) -> &Self {
+        // SAFETY: AsText is repr(transparent) over the value.
+        unsafe { &*(value as *const T as *const Self) }
+    }
+}
+
+impl<T> From<T> for AsText<T> {
+    fn from(s: T) -> Self {
+        AsText(s)
+    }
+}
+
+impl<T, B> FromSql<Text, B> for AsText<T>
+where
+    B: Backend,
+    T: FromStr,
+    T::Err: fmt::Display,
+    *const str: FromSql<Text, B>,
+{
+    fn from_sql(bytes: B::RawValue<'_>) -> deserialize::Result<Self> {
+        let text = <*const str as FromSql<Text, B>>::from_sql(bytes)?;
+        // SAFETY: diesel guarantees the pointer to be valid for the duration of `from_sql`.
+        let text = unsafe { &*text };
+        text.parse()
+            .map(AsText)
+            .map_err(|e| format!("{text:?} is not a valid `{}`: {e}", std::any::type_name::<T>()).into())
+    }
+}
+
+impl<T, B> ToSql<Text, B> for AsText<T>
+where
+    B: Backend,
+    T: fmt::Display + fmt::Debug,
+    String: ToSqlBuffered<Text, B>,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, B>) -> serialize::Result {
+        String::to_sql_buffered(out, |buffer| {
+            write!(buffer, "{}", self.0)?;
+            Ok(())
+        })
+    }
+}
+
+///
+/// Any type implementing `AsRef<[u8]>` and `TryFrom<Vec<u8>>` stored in `Binary` columns, on every backend,
+/// for hashes, keys, fixed size arrays and other byte-shaped types.\
+/// The bytes returned by `as_ref` are written, the bytes read are converted with `try_from`,
+/// a failed conversion is a deserialize error containing the number of bytes read.\
+/// Use it as type of the field, `Option<AsBinary<T>>` for nullable columns, no `wrap!` invocation is needed.\
+/// It is `repr(transparent)`, so a value can be borrowed as AsBinary with [`AsBinary::from_ref`].
+///
+/// # Example:
+#[cfg_attr(feature = "sqlite", doc = "```")]
+#[cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+/// # use diesel::prelude::*;
+/// # mod schema {
+/// #     diesel::table! {
+/// #         files (id) {
+/// #             id -> Integer,
+/// #             sha256 -> Binary,
+/// #             signature -> Nullable<Binary>,
+/// #         }
+/// #     }
+/// # }
+/// use diesel_as_wrap::AsBinary;
+///
+/// #[derive(Debug, Clone, PartialEq, Queryable, Selectable, Insertable)]
+/// #[diesel(table_name = schema::files)]
+/// pub struct File {
+///     id: i32,
+///     sha256: AsBinary<[u8; 32]>,
+///     signature: Option<AsBinary<[u8; 64]>>,
+/// }
+///
+/// # fn main() {
+/// let mut conn = SqliteConnection::establish(":memory:").unwrap();
+/// diesel::sql_query("CREATE TABLE files (id INTEGER PRIMARY KEY, sha256 BLOB NOT NULL, signature BLOB)")
+///     .execute(&mut conn)
+///     .unwrap();
+///
+/// let files = vec![
+///     File { id: 1, sha256: AsBinary([0xab; 32]), signature: Some(AsBinary([7; 64])) },
+///     File { id: 2, sha256: AsBinary([0x01; 32]), signature: None },
+/// ];
+/// diesel::insert_into(schema::files::table).values(files.clone()).execute(&mut conn).unwrap();
+///
+/// let stored: Vec<u8> = schema::files::table.select(schema::files::sha256).first(&mut conn).unwrap();
+/// assert_eq!(stored, [0xab; 32]);
+/// let loaded: Vec<File> = schema::files::table.select(File::as_select()).load(&mut conn).unwrap();
+/// assert_eq!(loaded, files);
+///
+/// diesel::sql_query("UPDATE files SET sha256 = x'abcd' WHERE id = 2").execute(&mut conn).unwrap();
+/// let error = schema::files::table.select(File::as_select()).load(&mut conn).unwrap_err();
+/// assert!(error.to_string().ends_with("2 bytes are not a valid `[u8; 32]`"));
+/// # }
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, diesel::deserialize::FromSqlRow, diesel::expression::AsExpression)]
+#[diesel(sql_type = Binary)]
+#[repr(transparent)]
+pub struct AsBinary<T>(pub T);
+
+impl<T> AsBinary<T> {
+    /// Borrows the value as AsBinary without moving or cloning it.
+    pub fn from_ref(value: &T) -> &Self {
+        // SAFETY: AsBinary is repr(transparent) over the value.
+        unsafe { &*(value as *const T as *const Self) }
+    }
+}
+
+impl<T> From<T> for AsBinary<T> {
+    fn from(s: T) -> Self {
+        AsBinary(s)
+    }
+}
+
+impl<T, B> FromSql<Binary, B> for AsBinary<T>
+where
+    B: Backend,
+    T: TryFrom<Vec<u8>>,
+    Vec<u8>: FromSql<Binary, B>,
+{
+    fn from_sql(bytes: B::RawValue<'_>) -> deserialize::Result<Self> {
+        let bytes = Vec::<u8>::from_sql(bytes)?;
+        let len = bytes.len();
+        T::try_from(bytes)
+            .map(AsBinary)
+            .map_err(|_| format!("{len} bytes are not a valid `{}`", std::any::type_name::<T>()).into())
+    }
+}
+
+impl<T, B> ToSql<Binary, B> for AsBinary<T>
+where
+    B: Backend,
+    T: AsRef<[u8]> + fmt::Debug,
+    [u8]: ToSql<Binary, B>,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, B>) -> serialize::Result {
+        self.0.as_ref().to_sql(out)
+    }
+}
+
+macro_rules! checked_int {
+    ($($(#[$attr:meta])* $name:ident: $sql_type:ident, $int:ident, $sql_name:literal;)+) => {
+        $(
+            ///
+            #[doc = concat!("Any type converting from and into `", stringify!($int), "` with `TryFrom` stored in `", $sql_name, "` columns, on every backend.\\")]
+            /// The value is written as its integer, read back with `try_from` and checked in both directions,
+            /// a value that doesn't fit the column is a serialize error and an integer the type rejects is a deserialize error,
+            /// both containing the value.
+            #[doc = concat!("Writing converts with `", stringify!($int), "::try_from` as well, so unsigned and wider types are checked instead of truncated.\\")]
+            #[doc = concat!("Use it as type of the field, `Option<", stringify!($name), "<T>>` for nullable columns, no `wrap!` invocation is needed.\\")]
+            #[doc = concat!("It is `repr(transparent)`, so a value can be borrowed as ", stringify!($name), " with `from_ref`.")]
+            ///
+            $(#[$attr])*
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, diesel::deserialize::FromSqlRow, diesel::expression::AsExpression)]
+            #[diesel(sql_type = $sql_type)]
+            #[repr(transparent)]
+            pub struct $name<T>(pub T);
+
+            impl<T> $name<T> {
+                #[doc = concat!("Borrows the value as ", stringify!($name), " without moving or cloning it.")]
+                pub fn from_ref(value: &T) -> &Self {
+                    // SAFETY: the wrapper is repr(transparent) over the value.
+                    unsafe { &*(value as *const T as *const Self) }
+                }
+            }
+
+            impl<T> From<T> for $name<T> {
+                fn from(s: T) -> Self {
+                    $name(s)
+                }
+            }
+
+            impl<T, B> FromSql<$sql_type, B> for $name<T>
+            where
+                B: Backend,
+                T: TryFrom<$int>,
+                $int: FromSql<$sql_type, B>,
+            {
+                fn from_sql(bytes: B::RawValue<'_>) -> deserialize::Result<Self> {
+                    let value = <$int>::from_sql(bytes)?;
+                    T::try_from(value)
+                        .map($name)
+                        .map_err(|_| format!("{value} is out of range for `{}`", std::any::type_name::<T>()).into())
+                }
+            }
+
+            impl<T, B> ToSql<$sql_type, B> for $name<T>
+            where
+                B: Backend,
+                T: Clone + fmt::Debug,
+                $int: TryFrom<T> + ToSqlOwned<$sql_type, B>,
+            {
+                fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, B>) -> serialize::Result {
+                    <$int>::try_from(self.0.clone())
+                        .map_err(|_| format!("{:?} is out of range for `{}`", self.0, $sql_name))?
+                        .to_sql_owned(out)
+                }
+            }
+        )+
+    };
+}
+
+checked_int! {
+    /// See [`AsI32`] for an example.
+    ///
+    AsI16: SmallInt, i16, "SmallInt";
+    /// # Example:
+    #[cfg_attr(feature = "sqlite", doc = "```")]
+    #[cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+    /// # use diesel::prelude::*;
+    /// # mod schema {
+    /// #     diesel::table! {
+    /// #         counters (id) {
+    /// #             id -> Integer,
+    /// #             hits -> Integer,
+    /// #             limit -> Nullable<Integer>,
+    /// #         }
+    /// #     }
+    /// # }
+    /// use diesel_as_wrap::AsI32;
+    ///
+    /// #[derive(Debug, Clone, PartialEq, Queryable, Selectable, Insertable)]
+    /// #[diesel(table_name = schema::counters)]
+    /// pub struct Counter {
+    ///     id: i32,
+    ///     hits: AsI32<u32>,
+    ///     limit: Option<AsI32<u16>>,
+    /// }
+    ///
+    /// # fn main() {
+    /// let mut conn = SqliteConnection::establish(":memory:").unwrap();
+    /// diesel::sql_query("CREATE TABLE counters (id INTEGER PRIMARY KEY, hits INTEGER NOT NULL, \"limit\" INTEGER)")
+    ///     .execute(&mut conn)
+    ///     .unwrap();
+    ///
+    /// let counters = vec![
+    ///     Counter { id: 1, hits: AsI32(2_000_000_000), limit: Some(AsI32(u16::MAX)) },
+    ///     Counter { id: 2, hits: AsI32(0), limit: None },
+    /// ];
+    /// diesel::insert_into(schema::counters::table).values(counters.clone()).execute(&mut conn).unwrap();
+    /// let loaded: Vec<Counter> = schema::counters::table.select(Counter::as_select()).load(&mut conn).unwrap();
+    /// assert_eq!(loaded, counters);
+    ///
+    /// let too_large = Counter { id: 3, hits: AsI32(u32::MAX), limit: None };
+    /// let error = diesel::insert_into(schema::counters::table).values(too_large).execute(&mut conn).unwrap_err();
+    /// assert!(error.to_string().ends_with("4294967295 is out of range for `Integer`"));
+    ///
+    /// diesel::sql_query("UPDATE counters SET hits = -1 WHERE id = 2").execute(&mut conn).unwrap();
+    /// let error = schema::counters::table.select(Counter::as_select()).load(&mut conn).unwrap_err();
+    /// assert!(error.to_string().ends_with("-1 is out of range for `u32`"));
+    /// # }
+    /// ```
+    ///
+    AsI32: Integer, i32, "Integer";
+    /// See [`AsI32`] for an example.
+    ///
+    AsI64: BigInt, i64, "BigInt";
+}
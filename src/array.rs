@@ -0,0 +1,185 @@
+// The array wrappers of the modules created by `wrap!`.
+// They are emitted through this macro instead of a `#[cfg]` inside `wrap!`,
+// because a `#[cfg(feature = "postgres")]` in the expansion would check the features of the invoking crate.
+
+#[cfg(feature = "postgres")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __arrays {
+    ($target:ty; $sql_type:ty) => {
+        ///Wrapper for a Postgres array (`Array<sql_type>`) of the target.
+        #[derive(Debug, FromSqlRow, AsExpression)]
+        #[diesel(sql_type = Array<$sql_type>)]
+        pub struct AsVec(pub Vec<As>);
+
+        ///Wrapper for a nullable Postgres array (`Nullable<Array<sql_type>>`) of the target.
+        #[derive(Debug, FromSqlRow, AsExpression)]
+        #[diesel(sql_type = Nullable<Array<$sql_type>>)]
+        pub struct AsOptionVec(pub Option<Vec<As>>);
+
+        ///Wrapper for a Postgres array with nullable elements (`Array<Nullable<sql_type>>`) of the target.
+        #[derive(Debug, FromSqlRow, AsExpression)]
+        #[diesel(sql_type = Array<Nullable<$sql_type>>)]
+        pub struct AsVecOption(pub Vec<Option<As>>);
+
+        ///Wrapper for a nullable Postgres array with nullable elements (`Nullable<Array<Nullable<sql_type>>>`) of the target.
+        #[derive(Debug, FromSqlRow, AsExpression)]
+        #[diesel(sql_type = Nullable<Array<Nullable<$sql_type>>>)]
+        pub struct AsOptionVecOption(pub Option<Vec<Option<As>>>);
+
+        impl From<AsVec> for Vec<$target> {
+            fn from(s: AsVec) -> Self {
+                As::into_targets(s.0)
+            }
+        }
+
+        impl From<Vec<$target>> for AsVec {
+            fn from(s: Vec<$target>) -> Self {
+                AsVec(As::from_targets(s))
+            }
+        }
+
+        impl From<AsOptionVec> for Option<Vec<$target>> {
+            fn from(s: AsOptionVec) -> Self {
+                s.0.map(As::into_targets)
+            }
+        }
+
+        impl From<Option<Vec<$target>>> for AsOptionVec {
+            fn from(s: Option<Vec<$target>>) -> Self {
+                AsOptionVec(s.map(As::from_targets))
+            }
+        }
+
+        impl From<AsVecOption> for Vec<Option<$target>> {
+            fn from(s: AsVecOption) -> Self {
+                s.0.into_iter().map(|w| w.map(|w| w.0)).collect()
+            }
+        }
+
+        impl From<Vec<Option<$target>>> for AsVecOption {
+            fn from(s: Vec<Option<$target>>) -> Self {
+                AsVecOption(s.into_iter().map(|u| u.map(As)).collect())
+            }
+        }
+
+        impl From<AsOptionVecOption> for Option<Vec<Option<$target>>> {
+            fn from(s: AsOptionVecOption) -> Self {
+                s.0.map(|v| v.into_iter().map(|w| w.map(|w| w.0)).collect())
+            }
+        }
+
+        impl From<Option<Vec<Option<$target>>>> for AsOptionVecOption {
+            fn from(s: Option<Vec<Option<$target>>>) -> Self {
+                AsOptionVecOption(s.map(|v| v.into_iter().map(|u| u.map(As)).collect()))
+            }
+        }
+
+        impl<B> FromSql<Array<$sql_type>, B> for AsVec
+        where
+            B: Backend,
+            Vec<As>: FromSql<Array<$sql_type>, B>,
+        {
+            fn from_sql(bytes: B::RawValue<'_>) -> DResult<Self> {
+                Ok(AsVec(<Vec<As>>::from_sql(bytes)?))
+            }
+        }
+
+        impl<B> ToSql<Array<$sql_type>, B> for AsVec
+        where
+            B: Backend,
+            Vec<As>: ToSql<Array<$sql_type>, B>,
+        {
+            fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, B>) -> SResult {
+                ToSql::<Array<$sql_type>, B>::to_sql(&self.0, out)
+            }
+        }
+
+        impl<B> FromSql<Nullable<Array<$sql_type>>, B> for AsOptionVec
+        where
+            B: Backend,
+            Vec<As>: FromSql<Array<$sql_type>, B>,
+        {
+            fn from_sql(bytes: B::RawValue<'_>) -> DResult<Self> {
+                Ok(AsOptionVec(Some(<Vec<As>>::from_sql(bytes)?)))
+            }
+
+            fn from_nullable_sql(bytes: Option<B::RawValue<'_>>) -> DResult<Self> {
+                match bytes {
+                    Some(bytes) => Self::from_sql(bytes),
+                    None => Ok(AsOptionVec(None)),
+                }
+            }
+        }
+
+        impl<B> ToSql<Nullable<Array<$sql_type>>, B> for AsOptionVec
+        where
+            B: Backend,
+            Vec<As>: ToSql<Array<$sql_type>, B>,
+        {
+            fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, B>) -> SResult {
+                match &self.0 {
+                    Some(values) => ToSql::<Array<$sql_type>, B>::to_sql(values, out),
+                    None => Ok(IsNull::Yes),
+                }
+            }
+        }
+
+        impl<B> FromSql<Array<Nullable<$sql_type>>, B> for AsVecOption
+        where
+            B: Backend,
+            Vec<Option<As>>: FromSql<Array<Nullable<$sql_type>>, B>,
+        {
+            fn from_sql(bytes: B::RawValue<'_>) -> DResult<Self> {
+                Ok(AsVecOption(<Vec<Option<As>>>::from_sql(bytes)?))
+            }
+        }
+
+        impl<B> ToSql<Array<Nullable<$sql_type>>, B> for AsVecOption
+        where
+            B: Backend,
+            Vec<Option<As>>: ToSql<Array<Nullable<$sql_type>>, B>,
+        {
+            fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, B>) -> SResult {
+                ToSql::<Array<Nullable<$sql_type>>, B>::to_sql(&self.0, out)
+            }
+        }
+
+        impl<B> FromSql<Nullable<Array<Nullable<$sql_type>>>, B> for AsOptionVecOption
+        where
+            B: Backend,
+            Vec<Option<As>>: FromSql<Array<Nullable<$sql_type>>, B>,
+        {
+            fn from_sql(bytes: B::RawValue<'_>) -> DResult<Self> {
+                Ok(AsOptionVecOption(Some(<Vec<Option<As>>>::from_sql(bytes)?)))
+            }
+
+            fn from_nullable_sql(bytes: Option<B::RawValue<'_>>) -> DResult<Self> {
+                match bytes {
+                    Some(bytes) => Self::from_sql(bytes),
+                    None => Ok(AsOptionVecOption(None)),
+                }
+            }
+        }
+
+        impl<B> ToSql<Nullable<Array<Nullable<$sql_type>>>, B> for AsOptionVecOption
+        where
+            B: Backend,
+            Vec<Option<As>>: ToSql<Array<Nullable<$sql_type>>, B>,
+        {
+            fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, B>) -> SResult {
+                match &self.0 {
+                    Some(values) => ToSql::<Array<Nullable<$sql_type>>, B>::to_sql(values, out),
+                    None => Ok(IsNull::Yes),
+                }
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "postgres"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __arrays {
+    ($target:ty; $sql_type:ty) => {};
+}
@@ -0,0 +1,104 @@
+use std::marker::PhantomData;
+
+use diesel::backend::Backend;
+use diesel::expression::{is_aggregate, AppearsOnTable, Expression, SelectableExpression, TypedExpressionType, ValidGrouping};
+use diesel::query_builder::{AstPass, QueryFragment, QueryId};
+use diesel::serialize::ToSql;
+use diesel::sql_types::{HasSqlType, SqlType};
+use diesel::QueryResult;
+
+///
+/// A value bound as query parameter of the sql type `ST`.\
+/// It is the expression of the wrappers created by [`wrap!`](crate::wrap) with a per backend `sql_type(...)`.
+/// Those wrappers can be used as expression of every sql type, whether the value can be written as that type
+/// is checked by the `ToSql` bound when the query is built for a backend.
+///
+#[derive(Debug, Clone, Copy)]
+pub struct BoundValue<ST, T> {
+    value: T,
+    sql_type: PhantomData<ST>,
+}
+
+impl<ST, T> BoundValue<ST, T> {
+    /// Binds the value as `ST`.
+    pub fn new(value: T) -> Self {
+        BoundValue { value, sql_type: PhantomData }
+    }
+}
+
+impl<ST, T> Expression for BoundValue<ST, T>
+where
+    ST: SqlType + TypedExpressionType,
+{
+    type SqlType = ST;
+}
+
+impl<ST, T, DB> QueryFragment<DB> for BoundValue<ST, T>
+where
+    DB: Backend + HasSqlType<ST>,
+    T: ToSql<ST, DB>,
+{
+    fn walk_ast<'b>(&'b self, mut pass: AstPass<'_, 'b, DB>) -> QueryResult<()> {
+        pass.push_bind_param(&self.value)
+    }
+}
+
+impl<ST: QueryId, T> QueryId for BoundValue<ST, T> {
+    type QueryId = BoundValue<ST::QueryId, ()>;
+
+    const HAS_STATIC_QUERY_ID: bool = ST::HAS_STATIC_QUERY_ID;
+}
+
+impl<ST, T, QS> SelectableExpression<QS> for BoundValue<ST, T> where Self: AppearsOnTable<QS> {}
+
+impl<ST, T, QS> AppearsOnTable<QS> for BoundValue<ST, T> where Self: Expression {}
+
+impl<ST, T, GB> ValidGrouping<GB> for BoundValue<ST, T> {
+    type IsAggregate = is_aggregate::Never;
+}
+
+// The impls of one backend in a per backend `sql_type(...)` of `wrap!`.
+// They are emitted through these macros instead of a `#[cfg]` inside `wrap!`,
+// because a `#[cfg(feature = "postgres")]` in the expansion would check the features of the invoking crate.
+
+#[cfg(feature = "postgres")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __if_postgres {
+    ($($item:tt)*) => { $($item)* };
+}
+
+#[cfg(not(feature = "postgres"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __if_postgres {
+    ($($item:tt)*) => {};
+}
+
+#[cfg(feature = "mysql")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __if_mysql {
+    ($($item:tt)*) => { $($item)* };
+}
+
+#[cfg(not(feature = "mysql"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __if_mysql {
+    ($($item:tt)*) => {};
+}
+
+#[cfg(feature = "sqlite")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __if_sqlite {
+    ($($item:tt)*) => { $($item)* };
+}
+
+#[cfg(not(feature = "sqlite"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __if_sqlite {
+    ($($item:tt)*) => {};
+}
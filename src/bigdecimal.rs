@@ -0,0 +1,146 @@
+//! Wrappers for [`bigdecimal::BigDecimal`](::bigdecimal::BigDecimal).
+//!
+//! [`PortableDecimal`] is a decimal sql type for every backend, use it inside `table!` (`total -> diesel_as_wrap::bigdecimal::PortableDecimal`).
+//! It is deliberately not named `Decimal`, `table!` treats that name as alias of `Numeric` and generates arithmetic operators for it.
+//! Postgres and MySQL store it as their native `numeric`, SQLite (which has no real decimal type and
+//! converts `NUMERIC` values to floating point) as the canonical string in a `TEXT` column.\
+//! [`decimal`] maps a `BigDecimal` to it, so a value round-trips losslessly, including its scale, on every backend
+//! and models with money or other exact amounts run unchanged in SQLite based test suites.
+//!
+//! # Example:
+#![cfg_attr(feature = "sqlite", doc = "```")]
+#![cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+//! # use diesel::prelude::*;
+//! # mod schema {
+//! #     diesel::table! {
+//! #         use diesel::sql_types::*;
+//! #         use diesel_as_wrap::bigdecimal::PortableDecimal;
+//! #
+//! #         invoices (id) {
+//! #             id -> Integer,
+//! #             total -> PortableDecimal,
+//! #             discount -> Nullable<PortableDecimal>,
+//! #         }
+//! #     }
+//! # }
+//! use std::str::FromStr;
+//! use bigdecimal::BigDecimal;
+//! use diesel_as_wrap::bigdecimal::decimal;
+//!
+//! #[derive(Debug, PartialEq, Queryable, Selectable, Insertable)]
+//! #[diesel(table_name = schema::invoices)]
+//! pub struct Invoice {
+//!     id: i32,
+//!     #[diesel(deserialize_as = decimal::As)]
+//!     #[diesel(serialize_as = decimal::As)]
+//!     total: BigDecimal,
+//!     #[diesel(deserialize_as = decimal::AsOption)]
+//!     #[diesel(serialize_as = decimal::AsOption)]
+//!     discount: Option<BigDecimal>,
+//! }
+//!
+//! # fn main() {
+//! let mut conn = SqliteConnection::establish(":memory:").unwrap();
+//! diesel::sql_query("CREATE TABLE invoices (id INTEGER PRIMARY KEY, total TEXT NOT NULL, discount TEXT)")
+//!     .execute(&mut conn)
+//!     .unwrap();
+//!
+//! let totals = ["0.10", "-12345678901234567890.000000000001", "1E+30", "0"];
+//! for (id, total) in totals.iter().enumerate() {
+//!     let total = BigDecimal::from_str(total).unwrap();
+//!     let invoice = Invoice { id: id as i32, discount: (total != BigDecimal::from(0)).then(|| &total / 2), total };
+//!     diesel::insert_into(schema::invoices::table).values(invoice).execute(&mut conn).unwrap();
+//! }
+//!
+//! let loaded: Vec<Invoice> = schema::invoices::table.select(Invoice::as_select()).load(&mut conn).unwrap();
+//! for (invoice, total) in loaded.iter().zip(totals) {
+//!     let expected = BigDecimal::from_str(total).unwrap();
+//!     assert_eq!(invoice.total, expected);
+//!     assert_eq!(invoice.total.fractional_digit_count(), expected.fractional_digit_count());
+//!     assert_eq!(invoice.discount, (expected != BigDecimal::from(0)).then(|| expected / 2));
+//! }
+//! # }
+//! ```
+
+#[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+use ::bigdecimal::BigDecimal;
+#[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+use diesel::deserialize::{self, FromSql};
+#[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+use diesel::serialize::{self, Output, ToSql};
+
+///
+/// A decimal sql type that is `numeric` on Postgres and MySQL and `TEXT` on SQLite.
+///
+#[derive(Debug, Clone, Copy, Default, diesel::sql_types::SqlType, diesel::query_builder::QueryId)]
+pub struct PortableDecimal;
+
+#[cfg(any(feature = "postgres", feature = "mysql"))]
+macro_rules! native_decimal {
+    ($($backend:ty),+) => {
+        $(
+            impl diesel::sql_types::HasSqlType<PortableDecimal> for $backend {
+                fn metadata(lookup: &mut Self::MetadataLookup) -> Self::TypeMetadata {
+                    <$backend as diesel::sql_types::HasSqlType<diesel::sql_types::Numeric>>::metadata(lookup)
+                }
+            }
+
+            impl ToSql<PortableDecimal, $backend> for BigDecimal {
+                fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, $backend>) -> serialize::Result {
+                    ToSql::<diesel::sql_types::Numeric, $backend>::to_sql(self, out)
+                }
+            }
+
+            impl FromSql<PortableDecimal, $backend> for BigDecimal {
+                fn from_sql(bytes: <$backend as diesel::backend::Backend>::RawValue<'_>) -> deserialize::Result<Self> {
+                    <BigDecimal as FromSql<diesel::sql_types::Numeric, $backend>>::from_sql(bytes)
+                }
+            }
+        )+
+    };
+}
+
+#[cfg(feature = "postgres")]
+native_decimal!(diesel::pg::Pg);
+
+#[cfg(feature = "mysql")]
+native_decimal!(diesel::mysql::Mysql);
+
+#[cfg(feature = "sqlite")]
+impl diesel::sql_types::HasSqlType<PortableDecimal> for diesel::sqlite::Sqlite {
+    fn metadata(lookup: &mut Self::MetadataLookup) -> Self::TypeMetadata {
+        <diesel::sqlite::Sqlite as diesel::sql_types::HasSqlType<diesel::sql_types::Text>>::metadata(lookup)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl ToSql<PortableDecimal, diesel::sqlite::Sqlite> for BigDecimal {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, diesel::sqlite::Sqlite>) -> serialize::Result {
+        out.set_value(self.to_string());
+        Ok(serialize::IsNull::No)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl FromSql<PortableDecimal, diesel::sqlite::Sqlite> for BigDecimal {
+    fn from_sql(bytes: diesel::sqlite::SqliteValue<'_, '_, '_>) -> deserialize::Result<Self> {
+        // SQLite converts integers and reals to text when they are read as text
+        let text = <*const str as FromSql<diesel::sql_types::Text, diesel::sqlite::Sqlite>>::from_sql(bytes)?;
+        // SAFETY: diesel guarantees the pointer to be valid for the duration of `from_sql`.
+        let text = unsafe { &*text };
+        text.parse().map_err(|e| format!("{text:?} is not a decimal: {e}").into())
+    }
+}
+
+crate::wrap! {
+    target = ::bigdecimal::BigDecimal;
+    sql_type = crate::bigdecimal::PortableDecimal;
+    pub mod decimal;
+    fn to_sql<::bigdecimal::BigDecimal>(self, out) {
+        let value: &::bigdecimal::BigDecimal = &self.0;
+        value.to_sql(out)
+    }
+    fn from_sql<::bigdecimal::BigDecimal>(bytes) {
+        <::bigdecimal::BigDecimal>::from_sql(bytes).map(As)
+    }
+}
@@ -0,0 +1,150 @@
+use diesel::backend::Backend;
+use diesel::serialize::{Output, Result as SResult};
+
+/// The error a `buffered_sql` body of [`wrap!`](crate::wrap) can return.
+pub type FillError = Box<dyn std::error::Error + Send + Sync>;
+
+///
+/// Serializes a value that is written into a scratch buffer first (text or bytes built by base64, json, compression, ...).\
+/// On byte based backends (Postgres and MySQL) the buffer is a thread local that is cleared and reused for every value,
+/// so bulk inserts don't allocate a temporary per row.
+/// Sqlite takes ownership of bound values, there every value gets a fresh buffer.\
+/// If the thread local buffer is already in use (a wrapper serializing another wrapper inside its body) a fresh buffer is used as well.
+/// Buffers that grew beyond [`MAX_RETAINED_CAPACITY`] are shrunk after use, so a single huge value doesn't pin its memory.\
+/// It is used by the `buffered_sql` form of [`wrap!`](crate::wrap) and implemented for `String` and `Vec<u8>`
+/// for the backends enabled by the `postgres`, `mysql` and `sqlite` features.
+///
+pub trait ToSqlBuffered<ST, DB: Backend>: Sized {
+    /// Fills a cleared buffer with `fill` and writes it to the output.
+    fn to_sql_buffered<F>(out: &mut Output<'_, '_, DB>, fill: F) -> SResult
+    where
+        F: FnOnce(&mut Self) -> Result<(), FillError>;
+}
+
+/// The capacity in bytes a thread local buffer keeps between values.
+pub const MAX_RETAINED_CAPACITY: usize = 64 * 1024;
+
+#[cfg(any(feature = "postgres", feature = "mysql"))]
+mod reused {
+    use super::{FillError, MAX_RETAINED_CAPACITY};
+    use std::cell::RefCell;
+    use std::thread::LocalKey;
+
+    thread_local! {
+        pub(super) static TEXT: RefCell<String> = const { RefCell::new(String::new()) };
+        pub(super) static BYTES: RefCell<Vec<u8>> = const { RefCell::new(Vec::new()) };
+    }
+
+    pub(super) trait Buffer: Default {
+        fn clear(&mut self);
+        fn shrink(&mut self);
+    }
+
+    impl Buffer for String {
+        fn clear(&mut self) {
+            String::clear(self)
+        }
+
+        fn shrink(&mut self) {
+            if self.capacity() > MAX_RETAINED_CAPACITY {
+                self.shrink_to(MAX_RETAINED_CAPACITY)
+            }
+        }
+    }
+
+    impl Buffer for Vec<u8> {
+        fn clear(&mut self) {
+            Vec::clear(self)
+        }
+
+        fn shrink(&mut self) {
+            if self.capacity() > MAX_RETAINED_CAPACITY {
+                self.shrink_to(MAX_RETAINED_CAPACITY)
+            }
+        }
+    }
+
+    pub(super) fn with_buffer<T, F, W, R>(key: &'static LocalKey<RefCell<T>>, fill: F, write: W) -> Result<R, FillError>
+    where
+        T: Buffer,
+        F: FnOnce(&mut T) -> Result<(), FillError>,
+        W: FnOnce(&T) -> Result<R, FillError>,
+    {
+        key.with(|cell| match cell.try_borrow_mut() {
+            Ok(mut buffer) => {
+                buffer.clear();
+                let result = fill(&mut buffer).and_then(|()| write(&buffer));
+                buffer.shrink();
+                result
+            }
+            Err(_) => {
+                let mut buffer = T::default();
+                fill(&mut buffer)?;
+                write(&buffer)
+            }
+        })
+    }
+}
+
+#[cfg(any(feature = "postgres", feature = "mysql"))]
+macro_rules! reused_buffered {
+    ($($backend:ty),+) => {
+        $(
+            impl<ST> ToSqlBuffered<ST, $backend> for String
+            where
+                str: diesel::serialize::ToSql<ST, $backend>,
+            {
+                fn to_sql_buffered<F>(out: &mut Output<'_, '_, $backend>, fill: F) -> SResult
+                where
+                    F: FnOnce(&mut Self) -> Result<(), FillError>,
+                {
+                    use diesel::serialize::ToSql;
+                    reused::with_buffer(&reused::TEXT, fill, |buffer| buffer.as_str().to_sql(&mut out.reborrow()))
+                }
+            }
+
+            impl<ST> ToSqlBuffered<ST, $backend> for Vec<u8>
+            where
+                [u8]: diesel::serialize::ToSql<ST, $backend>,
+            {
+                fn to_sql_buffered<F>(out: &mut Output<'_, '_, $backend>, fill: F) -> SResult
+                where
+                    F: FnOnce(&mut Self) -> Result<(), FillError>,
+                {
+                    use diesel::serialize::ToSql;
+                    reused::with_buffer(&reused::BYTES, fill, |buffer| buffer.as_slice().to_sql(&mut out.reborrow()))
+                }
+            }
+        )+
+    };
+}
+
+#[cfg(feature = "postgres")]
+reused_buffered!(diesel::pg::Pg);
+
+#[cfg(feature = "mysql")]
+reused_buffered!(diesel::mysql::Mysql);
+
+#[cfg(feature = "sqlite")]
+macro_rules! owned_buffered {
+    ($($ty:ty),+) => {
+        $(
+            impl<ST> ToSqlBuffered<ST, diesel::sqlite::Sqlite> for $ty
+            where
+                $ty: crate::ToSqlOwned<ST, diesel::sqlite::Sqlite>,
+            {
+                fn to_sql_buffered<F>(out: &mut Output<'_, '_, diesel::sqlite::Sqlite>, fill: F) -> SResult
+                where
+                    F: FnOnce(&mut Self) -> Result<(), FillError>,
+                {
+                    let mut buffer = <$ty>::default();
+                    fill(&mut buffer)?;
+                    crate::ToSqlOwned::<ST, diesel::sqlite::Sqlite>::to_sql_owned(buffer, out)
+                }
+            }
+        )+
+    };
+}
+
+#[cfg(feature = "sqlite")]
+owned_buffered!(String, Vec<u8>);
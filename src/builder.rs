@@ -0,0 +1,16 @@
+use std::fmt;
+
+///
+/// Returned by the `build` method of the builders created by [`insert_builder!`](crate::insert_builder)
+/// when a field without default was never set.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MissingField(pub &'static str);
+
+impl fmt::Display for MissingField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "missing field `{}`", self.0)
+    }
+}
+
+impl std::error::Error for MissingField {}
@@ -0,0 +1,63 @@
+//! Wrappers for [`bytes::Bytes`](::bytes::Bytes).
+//!
+//! [`binary`] stores `Bytes` inside a `Binary` column, on every backend.
+//! Reading borrows the raw value of the row and copies it into the `Bytes` once,
+//! instead of reading a `Vec<u8>` first, and writing binds the bytes by reference (`AsRef<[u8]>`) without copying them.
+//! Cloning the loaded value only bumps a reference count, which makes it cheap to hand large blobs to several consumers.
+//!
+//! # Example:
+#![cfg_attr(feature = "sqlite", doc = "```")]
+#![cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+//! # use diesel::prelude::*;
+//! # mod schema {
+//! #     diesel::table! {
+//! #         attachments (id) {
+//! #             id -> Integer,
+//! #             content -> Binary,
+//! #             preview -> Nullable<Binary>,
+//! #         }
+//! #     }
+//! # }
+//! use bytes::Bytes;
+//! use diesel_as_wrap::bytes::binary;
+//!
+//! #[derive(Debug, Clone, PartialEq, Queryable, Selectable, Insertable)]
+//! #[diesel(table_name = schema::attachments)]
+//! pub struct Attachment {
+//!     id: i32,
+//!     #[diesel(deserialize_as = binary::As)]
+//!     #[diesel(serialize_as = binary::As)]
+//!     content: Bytes,
+//!     #[diesel(deserialize_as = binary::AsOption)]
+//!     #[diesel(serialize_as = binary::AsOption)]
+//!     preview: Option<Bytes>,
+//! }
+//!
+//! # fn main() {
+//! let mut conn = SqliteConnection::establish(":memory:").unwrap();
+//! diesel::sql_query("CREATE TABLE attachments (id INTEGER PRIMARY KEY, content BLOB NOT NULL, preview BLOB)")
+//!     .execute(&mut conn)
+//!     .unwrap();
+//!
+//! let attachments = vec![
+//!     Attachment { id: 1, content: Bytes::from(vec![0x89; 64 * 1024]), preview: Some(Bytes::from_static(b"\x89PNG")) },
+//!     Attachment { id: 2, content: Bytes::new(), preview: None },
+//! ];
+//! diesel::insert_into(schema::attachments::table).values(attachments.clone()).execute(&mut conn).unwrap();
+//! let loaded: Vec<Attachment> = schema::attachments::table.select(Attachment::as_select()).load(&mut conn).unwrap();
+//! assert_eq!(loaded, attachments);
+//! # }
+//! ```
+
+crate::wrap! {
+    target = ::bytes::Bytes;
+    sql_type = Binary;
+    pub mod binary;
+    fn to_sql<[u8]>(self, out) {
+        let bytes: &[u8] = self.0.as_ref();
+        bytes.to_sql(out)
+    }
+    fn from_sql<&[u8]>(bytes) {
+        Ok(As(::bytes::Bytes::copy_from_slice(bytes)))
+    }
+}
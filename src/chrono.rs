@@ -0,0 +1,217 @@
+//! Wrappers for [`chrono`](::chrono) types.
+//!
+//! The `unix_*` modules store a `DateTime<Utc>` as a unix timestamp inside a `BigInt` column.
+//! The precision is selected by picking the module:
+//! - [`unix_seconds`] stores whole seconds.
+//! - [`unix_millis`] stores milliseconds.
+//! - [`unix_micros`] stores microseconds.
+//!
+//! Smaller parts are rounded down (floored), so `1969-12-31T23:59:59.5Z` is stored as `-1` second.
+//! Timestamps that are out of range for `DateTime<Utc>` produce a deserialization error.
+//!
+//! [`rfc3339`] stores a `DateTime<FixedOffset>` as RFC 3339 string inside a `Text` column (for example `2024-05-01T10:30:00+02:00`).
+//! Unlike `Timestamptz`, which only stores the instant, this keeps the original offset intact over the round trip.
+//! Note that sorting the column as text does not sort by instant when different offsets are mixed.
+//!
+//! [`utc_timestamp`] stores a `DateTime<Utc>` in a `Timestamp` column as UTC wall clock time, on every backend.
+//! [`timestamptz`] stores it in a Postgres `Timestamptz` column (requires the `postgres` feature),
+//! so models with `DateTime<Utc>` fields can use the same wrapper style for both column types.
+//!
+//! [`unix_days`] stores a `NaiveDate` as days since 1970-01-01 inside an `Integer` column and
+//! [`micros_of_day`] stores a `NaiveTime` as microseconds since midnight inside a `BigInt` column
+//! (sub microsecond parts are truncated, a leap second is stored as the last microsecond of the preceding second).
+//! Values that are out of range produce a deserialization error.
+//!
+//! # Example:
+//! ```
+//! # use diesel::prelude::*;
+//! # mod schema {
+//! #     diesel::table! {
+//! #         events (id) {
+//! #             id -> Integer,
+//! #             created_at -> BigInt,
+//! #             deleted_at -> Nullable<BigInt>,
+//! #         }
+//! #     }
+//! # }
+//! use chrono::{DateTime, Utc};
+//! use diesel_as_wrap::chrono::unix_millis;
+//!
+//! #[derive(Debug, Queryable, Selectable, Insertable)]
+//! #[diesel(table_name = schema::events)]
+//! pub struct Event {
+//!     id: i32,
+//!     #[diesel(deserialize_as = unix_millis::As)]
+//!     #[diesel(serialize_as = unix_millis::As)]
+//!     created_at: DateTime<Utc>,
+//!     #[diesel(deserialize_as = unix_millis::AsOption)]
+//!     #[diesel(serialize_as = unix_millis::AsOption)]
+//!     deleted_at: Option<DateTime<Utc>>,
+//! }
+//! ```
+//!
+//! The other encodings:
+#![cfg_attr(feature = "sqlite", doc = "```")]
+#![cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+//! use chrono::{DateTime, NaiveDate, NaiveTime};
+//! use diesel::prelude::*;
+//! use diesel::sql_types::{BigInt, Integer, Timestamp};
+//! use diesel_as_wrap::chrono::{micros_of_day, unix_days, unix_millis, unix_seconds, utc_timestamp};
+//!
+//! let mut conn = SqliteConnection::establish(":memory:").unwrap();
+//!
+//! let before_epoch = DateTime::from_timestamp(-1, 500_000_000).unwrap();
+//! assert_eq!(before_epoch.to_rfc3339(), "1969-12-31T23:59:59.500+00:00");
+//! let seconds: i64 = diesel::select(unix_seconds::As(before_epoch).into_sql::<BigInt>()).get_result(&mut conn).unwrap();
+//! assert_eq!(seconds, -1);
+//! let millis: i64 = diesel::select(unix_millis::As(before_epoch).into_sql::<BigInt>()).get_result(&mut conn).unwrap();
+//! assert_eq!(millis, -500);
+//!
+//! let launch = DateTime::from_timestamp(1_700_000_000, 250_000_000).unwrap();
+//! let loaded: utc_timestamp::As = diesel::select(utc_timestamp::As(launch).into_sql::<Timestamp>()).get_result(&mut conn).unwrap();
+//! assert_eq!(loaded.0, launch);
+//!
+//! let date = NaiveDate::from_ymd_opt(1969, 12, 31).unwrap();
+//! let days: i32 = diesel::select(unix_days::As(date).into_sql::<Integer>()).get_result(&mut conn).unwrap();
+//! assert_eq!(days, -1);
+//! let loaded: unix_days::As = diesel::select(days.into_sql::<Integer>()).get_result(&mut conn).unwrap();
+//! assert_eq!(loaded.0, date);
+//!
+//! let time = NaiveTime::from_hms_micro_opt(23, 59, 59, 999_999).unwrap();
+//! let micros: i64 = diesel::select(micros_of_day::As(time).into_sql::<BigInt>()).get_result(&mut conn).unwrap();
+//! assert_eq!(micros, 86_399_999_999);
+//! let loaded: micros_of_day::As = diesel::select(micros.into_sql::<BigInt>()).get_result(&mut conn).unwrap();
+//! assert_eq!(loaded.0, time);
+//!
+//! let next_day = diesel::select(86_400_000_000_i64.into_sql::<BigInt>()).get_result::<micros_of_day::As>(&mut conn);
+//! assert!(next_day.unwrap_err().to_string().ends_with("86400000000µs since midnight are out of range"));
+//! ```
+
+crate::wrap! {
+    target = ::chrono::DateTime<::chrono::Utc>;
+    sql_type = BigInt;
+    pub mod unix_seconds;
+    fn into_sql<i64>(self) {
+        self.0.timestamp()
+    }
+    fn from_sql<i64>(bytes) {
+        let seconds = i64::from_sql(bytes)?;
+        ::chrono::DateTime::from_timestamp(seconds, 0)
+            .map(As)
+            .ok_or_else(|| format!("unix timestamp {seconds}s is out of range").into())
+    }
+}
+
+crate::wrap! {
+    target = ::chrono::DateTime<::chrono::Utc>;
+    sql_type = BigInt;
+    pub mod unix_millis;
+    fn into_sql<i64>(self) {
+        self.0.timestamp_millis()
+    }
+    fn from_sql<i64>(bytes) {
+        let millis = i64::from_sql(bytes)?;
+        ::chrono::DateTime::from_timestamp_millis(millis)
+            .map(As)
+            .ok_or_else(|| format!("unix timestamp {millis}ms is out of range").into())
+    }
+}
+
+crate::wrap! {
+    target = ::chrono::DateTime<::chrono::Utc>;
+    sql_type = BigInt;
+    pub mod unix_micros;
+    fn into_sql<i64>(self) {
+        self.0.timestamp_micros()
+    }
+    fn from_sql<i64>(bytes) {
+        let micros = i64::from_sql(bytes)?;
+        ::chrono::DateTime::from_timestamp_micros(micros)
+            .map(As)
+            .ok_or_else(|| format!("unix timestamp {micros}µs is out of range").into())
+    }
+}
+
+crate::wrap! {
+    target = ::chrono::DateTime<::chrono::FixedOffset>;
+    sql_type = Text;
+    pub mod rfc3339;
+    fn into_sql<String>(self) {
+        self.0.to_rfc3339_opts(::chrono::SecondsFormat::AutoSi, false)
+    }
+    fn from_sql<String>(bytes) {
+        let text = String::from_sql(bytes)?;
+        ::chrono::DateTime::parse_from_rfc3339(&text)
+            .map(As)
+            .map_err(|e| format!("invalid rfc3339 timestamp {text}: {e}").into())
+    }
+}
+
+crate::wrap! {
+    target = ::chrono::DateTime<::chrono::Utc>;
+    sql_type = Timestamp;
+    pub mod utc_timestamp;
+    fn into_sql<::chrono::NaiveDateTime>(self) {
+        self.0.naive_utc()
+    }
+    fn from_sql<::chrono::NaiveDateTime>(bytes) {
+        <::chrono::NaiveDateTime>::from_sql(bytes).map(|naive| As(naive.and_utc()))
+    }
+}
+
+#[cfg(feature = "postgres")]
+crate::wrap! {
+    target = ::chrono::DateTime<::chrono::Utc>;
+    sql_type = Timestamptz;
+    pub mod timestamptz;
+    fn to_sql<::chrono::DateTime<::chrono::Utc>>(self, out) {
+        let value: &::chrono::DateTime<::chrono::Utc> = &self.0;
+        value.to_sql(out)
+    }
+    fn from_sql<::chrono::DateTime<::chrono::Utc>>(bytes) {
+        <::chrono::DateTime<::chrono::Utc>>::from_sql(bytes).map(As)
+    }
+}
+
+/// Days from 0001-01-01 (day 1 of the common era) to 1970-01-01.
+const UNIX_EPOCH_DAYS_FROM_CE: i32 = 719_163;
+
+crate::wrap! {
+    target = ::chrono::NaiveDate;
+    sql_type = Integer;
+    pub mod unix_days;
+    fn into_sql<i32>(self) {
+        use ::chrono::Datelike;
+        self.0.num_days_from_ce() - super::UNIX_EPOCH_DAYS_FROM_CE
+    }
+    fn from_sql<i32>(bytes) {
+        let days = i32::from_sql(bytes)?;
+        days.checked_add(super::UNIX_EPOCH_DAYS_FROM_CE)
+            .and_then(::chrono::NaiveDate::from_num_days_from_ce_opt)
+            .map(As)
+            .ok_or_else(|| format!("{days} days since the unix epoch are out of range").into())
+    }
+}
+
+crate::wrap! {
+    target = ::chrono::NaiveTime;
+    sql_type = BigInt;
+    pub mod micros_of_day;
+    fn into_sql<i64>(self) {
+        use ::chrono::Timelike;
+        // a leap second has nanoseconds beyond 999_999_999
+        let micros = self.0.nanosecond().min(999_999_999) / 1_000;
+        i64::from(self.0.num_seconds_from_midnight()) * 1_000_000 + i64::from(micros)
+    }
+    fn from_sql<i64>(bytes) {
+        let micros = i64::from_sql(bytes)?;
+        u32::try_from(micros.div_euclid(1_000_000))
+            .ok()
+            .and_then(|seconds| {
+                let nanos = micros.rem_euclid(1_000_000) as u32 * 1_000;
+                ::chrono::NaiveTime::from_num_seconds_from_midnight_opt(seconds, nanos)
+            })
+            .map(As)
+            .ok_or_else(|| format!("{micros}µs since midnight are out of range").into())
+    }
+}
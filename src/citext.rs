@@ -0,0 +1,224 @@
+//! Wrapper for the Postgres [`citext`](https://www.postgresql.org/docs/current/citext.html) extension type.
+//!
+//! [`Citext`] is the sql type of `citext` columns, use it inside `table!` (`email -> diesel_as_wrap::citext::Citext`).\
+//! [`ci_string`] maps a `citext` column to a [`CiString`], a string that compares, orders and hashes case insensitively
+//! like the column does, so `HashSet`s and `BTreeMap`s of loaded values agree with the database's unique constraints.
+//! The original case of the text is kept and written back unchanged.
+//! The extension has to be installed in the database (`CREATE EXTENSION citext`), its type oid is looked up on first use.
+//!
+//! # Example:
+#![cfg_attr(feature = "postgres", doc = "```")]
+#![cfg_attr(not(feature = "postgres"), doc = "```ignore")]
+//! # use diesel::prelude::*;
+//! # mod schema {
+//! #     diesel::table! {
+//! #         use diesel::sql_types::*;
+//! #         use diesel_as_wrap::citext::Citext;
+//! #
+//! #         accounts (id) {
+//! #             id -> Integer,
+//! #             username -> Citext,
+//! #             nickname -> Nullable<Citext>,
+//! #         }
+//! #     }
+//! # }
+//! use std::collections::HashSet;
+//! use diesel_as_wrap::citext::{ci_string, CiString};
+//!
+//! #[derive(Debug, Queryable, Selectable, Insertable)]
+//! #[diesel(table_name = schema::accounts)]
+//! #[diesel(check_for_backend(diesel::pg::Pg))]
+//! pub struct Account {
+//!     id: i32,
+//!     #[diesel(deserialize_as = ci_string::As)]
+//!     #[diesel(serialize_as = ci_string::As)]
+//!     username: CiString,
+//!     #[diesel(deserialize_as = ci_string::AsOption)]
+//!     #[diesel(serialize_as = ci_string::AsOption)]
+//!     nickname: Option<CiString>,
+//! }
+//!
+//! assert_eq!(CiString::from("Alice"), CiString::from("aLICE"));
+//! assert_eq!(CiString::from("Alice").as_str(), "Alice");
+//! assert!(CiString::from("alice") < CiString::from("Bob"));
+//!
+//! let names: HashSet<CiString> = ["Alice", "ALICE", "bob"].into_iter().map(CiString::from).collect();
+//! assert_eq!(names.len(), 2);
+//! ```
+//!
+//! The binary format is the text in its original case:
+#![cfg_attr(feature = "postgres", doc = "```")]
+#![cfg_attr(not(feature = "postgres"), doc = "```ignore")]
+//! # use diesel::deserialize::FromSql;
+//! # use diesel::pg::{Pg, PgMetadataLookup, PgTypeMetadata, PgValue};
+//! # use diesel::query_builder::bind_collector::RawBytesBindCollector;
+//! # use diesel::query_builder::BindCollector;
+//! # use diesel::serialize::ToSql;
+//! # use diesel::sql_types::HasSqlType;
+//! # struct Types;
+//! # impl PgMetadataLookup for Types {
+//! #     fn lookup_type(&mut self, _: &str, _: Option<&str>) -> PgTypeMetadata {
+//! #         PgTypeMetadata::new(100_000, 100_001)
+//! #     }
+//! # }
+//! # fn encode<ST, T: ToSql<ST, Pg>>(value: &T) -> Option<Vec<u8>> where Pg: HasSqlType<ST> {
+//! #     let mut collector = RawBytesBindCollector::<Pg>::new();
+//! #     collector.push_bound_value::<ST, T>(value, &mut Types).unwrap();
+//! #     collector.binds.pop().unwrap()
+//! # }
+//! # fn decode<ST, T: FromSql<ST, Pg>>(bytes: Option<&[u8]>) -> diesel::deserialize::Result<T> where Pg: HasSqlType<ST> {
+//! #     let oid = std::num::NonZeroU32::new(<Pg as HasSqlType<ST>>::metadata(&mut Types).oid().unwrap()).unwrap();
+//! #     T::from_nullable_sql(bytes.map(|bytes| PgValue::new(bytes, &oid)))
+//! # }
+//! use diesel::sql_types::Nullable;
+//! use diesel_as_wrap::citext::{ci_string, CiString, Citext};
+//!
+//! let bytes = encode::<Citext, _>(&ci_string::As(CiString::from("Alice"))).unwrap();
+//! assert_eq!(bytes, b"Alice");
+//! let read = decode::<Citext, ci_string::As>(Some(b"aLICE")).unwrap();
+//! assert_eq!(read.0, CiString::from("alice"));
+//! assert_eq!(read.0.as_str(), "aLICE");
+//! assert!(decode::<Nullable<Citext>, ci_string::AsOption>(None).unwrap().0.is_none());
+//! assert!(decode::<Citext, ci_string::As>(Some(b"\xff")).is_err());
+//! ```
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::io::Write;
+use std::ops::Deref;
+
+use diesel::deserialize::{self, FromSql};
+use diesel::pg::{Pg, PgValue};
+use diesel::serialize::{self, IsNull, Output, ToSql};
+
+///
+/// The sql type of the Postgres `citext` extension.
+///
+#[derive(Debug, Clone, Copy, Default, diesel::sql_types::SqlType, diesel::query_builder::QueryId)]
+#[diesel(postgres_type(name = "citext"))]
+pub struct Citext;
+
+impl ToSql<Citext, Pg> for str {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        out.write_all(self.as_bytes())?;
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Citext, Pg> for String {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        Ok(String::from_utf8(bytes.as_bytes().to_vec())?)
+    }
+}
+
+///
+/// A string that compares, orders and hashes case insensitively, the Rust counterpart of a `citext` value.\
+/// Both sides are compared by their lowercase characters (`char::to_lowercase`), as `citext` compares `lower(text)`.
+/// Postgres lowercases with the collation of the database, which agrees with Rust for the default collations.
+///
+#[derive(Clone, Default)]
+pub struct CiString(String);
+
+impl CiString {
+    /// The string in its original case.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the string in its original case.
+    pub fn into_string(self) -> String {
+        self.0
+    }
+
+    fn folded(&self) -> impl Iterator<Item = char> + '_ {
+        self.0.chars().flat_map(char::to_lowercase)
+    }
+}
+
+impl PartialEq for CiString {
+    fn eq(&self, other: &Self) -> bool {
+        self.folded().eq(other.folded())
+    }
+}
+
+impl Eq for CiString {}
+
+impl PartialOrd for CiString {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CiString {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.folded().cmp(other.folded())
+    }
+}
+
+impl Hash for CiString {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for c in self.folded() {
+            c.hash(state);
+        }
+        // terminates the string like `str`'s `Hash` does, so tuples of strings hash differently
+        state.write_u8(0xff);
+    }
+}
+
+impl fmt::Debug for CiString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Display for CiString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Deref for CiString {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for CiString {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for CiString {
+    fn from(s: String) -> Self {
+        CiString(s)
+    }
+}
+
+impl From<&str> for CiString {
+    fn from(s: &str) -> Self {
+        CiString(s.to_owned())
+    }
+}
+
+impl From<CiString> for String {
+    fn from(s: CiString) -> Self {
+        s.0
+    }
+}
+
+crate::wrap! {
+    target = crate::citext::CiString;
+    sql_type = crate::citext::Citext;
+    pub mod ci_string;
+    fn to_sql<str>(self, out) {
+        let text: &str = self.0.as_str();
+        text.to_sql(out)
+    }
+    fn from_sql<String>(bytes) {
+        String::from_sql(bytes).map(|text| As(text.into()))
+    }
+}
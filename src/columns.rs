@@ -0,0 +1,270 @@
+///
+/// This macro creates wrappers mapping a single value to several columns of a table, for domain values like
+/// `GeoPoint { lat, lon }` or `Money { amount, currency }` that are stored in one column per field.\
+/// Diesel's `#[diesel(embed)]` needs the embedded type to implement `Selectable`, `Queryable` and `Insertable` itself,
+/// which isn't possible for a type of another crate and only possible for one table per type.
+/// The created module contains a wrapper implementing these traits for the given table,
+/// so it is used as type of an `#[diesel(embed)]` field:
+/// `As` for `NOT NULL` columns, or `AsOption` for nullable columns if the columns are prefixed with `Nullable`
+/// (`None` is stored as `NULL` in every column).
+/// The wrapper dereferences to the target (or `Option` of it), converts from and into it and implements `Debug`
+/// (so the target has to implement `Debug`).
+///
+/// The `columns` section lists all fields of the target, each with the Rust type it is read as and the column it is stored in.
+/// Reading constructs the target from the fields, writing moves or borrows them, so the target doesn't need to implement `Clone`.
+/// An `AsOption` whose columns are only partially `NULL` produces a deserialization error.
+/// The fields have to be visible to the module invoking the macro.
+/// The optional `derive` section adds derives to the wrapper (for example `derive(Clone, PartialEq);`).
+/// The syntax for using this macro is the following:\
+/// ```text
+/// wrap_columns! {
+///     target = $type$;
+///     table = $path_to_table_module$;
+///     columns = [Nullable] { $field$: $type$ => $column$, ... };
+///     $module_visability$ mod $name_of_wrappers_module$;
+///     [derive($derive$, ...);]
+/// }
+/// ```
+///
+/// # Example:
+#[cfg_attr(feature = "sqlite", doc = "```")]
+#[cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+/// # use diesel::prelude::*;
+/// mod schema {
+///     diesel::table! {
+///         places (id) {
+///             id -> Integer,
+///             latitude -> Double,
+///             longitude -> Double,
+///             entrance_lat -> Nullable<Double>,
+///             entrance_lon -> Nullable<Double>,
+///         }
+///     }
+/// }
+/// use diesel_as_wrap::wrap_columns;
+///
+/// #[derive(Debug, Clone, PartialEq)]
+/// pub struct GeoPoint {
+///     lat: f64,
+///     lon: f64,
+/// }
+///
+/// wrap_columns! {
+///     target = crate::GeoPoint;
+///     table = crate::schema::places;
+///     columns = { lat: f64 => latitude, lon: f64 => longitude };
+///     pub mod location;
+///     derive(Clone, PartialEq);
+/// }
+///
+/// wrap_columns! {
+///     target = crate::GeoPoint;
+///     table = crate::schema::places;
+///     columns = Nullable { lat: f64 => entrance_lat, lon: f64 => entrance_lon };
+///     pub mod entrance;
+///     derive(Clone, PartialEq);
+/// }
+///
+/// #[derive(Debug, Clone, PartialEq, Queryable, Selectable, Insertable)]
+/// #[diesel(table_name = schema::places)]
+/// pub struct Place {
+///     id: i32,
+///     #[diesel(embed)]
+///     location: location::As,
+///     #[diesel(embed)]
+///     entrance: entrance::AsOption,
+/// }
+///
+/// # fn main() {
+/// let mut conn = SqliteConnection::establish(":memory:").unwrap();
+/// diesel::sql_query(
+///     "CREATE TABLE places (id INTEGER PRIMARY KEY, latitude DOUBLE NOT NULL, longitude DOUBLE NOT NULL, \
+///      entrance_lat DOUBLE, entrance_lon DOUBLE)",
+/// )
+/// .execute(&mut conn)
+/// .unwrap();
+///
+/// let places = vec![
+///     Place {
+///         id: 1,
+///         location: GeoPoint { lat: 47.3769, lon: 8.5417 }.into(),
+///         entrance: Some(GeoPoint { lat: 47.3771, lon: 8.5419 }).into(),
+///     },
+///     Place { id: 2, location: GeoPoint { lat: -33.8688, lon: 151.2093 }.into(), entrance: None.into() },
+/// ];
+/// diesel::insert_into(schema::places::table).values(places.clone()).execute(&mut conn).unwrap();
+///
+/// let stored: (f64, f64) = schema::places::table
+///     .select((schema::places::latitude, schema::places::longitude))
+///     .first(&mut conn)
+///     .unwrap();
+/// assert_eq!(stored, (47.3769, 8.5417));
+/// let loaded: Vec<Place> = schema::places::table.select(Place::as_select()).load(&mut conn).unwrap();
+/// assert_eq!(loaded, places);
+/// assert_eq!(loaded[0].location.lat, 47.3769);
+///
+/// diesel::sql_query("UPDATE places SET entrance_lat = 0 WHERE id = 2").execute(&mut conn).unwrap();
+/// let error = schema::places::table.select(Place::as_select()).load(&mut conn).unwrap_err();
+/// assert!(error.to_string().contains("are only partially NULL"));
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! wrap_columns {
+    (
+        target = $target:ty;
+        table = $table:path;
+        columns = Nullable { $($field:ident: $type:ty => $column:ident),+ $(,)? };
+        $vis:vis mod $module:ident;
+        $(derive($($derive:path),* $(,)?);)?
+    ) => {
+        $crate::wrap_columns! { @module AsOption Option<Target>; $target; $table; [$($field: $type => $column),+]; $vis $module; [$($($derive),*)?] }
+    };
+
+    (
+        target = $target:ty;
+        table = $table:path;
+        columns = { $($field:ident: $type:ty => $column:ident),+ $(,)? };
+        $vis:vis mod $module:ident;
+        $(derive($($derive:path),* $(,)?);)?
+    ) => {
+        $crate::wrap_columns! { @module As Target; $target; $table; [$($field: $type => $column),+]; $vis $module; [$($($derive),*)?] }
+    };
+
+    (@module $wrapper:ident $inner:ty; $target:ty; $table:path; [$($field:ident: $type:ty => $column:ident),+]; $vis:vis $module:ident; [$($derive:path),*]) => {
+        $vis mod $module {
+            use diesel::backend::Backend;
+            use diesel::deserialize::{FromStaticSqlRow, Queryable, Result as DResult};
+            use diesel::expression::Expression;
+            use diesel::{ExpressionMethods, Insertable, Selectable};
+            use $table as table_module;
+
+            ///The type wrapped by the wrapper.
+            pub type Target = $target;
+
+            ///The columns the wrapper is stored in, in the order of the fields.
+            pub type Columns = ($(table_module::$column,)+);
+
+            ///
+            /// The wrapper of the columns, use it as type of an `#[diesel(embed)]` field.
+            ///
+            #[derive($($derive),*)]
+            pub struct $wrapper(pub $inner);
+
+            impl std::fmt::Debug for $wrapper {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    f.debug_tuple(stringify!($wrapper)).field(&self.0).finish()
+                }
+            }
+
+            impl std::ops::Deref for $wrapper {
+                type Target = $inner;
+
+                fn deref(&self) -> &$inner {
+                    &self.0
+                }
+            }
+
+            impl std::ops::DerefMut for $wrapper {
+                fn deref_mut(&mut self) -> &mut $inner {
+                    &mut self.0
+                }
+            }
+
+            impl From<$inner> for $wrapper {
+                fn from(value: $inner) -> Self {
+                    $wrapper(value)
+                }
+            }
+
+            impl From<$wrapper> for $inner {
+                fn from(value: $wrapper) -> Self {
+                    value.0
+                }
+            }
+
+            impl<DB: Backend> Selectable<DB> for $wrapper {
+                type SelectExpression = Columns;
+
+                fn construct_selection() -> Columns {
+                    ($(table_module::$column,)+)
+                }
+            }
+
+            $crate::wrap_columns! { @impls $wrapper; [$($field: $type => $column),+] }
+        }
+    };
+
+    (@impls As; [$($field:ident: $type:ty => $column:ident),+]) => {
+        impl<DB> Queryable<<Columns as Expression>::SqlType, DB> for As
+        where
+            DB: Backend,
+            ($($type,)+): FromStaticSqlRow<<Columns as Expression>::SqlType, DB>,
+        {
+            type Row = ($($type,)+);
+
+            fn build(($($field,)+): Self::Row) -> DResult<Self> {
+                Ok(As(Target { $($field),+ }))
+            }
+        }
+
+        impl Insertable<table_module::table> for As {
+            type Values = <($(diesel::dsl::Eq<table_module::$column, $type>,)+) as Insertable<table_module::table>>::Values;
+
+            fn values(self) -> Self::Values {
+                let Target { $($field),+ } = self.0;
+                ($(table_module::$column.eq($field),)+).values()
+            }
+        }
+
+        impl<'insert> Insertable<table_module::table> for &'insert As {
+            type Values = <($(diesel::dsl::Eq<table_module::$column, &'insert $type>,)+) as Insertable<table_module::table>>::Values;
+
+            fn values(self) -> Self::Values {
+                let Target { $($field),+ } = &self.0;
+                ($(table_module::$column.eq($field),)+).values()
+            }
+        }
+    };
+
+    (@impls AsOption; [$($field:ident: $type:ty => $column:ident),+]) => {
+        impl<DB> Queryable<<Columns as Expression>::SqlType, DB> for AsOption
+        where
+            DB: Backend,
+            ($(Option<$type>,)+): FromStaticSqlRow<<Columns as Expression>::SqlType, DB>,
+        {
+            type Row = ($(Option<$type>,)+);
+
+            fn build(($($field,)+): Self::Row) -> DResult<Self> {
+                match ($($field,)+) {
+                    ($(Some($field),)+) => Ok(AsOption(Some(Target { $($field),+ }))),
+                    ($(None::<$type>,)+) => Ok(AsOption(None)),
+                    #[allow(unreachable_patterns)]
+                    _ => Err(concat!("the columns ", stringify!($($column),+), " are only partially NULL").into()),
+                }
+            }
+        }
+
+        impl Insertable<table_module::table> for AsOption {
+            type Values = <($(diesel::dsl::Eq<table_module::$column, Option<$type>>,)+) as Insertable<table_module::table>>::Values;
+
+            fn values(self) -> Self::Values {
+                match self.0 {
+                    Some(Target { $($field),+ }) => ($(table_module::$column.eq(Some($field)),)+).values(),
+                    None => ($(table_module::$column.eq(None::<$type>),)+).values(),
+                }
+            }
+        }
+
+        impl<'insert> Insertable<table_module::table> for &'insert AsOption {
+            type Values = <($(diesel::dsl::Eq<table_module::$column, Option<&'insert $type>>,)+) as Insertable<table_module::table>>::Values;
+
+            fn values(self) -> Self::Values {
+                match &self.0 {
+                    Some(Target { $($field),+ }) => ($(table_module::$column.eq(Some($field)),)+).values(),
+                    None => ($(table_module::$column.eq(None::<&'insert $type>),)+).values(),
+                }
+            }
+        }
+    };
+}
@@ -0,0 +1,289 @@
+//! Compression of `Binary` columns with zstd (`zstd` feature) or deflate (`deflate` feature).
+//!
+//! [`AsCompressed<T, C>`](AsCompressed) compresses a [`Payload`] with the [`Codec`] `C` on write and decompresses it on read,
+//! [`wrap_compressed!`](crate::wrap_compressed) creates the wrappers of [`wrap!`](crate::wrap) doing the same for a plain field.
+//! The payload decides how the value becomes bytes, `AsCompressed<AsJson<T>, Zstd>` stores a compressed json document.
+//! AsCompressed is a payload itself, so `AsEncrypted<AsCompressed<T, Zstd>, K>` compresses before encrypting
+//! (encrypted bytes don't compress).\
+//! [`Zstd`] compresses faster and smaller, [`Deflate`] is implemented in pure Rust and readable by zlib.
+//! Short values can grow by a few bytes, it pays off for large text, json and other redundant payloads.
+//! A value that isn't a valid compressed stream of the codec produces a deserialization error,
+//! as does a value decompressing to more than [`Codec::MAX_DECOMPRESSED_LEN`] bytes
+//! (64 MiB by default, `Zstd<{ 1 << 30 }>` raises it to 1 GiB), so a small crafted value can't exhaust the memory.
+//!
+//! # Example:
+#![cfg_attr(all(feature = "sqlite", feature = "zstd", feature = "deflate"), doc = "```")]
+#![cfg_attr(not(all(feature = "sqlite", feature = "zstd", feature = "deflate")), doc = "```ignore")]
+//! # use diesel::prelude::*;
+//! # mod schema {
+//! #     diesel::table! {
+//! #         articles (id) {
+//! #             id -> Integer,
+//! #             body -> Binary,
+//! #             draft -> Nullable<Binary>,
+//! #         }
+//! #     }
+//! # }
+//! use diesel_as_wrap::compressed::{AsCompressed, Deflate, Zstd};
+//! use diesel_as_wrap::wrap_compressed;
+//!
+//! wrap_compressed! {
+//!     target = String;
+//!     codec = diesel_as_wrap::compressed::Deflate;
+//!     pub mod text_deflate;
+//! }
+//!
+//! #[derive(Debug, Clone, PartialEq, Queryable, Selectable, Insertable)]
+//! #[diesel(table_name = schema::articles)]
+//! pub struct Article {
+//!     id: i32,
+//!     body: AsCompressed<String, Zstd>,
+//!     #[diesel(deserialize_as = text_deflate::AsOption)]
+//!     #[diesel(serialize_as = text_deflate::AsOption)]
+//!     draft: Option<String>,
+//! }
+//!
+//! # fn main() {
+//! let mut conn = SqliteConnection::establish(":memory:").unwrap();
+//! diesel::sql_query("CREATE TABLE articles (id INTEGER PRIMARY KEY, body BLOB NOT NULL, draft BLOB)")
+//!     .execute(&mut conn)
+//!     .unwrap();
+//!
+//! let body = "All work and no play makes Jack a dull boy. ".repeat(100);
+//! let articles = vec![
+//!     Article { id: 1, body: AsCompressed::new(body.clone()), draft: Some(body.replace("Jack", "Jill")) },
+//!     Article { id: 2, body: AsCompressed::new(String::new()), draft: None },
+//! ];
+//! diesel::insert_into(schema::articles::table).values(articles.clone()).execute(&mut conn).unwrap();
+//!
+//! let stored: (Vec<u8>, Option<Vec<u8>>) =
+//!     schema::articles::table.select((schema::articles::body, schema::articles::draft)).first(&mut conn).unwrap();
+//! assert!(stored.0.len() < body.len() / 20);
+//! assert!(stored.1.unwrap().len() < body.len() / 20);
+//! let loaded: Vec<Article> = schema::articles::table.select(Article::as_select()).load(&mut conn).unwrap();
+//! assert_eq!(loaded, articles);
+//!
+//! diesel::sql_query("UPDATE articles SET body = x'0102030405' WHERE id = 2").execute(&mut conn).unwrap();
+//! let error = schema::articles::table.select(Article::as_select()).load(&mut conn).unwrap_err();
+//! assert!(error.to_string().contains("could not be decompressed with zstd"));
+//!
+//! // 64 KiB of zeros compress to a few bytes, but decompress to more than the 1 KiB allowed
+//! let zeros: AsCompressed<Vec<u8>, Deflate<1024>> = AsCompressed::new(vec![0; 64 * 1024]);
+//! let stored: Vec<u8> = diesel::select(zeros.into_sql::<diesel::sql_types::Binary>()).get_result(&mut conn).unwrap();
+//! assert!(stored.len() < 1024);
+//! diesel::update(schema::articles::table).set(schema::articles::body.eq(stored)).execute(&mut conn).unwrap();
+//! let error = schema::articles::table.select(schema::articles::body).load::<AsCompressed<Vec<u8>, Deflate<1024>>>(&mut conn).unwrap_err();
+//! assert!(error.to_string().contains("decompresses to more than 1024 bytes"));
+//! # }
+//! ```
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use diesel::backend::Backend;
+use diesel::deserialize::{self, FromSql};
+use diesel::serialize::{self, Output, ToSql};
+use diesel::sql_types::Binary;
+
+use crate::{FillError, Payload, ToSqlBuffered};
+
+/// The default of [`Codec::MAX_DECOMPRESSED_LEN`] and of the limit of [`Zstd`] and [`Deflate`], 64 MiB.
+pub const DEFAULT_MAX_DECOMPRESSED_LEN: usize = 64 * 1024 * 1024;
+
+///
+/// A compression algorithm used by [`AsCompressed`].
+///
+pub trait Codec {
+    /// The most bytes a value may decompress to, [`decompress`](Codec::decompress) fails for larger values.
+    const MAX_DECOMPRESSED_LEN: usize = DEFAULT_MAX_DECOMPRESSED_LEN;
+
+    /// Appends the compressed payload to the buffer.
+    fn compress(payload: &[u8], buffer: &mut Vec<u8>) -> Result<(), FillError>;
+
+    /// Decompresses bytes written by [`compress`](Codec::compress),
+    /// failing if they decompress to more than [`MAX_DECOMPRESSED_LEN`](Codec::MAX_DECOMPRESSED_LEN) bytes.
+    fn decompress(bytes: &[u8]) -> deserialize::Result<Vec<u8>>;
+}
+
+/// Reads at most `max_len` bytes from the decoder, never allocating more than that for a crafted value.
+fn read_limited(decoder: std::io::Result<impl std::io::Read>, max_len: usize, codec: &str) -> deserialize::Result<Vec<u8>> {
+    use std::io::Read;
+    let mut payload = Vec::new();
+    decoder
+        .and_then(|decoder| decoder.take(max_len as u64 + 1).read_to_end(&mut payload))
+        .map_err(|e| format!("the value could not be decompressed with {codec}: {e}"))?;
+    if payload.len() > max_len {
+        return Err(format!("the value decompresses to more than {max_len} bytes").into());
+    }
+    Ok(payload)
+}
+
+///
+/// Zstandard at its default level 3, as a single zstd frame.\
+/// Values decompress to at most `MAX_LEN` bytes, [`DEFAULT_MAX_DECOMPRESSED_LEN`] unless given.
+///
+#[cfg(feature = "zstd")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Zstd<const MAX_LEN: usize = DEFAULT_MAX_DECOMPRESSED_LEN>;
+
+#[cfg(feature = "zstd")]
+impl<const MAX_LEN: usize> Codec for Zstd<MAX_LEN> {
+    const MAX_DECOMPRESSED_LEN: usize = MAX_LEN;
+
+    fn compress(payload: &[u8], buffer: &mut Vec<u8>) -> Result<(), FillError> {
+        ::zstd::stream::copy_encode(payload, buffer, ::zstd::DEFAULT_COMPRESSION_LEVEL)?;
+        Ok(())
+    }
+
+    fn decompress(bytes: &[u8]) -> deserialize::Result<Vec<u8>> {
+        read_limited(::zstd::stream::read::Decoder::with_buffer(bytes), MAX_LEN, "zstd")
+    }
+}
+
+///
+/// Deflate at the default level 6, in the zlib format (a two byte header and an Adler-32 checksum).\
+/// Values decompress to at most `MAX_LEN` bytes, [`DEFAULT_MAX_DECOMPRESSED_LEN`] unless given.
+///
+#[cfg(feature = "deflate")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Deflate<const MAX_LEN: usize = DEFAULT_MAX_DECOMPRESSED_LEN>;
+
+#[cfg(feature = "deflate")]
+impl<const MAX_LEN: usize> Codec for Deflate<MAX_LEN> {
+    const MAX_DECOMPRESSED_LEN: usize = MAX_LEN;
+
+    fn compress(payload: &[u8], buffer: &mut Vec<u8>) -> Result<(), FillError> {
+        use std::io::Write;
+        let mut encoder = ::flate2::write::ZlibEncoder::new(buffer, ::flate2::Compression::default());
+        encoder.write_all(payload)?;
+        encoder.finish()?;
+        Ok(())
+    }
+
+    fn decompress(bytes: &[u8]) -> deserialize::Result<Vec<u8>> {
+        read_limited(Ok(::flate2::read::ZlibDecoder::new(bytes)), MAX_LEN, "deflate")
+    }
+}
+
+///
+/// A [`Payload`] stored compressed with the codec `C` in `Binary` columns, on every backend.\
+/// Use it as type of the field, `Option<AsCompressed<T, C>>` for nullable columns, or create wrappers for
+/// `deserialize_as` and `serialize_as` with [`wrap_compressed!`](crate::wrap_compressed) to keep the plain type in the struct.\
+/// It is `repr(transparent)`, so a value can be borrowed as AsCompressed with [`AsCompressed::from_ref`].
+/// See the [module documentation](crate::compressed) for an example.
+///
+#[derive(diesel::deserialize::FromSqlRow, diesel::expression::AsExpression)]
+#[diesel(sql_type = Binary)]
+#[repr(transparent)]
+pub struct AsCompressed<T, C>(pub T, PhantomData<fn() -> C>);
+
+impl<T, C> AsCompressed<T, C> {
+    /// Wraps the value.
+    pub fn new(value: T) -> Self {
+        AsCompressed(value, PhantomData)
+    }
+
+    /// Borrows the value as AsCompressed without moving or cloning it.
+    pub fn from_ref(value: &T) -> &Self {
+        // SAFETY: AsCompressed is repr(transparent) over the value.
+        unsafe { &*(value as *const T as *const Self) }
+    }
+}
+
+impl<T, C> From<T> for AsCompressed<T, C> {
+    fn from(value: T) -> Self {
+        AsCompressed::new(value)
+    }
+}
+
+impl<T: Clone, C> Clone for AsCompressed<T, C> {
+    fn clone(&self) -> Self {
+        AsCompressed::new(self.0.clone())
+    }
+}
+
+impl<T: PartialEq, C> PartialEq for AsCompressed<T, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T: Eq, C> Eq for AsCompressed<T, C> {}
+
+impl<T: fmt::Debug, C> fmt::Debug for AsCompressed<T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("AsCompressed").field(&self.0).finish()
+    }
+}
+
+impl<T: Payload, C: Codec> Payload for AsCompressed<T, C> {
+    fn write_payload(&self, buffer: &mut Vec<u8>) -> Result<(), FillError> {
+        let mut payload = Vec::new();
+        self.0.write_payload(&mut payload)?;
+        C::compress(&payload, buffer)
+    }
+
+    fn read_payload(bytes: Vec<u8>) -> deserialize::Result<Self> {
+        T::read_payload(C::decompress(&bytes)?).map(AsCompressed::new)
+    }
+}
+
+impl<T, C, B> FromSql<Binary, B> for AsCompressed<T, C>
+where
+    B: Backend,
+    T: Payload,
+    C: Codec,
+    *const [u8]: FromSql<Binary, B>,
+{
+    fn from_sql(bytes: B::RawValue<'_>) -> deserialize::Result<Self> {
+        let bytes = <*const [u8] as FromSql<Binary, B>>::from_sql(bytes)?;
+        // SAFETY: diesel guarantees the pointer to be valid for the duration of `from_sql`.
+        let bytes = unsafe { &*bytes };
+        T::read_payload(C::decompress(bytes)?).map(AsCompressed::new)
+    }
+}
+
+impl<T, C, B> ToSql<Binary, B> for AsCompressed<T, C>
+where
+    B: Backend,
+    T: Payload + fmt::Debug,
+    C: Codec,
+    Vec<u8>: ToSqlBuffered<Binary, B>,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, B>) -> serialize::Result {
+        Vec::<u8>::to_sql_buffered(out, |buffer| self.write_payload(buffer))
+    }
+}
+
+///
+/// This macro creates the wrappers of [`wrap!`](crate::wrap) for a [`Payload`] stored compressed in a `Binary` column,
+/// the `sql_type`, `to_sql` and `from_sql` sections are generated and compress the value with [`AsCompressed`]
+/// and the given [`Codec`].\
+/// The `target` and `codec` sections have to come first, all other sections of [`wrap!`](crate::wrap) can follow in any order.
+/// Requires the `zstd` or `deflate` feature, see the [module documentation](crate::compressed) for an example.
+/// The syntax for using this macro is the following:\
+/// ```text
+/// wrap_compressed! {
+///     target = $type_to_wrap$;
+///     codec = $codec$;
+///     $module_visability$ mod $name_of_wrappers_module$;
+///     [more sections of wrap!]
+/// }
+/// ```
+///
+#[macro_export]
+macro_rules! wrap_compressed {
+    (target = $target:ty; codec = $codec:ty; $($rest:tt)*) => {
+        $crate::wrap! {
+            target = $target;
+            sql_type = Binary;
+            $($rest)*
+            fn to_sql<$crate::compressed::AsCompressed<Target, $codec>>(self, out) {
+                <$crate::compressed::AsCompressed<Target, $codec>>::from_ref(&self.0).to_sql(out)
+            }
+            fn from_sql<$crate::compressed::AsCompressed<Target, $codec>>(bytes) {
+                <$crate::compressed::AsCompressed<Target, $codec>>::from_sql(bytes).map(|value| As(value.0))
+            }
+        }
+    };
+}
@@ -0,0 +1,27 @@
+use diesel::backend::Backend;
+
+///
+/// Backends that can render a bound value as sql literal.\
+/// Used by the `as_sql_debug` method of the As types created by [`wrap!`](crate::wrap), which binds the serialized value into
+/// `SELECT <QUOTE_FUNCTION>(?)` so the database itself shows how it sees the value.
+/// Implemented for the backends enabled by the `postgres`, `mysql` and `sqlite` features.
+///
+pub trait QuoteLiteral: Backend {
+    /// The sql function quoting its argument as literal.
+    const QUOTE_FUNCTION: &'static str;
+}
+
+#[cfg(feature = "postgres")]
+impl QuoteLiteral for diesel::pg::Pg {
+    const QUOTE_FUNCTION: &'static str = "quote_nullable";
+}
+
+#[cfg(feature = "mysql")]
+impl QuoteLiteral for diesel::mysql::Mysql {
+    const QUOTE_FUNCTION: &'static str = "QUOTE";
+}
+
+#[cfg(feature = "sqlite")]
+impl QuoteLiteral for diesel::sqlite::Sqlite {
+    const QUOTE_FUNCTION: &'static str = "quote";
+}
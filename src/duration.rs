@@ -0,0 +1,181 @@
+//! Wrappers for [`std::time::Duration`].
+//!
+//! The integer modules store a `Duration` inside a `BigInt` column, on every backend.
+//! The precision is selected by picking the module:
+//! - [`millis`] stores milliseconds (sub millisecond parts are truncated).
+//! - [`micros`] stores microseconds (sub microsecond parts are truncated).
+//!
+//! Durations that don't fit into an `i64` produce a serialization error, negative values a deserialization error.
+//!
+//! [`interval`] maps a `Duration` to the native Postgres `Interval` type (requires the `postgres` feature).
+//! Postgres intervals consist of months, days and microseconds.
+//! When reading, days are counted as 24 hours, every 12 months as 365.25 days and the remaining months as 30 days,
+//! the same way `EXTRACT(EPOCH FROM ...)` does it (`interval '1 year'` is read as 31557600 seconds).
+//! Intervals with negative parts are read as their sum, only a negative sum can't be represented.
+//! Negative intervals and durations that don't fit into the microseconds of an interval produce a (de)serialization error.
+//! Sub microsecond parts of a `Duration` are truncated.
+//!
+//! # Example:
+#![cfg_attr(feature = "postgres", doc = "```")]
+#![cfg_attr(not(feature = "postgres"), doc = "```ignore")]
+//! # use diesel::prelude::*;
+//! # mod schema {
+//! #     diesel::table! {
+//! #         jobs (id) {
+//! #             id -> Integer,
+//! #             timeout -> Interval,
+//! #             retry_after -> Nullable<Interval>,
+//! #         }
+//! #     }
+//! # }
+//! use std::time::Duration;
+//! use diesel_as_wrap::duration::interval;
+//!
+//! #[derive(Debug, Queryable, Selectable, Insertable)]
+//! #[diesel(table_name = schema::jobs)]
+//! #[diesel(check_for_backend(diesel::pg::Pg))]
+//! pub struct Job {
+//!     id: i32,
+//!     #[diesel(deserialize_as = interval::As)]
+//!     #[diesel(serialize_as = interval::As)]
+//!     timeout: Duration,
+//!     #[diesel(deserialize_as = interval::AsOption)]
+//!     #[diesel(serialize_as = interval::AsOption)]
+//!     retry_after: Option<Duration>,
+//! }
+//! ```
+//!
+//! The months and days of intervals read from the database:
+#![cfg_attr(feature = "postgres", doc = "```")]
+#![cfg_attr(not(feature = "postgres"), doc = "```ignore")]
+//! # use diesel::deserialize::FromSql;
+//! # use diesel::pg::{Pg, PgMetadataLookup, PgTypeMetadata, PgValue};
+//! # use diesel::query_builder::bind_collector::RawBytesBindCollector;
+//! # use diesel::query_builder::BindCollector;
+//! # use diesel::serialize::ToSql;
+//! # use diesel::sql_types::HasSqlType;
+//! # struct Types;
+//! # impl PgMetadataLookup for Types {
+//! #     fn lookup_type(&mut self, _: &str, _: Option<&str>) -> PgTypeMetadata {
+//! #         PgTypeMetadata::new(100_000, 100_001)
+//! #     }
+//! # }
+//! # fn encode<ST, T: ToSql<ST, Pg>>(value: &T) -> Option<Vec<u8>> where Pg: HasSqlType<ST> {
+//! #     let mut collector = RawBytesBindCollector::<Pg>::new();
+//! #     collector.push_bound_value::<ST, T>(value, &mut Types).unwrap();
+//! #     collector.binds.pop().unwrap()
+//! # }
+//! # fn decode<ST, T: FromSql<ST, Pg>>(bytes: Option<&[u8]>) -> diesel::deserialize::Result<T> where Pg: HasSqlType<ST> {
+//! #     let oid = std::num::NonZeroU32::new(<Pg as HasSqlType<ST>>::metadata(&mut Types).oid().unwrap()).unwrap();
+//! #     T::from_nullable_sql(bytes.map(|bytes| PgValue::new(bytes, &oid)))
+//! # }
+//! use std::time::Duration;
+//! use diesel::pg::data_types::PgInterval;
+//! use diesel::sql_types::Interval;
+//! use diesel_as_wrap::duration::interval;
+//!
+//! const DAY: u64 = 86_400;
+//! let read = |months, days, microseconds| {
+//!     let bytes = encode::<Interval, _>(&PgInterval::new(microseconds, days, months)).unwrap();
+//!     decode::<Interval, interval::As>(Some(&bytes)).map(|read| read.0)
+//! };
+//!
+//! // interval '1 year', '1 year 1 mon', '1 day 2 hours'
+//! assert_eq!(read(12, 0, 0).unwrap(), Duration::from_secs(31_557_600));
+//! assert_eq!(read(13, 0, 0).unwrap(), Duration::from_secs(31_557_600 + 30 * DAY));
+//! assert_eq!(read(0, 1, 7_200_000_000).unwrap(), Duration::from_secs(DAY + 7_200));
+//! // interval '1 mon -1 day', '1 day -1 hour' and '-1 year -1 mon 400 days' are positive
+//! assert_eq!(read(1, -1, 0).unwrap(), Duration::from_secs(29 * DAY));
+//! assert_eq!(read(-13, 400, 0).unwrap(), Duration::from_secs(410_400));
+//! assert_eq!(read(0, 1, -3_600_000_000).unwrap(), Duration::from_secs(DAY - 3_600));
+//!
+//! let negative = read(0, -1, 0).unwrap_err();
+//! assert_eq!(negative.to_string(), "negative intervals can't be represented as a duration");
+//! let negative = read(-13, 0, 0).unwrap_err();
+//! assert_eq!(negative.to_string(), "negative intervals can't be represented as a duration");
+//! let overflow = read(i32::MAX, i32::MAX, i64::MAX).unwrap_err();
+//! assert_eq!(overflow.to_string(), "interval overflows a duration");
+//!
+//! // a duration is written as microseconds only
+//! let bytes = encode::<Interval, _>(&interval::As(Duration::new(90, 123_456_789))).unwrap();
+//! assert_eq!(decode::<Interval, PgInterval>(Some(&bytes)).unwrap(), PgInterval::new(90_123_456, 0, 0));
+//! ```
+//!
+//! The integer encodings:
+#![cfg_attr(feature = "sqlite", doc = "```")]
+#![cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+//! use std::time::Duration;
+//! use diesel::prelude::*;
+//! use diesel::sql_types::BigInt;
+//! use diesel_as_wrap::duration::{micros, millis};
+//!
+//! let mut conn = SqliteConnection::establish(":memory:").unwrap();
+//!
+//! let timeout = Duration::new(90, 123_456_789);
+//! let stored: i64 = diesel::select(millis::As(timeout).into_sql::<BigInt>()).get_result(&mut conn).unwrap();
+//! assert_eq!(stored, 90_123);
+//! let stored: i64 = diesel::select(micros::As(timeout).into_sql::<BigInt>()).get_result(&mut conn).unwrap();
+//! assert_eq!(stored, 90_123_456);
+//! let loaded: micros::As = diesel::select(stored.into_sql::<BigInt>()).get_result(&mut conn).unwrap();
+//! assert_eq!(loaded.0, Duration::from_micros(90_123_456));
+//!
+//! let negative = diesel::select((-1_i64).into_sql::<BigInt>()).get_result::<millis::As>(&mut conn);
+//! assert!(negative.unwrap_err().to_string().ends_with("-1ms can't be represented as a duration"));
+//! ```
+
+crate::wrap! {
+    target = ::std::time::Duration;
+    sql_type = BigInt;
+    pub mod millis;
+    fn into_sql<i64>(self) {
+        i64::try_from(self.0.as_millis()).map_err(|_| format!("duration {:?} overflows i64 milliseconds", self.0))?
+    }
+    fn from_sql<i64>(bytes) {
+        let millis = i64::from_sql(bytes)?;
+        u64::try_from(millis)
+            .map(|millis| As(::std::time::Duration::from_millis(millis)))
+            .map_err(|_| format!("{millis}ms can't be represented as a duration").into())
+    }
+}
+
+crate::wrap! {
+    target = ::std::time::Duration;
+    sql_type = BigInt;
+    pub mod micros;
+    fn into_sql<i64>(self) {
+        i64::try_from(self.0.as_micros()).map_err(|_| format!("duration {:?} overflows i64 microseconds", self.0))?
+    }
+    fn from_sql<i64>(bytes) {
+        let micros = i64::from_sql(bytes)?;
+        u64::try_from(micros)
+            .map(|micros| As(::std::time::Duration::from_micros(micros)))
+            .map_err(|_| format!("{micros}µs can't be represented as a duration").into())
+    }
+}
+
+#[cfg(feature = "postgres")]
+crate::wrap! {
+    target = ::std::time::Duration;
+    sql_type = Interval;
+    pub mod interval;
+    fn into_sql<::diesel::pg::data_types::PgInterval>(self) {
+        let microseconds = i64::try_from(self.0.as_micros())
+            .map_err(|_| format!("duration {:?} overflows an interval", self.0))?;
+        ::diesel::pg::data_types::PgInterval::new(microseconds, 0, 0)
+    }
+    fn from_sql<::diesel::pg::data_types::PgInterval>(bytes) {
+        const MICROS_PER_DAY: i64 = 86_400_000_000;
+        // 365.25 days, like the `DAYS_PER_YEAR` of Postgres.
+        const MICROS_PER_YEAR: i64 = 365 * MICROS_PER_DAY + MICROS_PER_DAY / 4;
+        let interval = <::diesel::pg::data_types::PgInterval>::from_sql(bytes)?;
+        let months = i64::from(interval.months);
+        let microseconds = (months / 12)
+            .checked_mul(MICROS_PER_YEAR)
+            .and_then(|micros| micros.checked_add((months % 12 * 30 + i64::from(interval.days)).checked_mul(MICROS_PER_DAY)?))
+            .and_then(|micros| micros.checked_add(interval.microseconds))
+            .ok_or("interval overflows a duration")?;
+        u64::try_from(microseconds)
+            .map(|micros| As(::std::time::Duration::from_micros(micros)))
+            .map_err(|_| "negative intervals can't be represented as a duration".into())
+    }
+}
@@ -0,0 +1,78 @@
+//! Wrappers for [`email_address::EmailAddress`](::email_address::EmailAddress).
+//!
+//! [`text`] stores an email address inside a `Text` column exactly as it was parsed (`EmailAddress::as_str`).
+//! The address is validated in both directions:
+//! reading text that isn't a valid address produces a deserialization error containing the text and the reason,
+//! instead of a value that fails later in the domain layer.
+//! Writing validates the address again, so addresses created with `EmailAddress::new_unchecked` can't be stored
+//! if they aren't valid and produce a serialization error instead.
+//!
+//! # Example:
+#![cfg_attr(feature = "sqlite", doc = "```")]
+#![cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+//! # use diesel::prelude::*;
+//! # mod schema {
+//! #     diesel::table! {
+//! #         users (id) {
+//! #             id -> Integer,
+//! #             email -> Text,
+//! #             recovery_email -> Nullable<Text>,
+//! #         }
+//! #     }
+//! # }
+//! use email_address::EmailAddress;
+//! use diesel_as_wrap::email::text;
+//!
+//! #[derive(Debug, Clone, PartialEq, Queryable, Selectable, Insertable)]
+//! #[diesel(table_name = schema::users)]
+//! pub struct User {
+//!     id: i32,
+//!     #[diesel(deserialize_as = text::As)]
+//!     #[diesel(serialize_as = text::As)]
+//!     email: EmailAddress,
+//!     #[diesel(deserialize_as = text::AsOption)]
+//!     #[diesel(serialize_as = text::AsOption)]
+//!     recovery_email: Option<EmailAddress>,
+//! }
+//!
+//! # fn main() {
+//! let mut conn = SqliteConnection::establish(":memory:").unwrap();
+//! diesel::sql_query("CREATE TABLE users (id INTEGER PRIMARY KEY, email TEXT NOT NULL, recovery_email TEXT)")
+//!     .execute(&mut conn)
+//!     .unwrap();
+//!
+//! let users = vec![
+//!     User { id: 1, email: "ada@example.com".parse().unwrap(), recovery_email: Some("ada@example.org".parse().unwrap()) },
+//!     User { id: 2, email: "grace@example.com".parse().unwrap(), recovery_email: None },
+//! ];
+//! diesel::insert_into(schema::users::table).values(users.clone()).execute(&mut conn).unwrap();
+//! let loaded: Vec<User> = schema::users::table.select(User::as_select()).load(&mut conn).unwrap();
+//! assert_eq!(loaded, users);
+//!
+//! let unchecked = User { id: 3, email: EmailAddress::new_unchecked("nobody"), recovery_email: None };
+//! let error = diesel::insert_into(schema::users::table).values(unchecked).execute(&mut conn).unwrap_err();
+//! assert!(error.to_string().contains(r#""nobody" is not a valid email address"#));
+//!
+//! diesel::sql_query("UPDATE users SET email = 'grace.example.com' WHERE id = 2").execute(&mut conn).unwrap();
+//! let error = schema::users::table.select(User::as_select()).load(&mut conn).unwrap_err();
+//! assert!(error.to_string().contains(r#""grace.example.com" is not a valid email address"#));
+//! # }
+//! ```
+
+crate::wrap! {
+    target = ::email_address::EmailAddress;
+    sql_type = Text;
+    pub mod text;
+    fn to_sql<str>(self, out) {
+        let text: &str = self.0.as_str();
+        if let Err(e) = text.parse::<::email_address::EmailAddress>() {
+            return Err(format!("{text:?} is not a valid email address: {e}").into());
+        }
+        text.to_sql(out)
+    }
+    fn from_sql<&str>(bytes) {
+        bytes.parse()
+            .map(As)
+            .map_err(|e| format!("{bytes:?} is not a valid email address: {e}").into())
+    }
+}
@@ -0,0 +1,765 @@
+//! Application level encryption of `Binary` columns with XChaCha20-Poly1305.
+//!
+//! [`AsEncrypted<T, K>`](AsEncrypted) encrypts a [`Payload`] on write and decrypts it on read,
+//! [`wrap_encrypted!`](crate::wrap_encrypted) creates the wrappers of [`wrap!`](crate::wrap) doing the same for a plain field.
+//! The keys are supplied by a [`KeyProvider`], typically loaded once from a secret manager or the environment.
+//!
+//! Every value is encrypted with a fresh random nonce and stored as
+//! `key id (4 bytes, big endian) | nonce (24 bytes) | ciphertext | tag (16 bytes)`, [`OVERHEAD`] bytes more than the payload.
+//! The key id is authenticated as well and selects the key on read, so keys are rotated by changing the current key
+//! while the old keys stay available until every value was rewritten.
+//! A modified value, or one encrypted with another key of the same id, produces a deserialization error.\
+//! The ciphertext isn't bound to its row or column, the database can still swap encrypted values of the same key between them.
+//! Encrypted values can't be compared, ordered or searched by the database, and equal values have different ciphertexts.
+//! Equality lookups are possible with a [`BlindIndex`] in a sibling column or the opt-in [`Deterministic`] mode.
+//!
+//! # Example:
+#![cfg_attr(feature = "sqlite", doc = "```")]
+#![cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+//! # use diesel::prelude::*;
+//! # mod schema {
+//! #     diesel::table! {
+//! #         patients (id) {
+//! #             id -> Integer,
+//! #             ssn -> Binary,
+//! #             weight_kg -> Nullable<Binary>,
+//! #         }
+//! #     }
+//! # }
+//! use std::sync::atomic::{AtomicU32, Ordering};
+//! use diesel_as_wrap::encrypted::{AsEncrypted, KeyProvider, OVERHEAD};
+//! use diesel_as_wrap::{wrap_encrypted, AsText, FillError};
+//!
+//! static CURRENT_KEY: AtomicU32 = AtomicU32::new(1);
+//!
+//! pub struct AppKeys;
+//!
+//! impl KeyProvider for AppKeys {
+//!     fn current_key() -> Result<(u32, [u8; 32]), FillError> {
+//!         let id = CURRENT_KEY.load(Ordering::Relaxed);
+//!         Ok((id, Self::key(id)?))
+//!     }
+//!
+//!     fn key(id: u32) -> Result<[u8; 32], FillError> {
+//!         // a real application loads its keys from a secret manager
+//!         match id {
+//!             1 => Ok([0x11; 32]),
+//!             2 => Ok([0x22; 32]),
+//!             _ => Err(format!("unknown key {id}").into()),
+//!         }
+//!     }
+//! }
+//!
+//! wrap_encrypted! {
+//!     target = String;
+//!     key = crate::AppKeys;
+//!     pub mod ssn_encrypted;
+//! }
+//!
+//! #[derive(Debug, Clone, PartialEq, Queryable, Selectable, Insertable)]
+//! #[diesel(table_name = schema::patients)]
+//! pub struct Patient {
+//!     id: i32,
+//!     #[diesel(deserialize_as = ssn_encrypted::As)]
+//!     #[diesel(serialize_as = ssn_encrypted::As)]
+//!     ssn: String,
+//!     weight_kg: Option<AsEncrypted<AsText<f32>, AppKeys>>,
+//! }
+//!
+//! # fn main() {
+//! let mut conn = SqliteConnection::establish(":memory:").unwrap();
+//! diesel::sql_query("CREATE TABLE patients (id INTEGER PRIMARY KEY, ssn BLOB NOT NULL, weight_kg BLOB)")
+//!     .execute(&mut conn)
+//!     .unwrap();
+//!
+//! let patients = vec![
+//!     Patient { id: 1, ssn: "123-45-6789".to_string(), weight_kg: Some(AsEncrypted::new(AsText(72.5))) },
+//!     Patient { id: 2, ssn: "987-65-4321".to_string(), weight_kg: None },
+//! ];
+//! diesel::insert_into(schema::patients::table).values(patients.clone()).execute(&mut conn).unwrap();
+//!
+//! let stored: Vec<u8> = schema::patients::table.select(schema::patients::ssn).first(&mut conn).unwrap();
+//! assert_eq!(stored.len(), "123-45-6789".len() + OVERHEAD);
+//! assert_eq!(stored[..4], 1_u32.to_be_bytes());
+//! assert!(!stored.windows(11).any(|window| window == b"123-45-6789"));
+//!
+//! // rotating the key keeps the values encrypted with the old key readable
+//! CURRENT_KEY.store(2, Ordering::Relaxed);
+//! let patient = Patient { id: 3, ssn: "555-12-3456".to_string(), weight_kg: None };
+//! diesel::insert_into(schema::patients::table).values(patient.clone()).execute(&mut conn).unwrap();
+//! let stored: Vec<u8> = schema::patients::table.find(3).select(schema::patients::ssn).first(&mut conn).unwrap();
+//! assert_eq!(stored[..4], 2_u32.to_be_bytes());
+//! let loaded: Vec<Patient> = schema::patients::table.select(Patient::as_select()).load(&mut conn).unwrap();
+//! assert_eq!(loaded[..2], patients);
+//! assert_eq!(loaded[2], patient);
+//!
+//! diesel::sql_query("UPDATE patients SET ssn = ssn || x'00' WHERE id = 2").execute(&mut conn).unwrap();
+//! let error = schema::patients::table.select(Patient::as_select()).load(&mut conn).unwrap_err();
+//! assert!(error.to_string().contains("could not be decrypted with key 1"));
+//! # }
+//! ```
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use aes_siv::siv::Aes256Siv;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{AeadInPlace, KeyInit, OsRng};
+use chacha20poly1305::{Key, Tag, XChaCha20Poly1305, XNonce};
+use diesel::backend::Backend;
+use diesel::deserialize::{self, FromSql};
+use diesel::serialize::{self, Output, ToSql};
+use diesel::sql_types::Binary;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::{FillError, Payload, ToSqlBuffered};
+
+const KEY_ID_LEN: usize = 4;
+const NONCE_LEN: usize = 24;
+const TAG_LEN: usize = 16;
+const HEADER_LEN: usize = KEY_ID_LEN + NONCE_LEN;
+
+/// The number of bytes a value encrypted in the default [`Randomized`] mode is larger than its payload.
+pub const OVERHEAD: usize = HEADER_LEN + TAG_LEN;
+
+///
+/// Supplies the 256 bit keys values are encrypted and decrypted with.\
+/// The functions are called for every value, so providers should cache their keys instead of fetching them each time.
+///
+pub trait KeyProvider {
+    /// The id and key new values are encrypted with.
+    fn current_key() -> Result<(u32, [u8; 32]), FillError>;
+
+    /// The key with the given id, for values encrypted with it before the current key was changed.
+    fn key(id: u32) -> Result<[u8; 32], FillError>;
+}
+
+/// Derives an independent key for another algorithm from a key of a [`KeyProvider`] with HMAC-SHA256.
+pub(crate) fn derive_key(key: &[u8; 32], label: &[u8]) -> [u8; 32] {
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(b"diesel_as_wrap ");
+    mac.update(label);
+    mac.finalize().into_bytes().into()
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+///
+/// How [`AsEncrypted`] encrypts its values, [`Randomized`] or [`Deterministic`].
+/// The trait is sealed, the modes are the formats this crate can read back.
+///
+pub trait Mode: sealed::Sealed {
+    /// The number of bytes an encrypted value is larger than its payload.
+    const OVERHEAD: usize;
+
+    /// Appends the encrypted payload to the key id starting at `start` of the buffer.
+    #[doc(hidden)]
+    fn seal(key: &[u8; 32], buffer: &mut Vec<u8>, start: usize, payload: &impl Payload) -> Result<(), FillError>;
+
+    /// Decrypts the value (including its key id) in place and strips everything but the payload,
+    /// returns whether the value was authentic.
+    #[doc(hidden)]
+    fn open(key: &[u8; 32], bytes: &mut Vec<u8>) -> bool;
+}
+
+///
+/// The default mode of [`AsEncrypted`], XChaCha20-Poly1305 with a fresh random nonce for every value.\
+/// Equal values have different ciphertexts, the database learns nothing but their lengths.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Randomized;
+
+impl sealed::Sealed for Randomized {}
+
+impl Mode for Randomized {
+    const OVERHEAD: usize = OVERHEAD;
+
+    fn seal(key: &[u8; 32], buffer: &mut Vec<u8>, start: usize, payload: &impl Payload) -> Result<(), FillError> {
+        let mut nonce = XNonce::default();
+        OsRng.try_fill_bytes(&mut nonce).map_err(|e| format!("could not generate a nonce: {e}"))?;
+        buffer.extend_from_slice(&nonce);
+        payload.write_payload(buffer)?;
+        let (header, plaintext) = buffer[start..].split_at_mut(HEADER_LEN);
+        let tag = XChaCha20Poly1305::new(Key::from_slice(key))
+            .encrypt_in_place_detached(&nonce, &header[..KEY_ID_LEN], plaintext)
+            .map_err(|_| format!("a payload of {} bytes is too long to be encrypted", plaintext.len()))?;
+        buffer.extend_from_slice(&tag);
+        Ok(())
+    }
+
+    fn open(key: &[u8; 32], bytes: &mut Vec<u8>) -> bool {
+        let tag = Tag::clone_from_slice(&bytes[bytes.len() - TAG_LEN..]);
+        bytes.truncate(bytes.len() - TAG_LEN);
+        let (header, ciphertext) = bytes.split_at_mut(HEADER_LEN);
+        let (id, nonce) = header.split_at(KEY_ID_LEN);
+        let authentic = XChaCha20Poly1305::new(Key::from_slice(key))
+            .decrypt_in_place_detached(XNonce::from_slice(nonce), id, ciphertext, &tag)
+            .is_ok();
+        if authentic {
+            bytes.drain(..HEADER_LEN);
+        }
+        authentic
+    }
+}
+
+///
+/// The opt-in deterministic mode of [`AsEncrypted`], AES-256-SIV (RFC 5297, misuse resistant) without a nonce,
+/// stored as `key id (4 bytes, big endian) | synthetic iv (16 bytes) | ciphertext`.\
+/// The SIV key is derived from the key of the [`KeyProvider`] with HMAC-SHA256,
+/// so the same keys can be used for both modes without being reused by two algorithms.
+///
+/// # Warning: equal values have equal ciphertexts
+/// The same value encrypted with the same key always produces the same bytes, that is what makes
+/// `WHERE column = ...` and unique constraints work on the column, and it is also what leaks:
+/// - Anyone reading the column learns which rows share a value and how often every value occurs.
+///   For values with few possibilities (countries, booleans, birth years, diagnoses) the frequencies
+///   alone often reveal the values, an attacker knowing a single plaintext learns every row with it.
+/// - The lengths of the values are visible as well, as in the randomized mode.
+/// - Lookups only match values encrypted with the same key, so after a key rotation every row has to be
+///   rewritten with the current key before lookups find it again.
+///
+/// Only use it for values that are unique or close to it (emails, account numbers) and actually searched by equality.
+/// A randomized column with a [`BlindIndex`] next to it leaks the same equality for lookups
+/// but keeps the stored value itself randomized, prefer it when the column is read elsewhere.
+///
+/// # Example:
+#[cfg_attr(feature = "sqlite", doc = "```")]
+#[cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+/// # use diesel::prelude::*;
+/// # mod schema {
+/// #     diesel::table! {
+/// #         users (id) {
+/// #             id -> Integer,
+/// #             email -> Binary,
+/// #         }
+/// #     }
+/// # }
+/// use diesel_as_wrap::encrypted::{AsEncrypted, Deterministic, KeyProvider};
+/// use diesel_as_wrap::{wrap_encrypted, FillError};
+///
+/// pub struct AppKeys;
+///
+/// impl KeyProvider for AppKeys {
+///     fn current_key() -> Result<(u32, [u8; 32]), FillError> {
+///         Ok((1, Self::key(1)?))
+///     }
+///
+///     fn key(id: u32) -> Result<[u8; 32], FillError> {
+///         match id {
+///             1 => Ok([0x11; 32]),
+///             _ => Err(format!("unknown key {id}").into()),
+///         }
+///     }
+/// }
+///
+/// type Email = AsEncrypted<String, AppKeys, Deterministic>;
+///
+/// wrap_encrypted! {
+///     target = String;
+///     key = crate::AppKeys;
+///     mode = diesel_as_wrap::encrypted::Deterministic;
+///     pub mod email_encrypted;
+/// }
+///
+/// #[derive(Debug, Clone, PartialEq, Queryable, Selectable, Insertable)]
+/// #[diesel(table_name = schema::users)]
+/// pub struct User {
+///     id: i32,
+///     email: Email,
+/// }
+///
+/// # fn main() {
+/// let mut conn = SqliteConnection::establish(":memory:").unwrap();
+/// diesel::sql_query("CREATE TABLE users (id INTEGER PRIMARY KEY, email BLOB NOT NULL UNIQUE)")
+///     .execute(&mut conn)
+///     .unwrap();
+///
+/// let users = vec![
+///     User { id: 1, email: Email::new("alice@example.com".to_string()) },
+///     User { id: 2, email: Email::new("bob@example.com".to_string()) },
+/// ];
+/// diesel::insert_into(schema::users::table).values(users.clone()).execute(&mut conn).unwrap();
+///
+/// let bob: User = schema::users::table
+///     .filter(schema::users::email.eq(Email::new("bob@example.com".to_string())))
+///     .select(User::as_select())
+///     .first(&mut conn)
+///     .unwrap();
+/// assert_eq!(bob, users[1]);
+/// let alice: i32 = schema::users::table
+///     .filter(schema::users::email.eq(email_encrypted::As("alice@example.com".to_string())))
+///     .select(schema::users::id)
+///     .first(&mut conn)
+///     .unwrap();
+/// assert_eq!(alice, 1);
+///
+/// // the unique constraint sees the equal ciphertext
+/// let duplicate = User { id: 3, email: Email::new("alice@example.com".to_string()) };
+/// assert!(diesel::insert_into(schema::users::table).values(duplicate).execute(&mut conn).is_err());
+/// # }
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Deterministic;
+
+impl sealed::Sealed for Deterministic {}
+
+impl Mode for Deterministic {
+    const OVERHEAD: usize = KEY_ID_LEN + TAG_LEN;
+
+    fn seal(key: &[u8; 32], buffer: &mut Vec<u8>, start: usize, payload: &impl Payload) -> Result<(), FillError> {
+        buffer.extend_from_slice(&[0; TAG_LEN]);
+        payload.write_payload(buffer)?;
+        let (header, plaintext) = buffer[start..].split_at_mut(Self::OVERHEAD);
+        let (id, iv) = header.split_at_mut(KEY_ID_LEN);
+        let tag = siv(key)
+            .encrypt_in_place_detached([&*id], plaintext)
+            .map_err(|_| format!("a payload of {} bytes is too long to be encrypted", plaintext.len()))?;
+        iv.copy_from_slice(&tag);
+        Ok(())
+    }
+
+    fn open(key: &[u8; 32], bytes: &mut Vec<u8>) -> bool {
+        let (header, ciphertext) = bytes.split_at_mut(Self::OVERHEAD);
+        let (id, iv) = header.split_at(KEY_ID_LEN);
+        let authentic = siv(key)
+            .decrypt_in_place_detached([id], ciphertext, aes_siv::Tag::from_slice(iv))
+            .is_ok();
+        if authentic {
+            bytes.drain(..Self::OVERHEAD);
+        }
+        authentic
+    }
+}
+
+fn siv(key: &[u8; 32]) -> Aes256Siv {
+    let mut siv_key = [0; 64];
+    siv_key[..32].copy_from_slice(&derive_key(key, b"aes-siv mac key"));
+    siv_key[32..].copy_from_slice(&derive_key(key, b"aes-siv encryption key"));
+    Aes256Siv::new(aes_siv::Key::<Aes256Siv>::from_slice(&siv_key))
+}
+
+///
+/// A [`Payload`] stored encrypted with the keys of `K` in `Binary` columns, on every backend.\
+/// Use it as type of the field, `Option<AsEncrypted<T, K>>` for nullable columns, or create wrappers for
+/// `deserialize_as` and `serialize_as` with [`wrap_encrypted!`](crate::wrap_encrypted) to keep the plain type in the struct.\
+/// The mode `M` is [`Randomized`] by default, [`Deterministic`] makes the column searchable by equality at the cost
+/// of leaking which values are equal (read its warning before using it).\
+/// Its `Debug` implementation doesn't show the value, so bound values don't end up in query logs.
+/// It is `repr(transparent)`, so a value can be borrowed as AsEncrypted with [`AsEncrypted::from_ref`].
+/// See the [module documentation](crate::encrypted) for the stored format and an example.
+///
+#[derive(diesel::deserialize::FromSqlRow, diesel::expression::AsExpression)]
+#[diesel(sql_type = Binary)]
+#[repr(transparent)]
+pub struct AsEncrypted<T, K, M = Randomized>(pub T, PhantomData<fn() -> (K, M)>);
+
+impl<T, K, M> AsEncrypted<T, K, M> {
+    /// Wraps the value.
+    pub fn new(value: T) -> Self {
+        AsEncrypted(value, PhantomData)
+    }
+
+    /// Borrows the value as AsEncrypted without moving or cloning it.
+    pub fn from_ref(value: &T) -> &Self {
+        // SAFETY: AsEncrypted is repr(transparent) over the value.
+        unsafe { &*(value as *const T as *const Self) }
+    }
+}
+
+impl<T, K, M> From<T> for AsEncrypted<T, K, M> {
+    fn from(value: T) -> Self {
+        AsEncrypted::new(value)
+    }
+}
+
+impl<T: Clone, K, M> Clone for AsEncrypted<T, K, M> {
+    fn clone(&self) -> Self {
+        AsEncrypted::new(self.0.clone())
+    }
+}
+
+impl<T: PartialEq, K, M> PartialEq for AsEncrypted<T, K, M> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T: Eq, K, M> Eq for AsEncrypted<T, K, M> {}
+
+impl<T, K, M> fmt::Debug for AsEncrypted<T, K, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("AsEncrypted(<redacted>)")
+    }
+}
+
+fn encrypt<K: KeyProvider, M: Mode>(buffer: &mut Vec<u8>, payload: &impl Payload) -> Result<(), FillError> {
+    let (id, key) = K::current_key()?;
+    let start = buffer.len();
+    buffer.extend_from_slice(&id.to_be_bytes());
+    M::seal(&key, buffer, start, payload)
+}
+
+fn decrypt<K: KeyProvider, M: Mode>(mut bytes: Vec<u8>) -> deserialize::Result<Vec<u8>> {
+    if bytes.len() < M::OVERHEAD {
+        return Err(format!("{} bytes are too short to be an encrypted value, expected at least {}", bytes.len(), M::OVERHEAD).into());
+    }
+    let id = u32::from_be_bytes(bytes[..KEY_ID_LEN].try_into()?);
+    let key = K::key(id)?;
+    if !M::open(&key, &mut bytes) {
+        return Err(format!("the value could not be decrypted with key {id}, it was modified or encrypted with another key").into());
+    }
+    Ok(bytes)
+}
+
+impl<T, K, M, B> FromSql<Binary, B> for AsEncrypted<T, K, M>
+where
+    B: Backend,
+    T: Payload,
+    K: KeyProvider,
+    M: Mode,
+    Vec<u8>: FromSql<Binary, B>,
+{
+    fn from_sql(bytes: B::RawValue<'_>) -> deserialize::Result<Self> {
+        let bytes = decrypt::<K, M>(Vec::<u8>::from_sql(bytes)?)?;
+        T::read_payload(bytes).map(AsEncrypted::new)
+    }
+}
+
+impl<T, K, M, B> ToSql<Binary, B> for AsEncrypted<T, K, M>
+where
+    B: Backend,
+    T: Payload,
+    K: KeyProvider,
+    M: Mode,
+    Vec<u8>: ToSqlBuffered<Binary, B>,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, B>) -> serialize::Result {
+        Vec::<u8>::to_sql_buffered(out, |buffer| encrypt::<K, M>(buffer, &self.0))
+    }
+}
+
+///
+/// Supplies the 256 bit key of [`BlindIndex`]es, separate from the keys of [`KeyProvider`]
+/// because an index key can't be rotated without recomputing the index of every row.
+///
+pub trait IndexKeyProvider {
+    /// The key indexes are computed with.
+    fn index_key() -> Result<[u8; 32], FillError>;
+}
+
+///
+/// A keyed hash (HMAC-SHA256) of a [`Payload`], stored in a `Binary` column next to the encrypted value
+/// so rows can be looked up by the value without decrypting them or encrypting deterministically.
+/// [`BlindIndex::of`] computes the index of a value, both for the row written and for the parameter of a lookup,
+/// the index is 32 bytes long and can't be turned back into the value.
+/// The HMAC key is derived from the key of the [`IndexKeyProvider`], so it stays independent even if the same key is used elsewhere.
+/// Like the [`Deterministic`] mode the index reveals which rows share a value, index only values that are unique or close to it.
+/// Values have to be normalized before computing the index if lookups should ignore case or whitespace.
+///
+/// # Example:
+#[cfg_attr(feature = "sqlite", doc = "```")]
+#[cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+/// # use diesel::prelude::*;
+/// # mod schema {
+/// #     diesel::table! {
+/// #         users (id) {
+/// #             id -> Integer,
+/// #             email -> Binary,
+/// #             email_index -> Binary,
+/// #         }
+/// #     }
+/// # }
+/// use diesel_as_wrap::encrypted::{AsEncrypted, BlindIndex, IndexKeyProvider, KeyProvider};
+/// use diesel_as_wrap::FillError;
+///
+/// pub struct AppKeys;
+///
+/// impl KeyProvider for AppKeys {
+///     fn current_key() -> Result<(u32, [u8; 32]), FillError> {
+///         Ok((1, Self::key(1)?))
+///     }
+///
+///     fn key(id: u32) -> Result<[u8; 32], FillError> {
+///         match id {
+///             1 => Ok([0x11; 32]),
+///             _ => Err(format!("unknown key {id}").into()),
+///         }
+///     }
+/// }
+///
+/// impl IndexKeyProvider for AppKeys {
+///     fn index_key() -> Result<[u8; 32], FillError> {
+///         Ok([0x99; 32])
+///     }
+/// }
+///
+/// #[derive(Debug, Clone, PartialEq, Queryable, Selectable, Insertable)]
+/// #[diesel(table_name = schema::users)]
+/// pub struct User {
+///     id: i32,
+///     email: AsEncrypted<String, AppKeys>,
+///     email_index: BlindIndex<AppKeys>,
+/// }
+///
+/// impl User {
+///     fn new(id: i32, email: &str) -> Result<Self, FillError> {
+///         let email = email.to_string();
+///         Ok(User { id, email_index: BlindIndex::of(&email)?, email: AsEncrypted::new(email) })
+///     }
+/// }
+///
+/// # fn main() -> Result<(), FillError> {
+/// let mut conn = SqliteConnection::establish(":memory:").unwrap();
+/// diesel::sql_query("CREATE TABLE users (id INTEGER PRIMARY KEY, email BLOB NOT NULL, email_index BLOB NOT NULL)")
+///     .execute(&mut conn)
+///     .unwrap();
+///
+/// let users = vec![User::new(1, "alice@example.com")?, User::new(2, "bob@example.com")?];
+/// diesel::insert_into(schema::users::table).values(users.clone()).execute(&mut conn).unwrap();
+///
+/// let bob: User = schema::users::table
+///     .filter(schema::users::email_index.eq(BlindIndex::<AppKeys>::of(&"bob@example.com".to_string())?))
+///     .select(User::as_select())
+///     .first(&mut conn)
+///     .unwrap();
+/// assert_eq!(bob.email.0, "bob@example.com");
+/// assert_eq!(bob.email_index.as_bytes().len(), 32);
+/// # Ok(())
+/// # }
+/// ```
+///
+#[derive(diesel::deserialize::FromSqlRow, diesel::expression::AsExpression)]
+#[diesel(sql_type = Binary)]
+pub struct BlindIndex<K>([u8; 32], PhantomData<fn() -> K>);
+
+impl<K: IndexKeyProvider> BlindIndex<K> {
+    /// Computes the index of the value.
+    pub fn of<T: Payload>(value: &T) -> Result<Self, FillError> {
+        let mut payload = Vec::new();
+        value.write_payload(&mut payload)?;
+        let key = derive_key(&K::index_key()?, b"blind index key");
+        let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(&key).expect("HMAC accepts keys of any length");
+        mac.update(&payload);
+        Ok(BlindIndex(mac.finalize().into_bytes().into(), PhantomData))
+    }
+}
+
+impl<K> BlindIndex<K> {
+    /// The 32 bytes of the index.
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl<K> Clone for BlindIndex<K> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K> Copy for BlindIndex<K> {}
+
+impl<K> PartialEq for BlindIndex<K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<K> Eq for BlindIndex<K> {}
+
+impl<K> std::hash::Hash for BlindIndex<K> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl<K> fmt::Debug for BlindIndex<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("BlindIndex(")?;
+        for byte in self.0 {
+            write!(f, "{byte:02x}")?;
+        }
+        f.write_str(")")
+    }
+}
+
+impl<K, B> FromSql<Binary, B> for BlindIndex<K>
+where
+    B: Backend,
+    *const [u8]: FromSql<Binary, B>,
+{
+    fn from_sql(bytes: B::RawValue<'_>) -> deserialize::Result<Self> {
+        let bytes = <*const [u8] as FromSql<Binary, B>>::from_sql(bytes)?;
+        // SAFETY: diesel guarantees the pointer to be valid for the duration of `from_sql`.
+        let bytes = unsafe { &*bytes };
+        <[u8; 32]>::try_from(bytes)
+            .map(|index| BlindIndex(index, PhantomData))
+            .map_err(|_| format!("{} bytes are not a blind index, expected 32", bytes.len()).into())
+    }
+}
+
+impl<K, B> ToSql<Binary, B> for BlindIndex<K>
+where
+    B: Backend,
+    [u8]: ToSql<Binary, B>,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, B>) -> serialize::Result {
+        self.0.as_slice().to_sql(out)
+    }
+}
+
+///
+/// This macro creates the wrappers of [`wrap!`](crate::wrap) for a [`Payload`] stored encrypted in a `Binary` column,
+/// the `sql_type`, `debug`, `to_sql` and `from_sql` sections are generated and encrypt the value with [`AsEncrypted`]
+/// and the keys of the given [`KeyProvider`].\
+/// The `target` and `key` sections have to come first, optionally followed by a `mode` section selecting
+/// [`Deterministic`] encryption (read its warning first), [`Randomized`] is used without it.
+/// All other sections of [`wrap!`](crate::wrap) but `debug` can follow in any order, the wrappers always use `debug = redacted`.
+/// Requires the `encrypted` feature, see the [module documentation](crate::encrypted) for an example.
+/// The syntax for using this macro is the following:\
+/// ```text
+/// wrap_encrypted! {
+///     target = $type_to_wrap$;
+///     key = $key_provider$;
+///     [mode = $mode$;]
+///     [index;]
+///     $module_visability$ mod $name_of_wrappers_module$;
+///     [more sections of wrap!]
+/// }
+/// ```
+///
+/// The opt-in `index;` section, directly before the `mod` section, adds a [`BlindIndex`] of the key provider
+/// (which has to implement [`IndexKeyProvider`] as well) for a sibling column to `As`:
+/// `As::blind_index(&self)` computes the index of the wrapped value for the row written
+/// and `As::index_of(&value)` the index of a value for the parameter of a lookup.
+/// It can't be combined with [generic targets](crate::wrap#generic-targets) or `types = ...;`.
+///
+/// # Example:
+#[cfg_attr(feature = "sqlite", doc = "```")]
+#[cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+/// # use diesel::prelude::*;
+/// # mod schema {
+/// #     diesel::table! {
+/// #         users (id) {
+/// #             id -> Integer,
+/// #             email -> Binary,
+/// #             email_index -> Binary,
+/// #         }
+/// #     }
+/// # }
+/// use diesel_as_wrap::encrypted::{BlindIndex, IndexKeyProvider, KeyProvider};
+/// use diesel_as_wrap::{wrap_encrypted, FillError};
+///
+/// pub struct AppKeys;
+///
+/// impl KeyProvider for AppKeys {
+///     fn current_key() -> Result<(u32, [u8; 32]), FillError> {
+///         Ok((1, [0x11; 32]))
+///     }
+///
+///     fn key(id: u32) -> Result<[u8; 32], FillError> {
+///         match id {
+///             1 => Ok([0x11; 32]),
+///             _ => Err(format!("unknown key {id}").into()),
+///         }
+///     }
+/// }
+///
+/// impl IndexKeyProvider for AppKeys {
+///     fn index_key() -> Result<[u8; 32], FillError> {
+///         Ok([0x99; 32])
+///     }
+/// }
+///
+/// wrap_encrypted! {
+///     target = String;
+///     key = crate::AppKeys;
+///     index;
+///     pub mod email_encrypted;
+/// }
+///
+/// #[derive(Debug, Clone, PartialEq, Queryable, Selectable, Insertable)]
+/// #[diesel(table_name = schema::users)]
+/// pub struct User {
+///     id: i32,
+///     #[diesel(serialize_as = email_encrypted::As, deserialize_as = email_encrypted::As)]
+///     email: String,
+///     email_index: BlindIndex<AppKeys>,
+/// }
+///
+/// # fn main() -> Result<(), FillError> {
+/// let mut conn = SqliteConnection::establish(":memory:").unwrap();
+/// diesel::sql_query("CREATE TABLE users (id INTEGER PRIMARY KEY, email BLOB NOT NULL, email_index BLOB NOT NULL)")
+///     .execute(&mut conn)
+///     .unwrap();
+///
+/// for (id, email) in [(1, "alice@example.com"), (2, "bob@example.com")] {
+///     let encrypted = email_encrypted::As(email.to_string());
+///     let user = User { id, email_index: encrypted.blind_index()?, email: encrypted.0 };
+///     diesel::insert_into(schema::users::table).values(user).execute(&mut conn).unwrap();
+/// }
+///
+/// let bob: User = schema::users::table
+///     .filter(schema::users::email_index.eq(email_encrypted::As::index_of(&"bob@example.com".to_string())?))
+///     .select(User::as_select())
+///     .first(&mut conn)
+///     .unwrap();
+/// assert_eq!(bob.id, 2);
+/// assert_eq!(bob.email, "bob@example.com");
+/// # Ok(())
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! wrap_encrypted {
+    (target = $target:ty; key = $key:ty; mode = $mode:ty; index; $(#[$attr:meta])* $vis:vis mod $name:ident; $($rest:tt)*) => {
+        $crate::wrap_encrypted! {
+            target = $target;
+            key = $key;
+            mode = $mode;
+            $(#[$attr])* $vis mod $name;
+            $($rest)*
+        }
+
+        $(#[$attr])*
+        impl $name::As {
+            /// The blind index of the wrapped value, for the sibling column of the row written.
+            pub fn blind_index(&self) -> ::std::result::Result<$crate::encrypted::BlindIndex<$key>, $crate::FillError> {
+                $crate::encrypted::BlindIndex::of(&self.0)
+            }
+
+            /// The blind index of a value, for the parameter of a lookup.
+            pub fn index_of(value: &$target) -> ::std::result::Result<$crate::encrypted::BlindIndex<$key>, $crate::FillError> {
+                $crate::encrypted::BlindIndex::of(value)
+            }
+        }
+    };
+
+    (target = $target:ty; key = $key:ty; mode = $mode:ty; $($rest:tt)*) => {
+        $crate::wrap! {
+            target = $target;
+            sql_type = Binary;
+            debug = redacted;
+            $($rest)*
+            fn to_sql<$crate::encrypted::AsEncrypted<Target, $key, $mode>>(self, out) {
+                <$crate::encrypted::AsEncrypted<Target, $key, $mode>>::from_ref(&self.0).to_sql(out)
+            }
+            fn from_sql<$crate::encrypted::AsEncrypted<Target, $key, $mode>>(bytes) {
+                <$crate::encrypted::AsEncrypted<Target, $key, $mode>>::from_sql(bytes).map(|value| As(value.0))
+            }
+        }
+    };
+
+    (target = $target:ty; key = $key:ty; $($rest:tt)*) => {
+        $crate::wrap_encrypted! {
+            target = $target;
+            key = $key;
+            mode = $crate::encrypted::Randomized;
+            $($rest)*
+        }
+    };
+}
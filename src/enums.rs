@@ -0,0 +1,405 @@
+use std::fmt;
+
+///
+/// Returned as deserialization error by the wrappers created by [`wrap_enum!`](crate::wrap_enum)
+/// and [`wrap_enum_int!`](crate::wrap_enum_int) when the database contains a value that is no variant of the enum.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UnknownVariant {
+    /// The name of the enum.
+    pub target: &'static str,
+    /// The value read from the database.
+    pub value: String,
+    /// The values of all variants.
+    pub expected: &'static [&'static str],
+}
+
+impl fmt::Display for UnknownVariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} is not a variant of `{}`, expected one of ", self.value, self.target)?;
+        for (i, expected) in self.expected.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{expected:?}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for UnknownVariant {}
+
+///
+/// This macro creates the wrappers of [`wrap!`](crate::wrap) for a fieldless enum in a `Text` column,
+/// the `sql_type`, `to_sql` and `from_sql` sections are generated from the listed variants.\
+/// Writing matches the variants exhaustively, so a variant missing from the list is a compile error.
+/// Reading a string that belongs to no variant fails with an [`UnknownVariant`](crate::UnknownVariant) error.\
+/// The `target` and `variants` sections have to come first, all other sections of [`wrap!`](crate::wrap) can follow in any order.
+/// The syntax for using this macro is the following:\
+/// ```text
+/// wrap_enum! {
+///     target = $enum_to_wrap$;
+///     variants { $variant$ => $string$, ... }
+///     $module_visability$ mod $name_of_wrappers_module$;
+///     [more sections of wrap!]
+/// }
+/// ```
+///
+/// # Example:
+#[cfg_attr(feature = "sqlite", doc = "```")]
+#[cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+/// use diesel::prelude::*;
+/// use diesel::sql_types::Text;
+/// use diesel_as_wrap::wrap_enum;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// pub enum Status {
+///     Active,
+///     Banned,
+/// }
+///
+/// wrap_enum! {
+///     target = crate::Status;
+///     variants {
+///         Active => "active",
+///         Banned => "banned",
+///     }
+///     pub mod status_wrap;
+/// }
+///
+/// # fn main() {
+/// let mut conn = SqliteConnection::establish(":memory:").unwrap();
+/// let loaded: status_wrap::As = diesel::select(status_wrap::As(Status::Banned).into_sql::<Text>()).get_result(&mut conn).unwrap();
+/// assert_eq!(loaded.0, Status::Banned);
+///
+/// let raw: String = diesel::select(status_wrap::As(Status::Active).into_sql::<Text>()).get_result(&mut conn).unwrap();
+/// assert_eq!(raw, "active");
+///
+/// let unknown = diesel::select("deleted".into_sql::<Text>()).get_result::<status_wrap::As>(&mut conn);
+/// assert!(unknown
+///     .unwrap_err()
+///     .to_string()
+///     .ends_with(r#""deleted" is not a variant of `crate::Status`, expected one of "active", "banned""#));
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! wrap_enum {
+    (target = $target:ty; variants { $($variants:tt)* } $($rest:tt)*) => {
+        $crate::wrap_enum! { @text $target; Text; variants { $($variants)* } $($rest)* }
+    };
+
+    // `wrap_pg_enum!` uses the same bodies with a sql type for which `str` and `*const str` implement `ToSql` and `FromSql`
+    (@text $target:ty; $sql_type:ty; variants { $($variant:ident => $text:literal),+ $(,)? } $($rest:tt)*) => {
+        $crate::wrap! {
+            target = $target;
+            sql_type = $sql_type;
+            $($rest)*
+            fn to_sql<str>(self, out) {
+                let text: &str = match &self.0 {
+                    $(Target::$variant => $text,)+
+                };
+                text.to_sql(out)
+            }
+            fn from_sql<&str>(bytes) {
+                match bytes {
+                    $($text => Ok(As(Target::$variant)),)+
+                    value => Err(Box::new($crate::UnknownVariant {
+                        target: stringify!($target),
+                        value: value.to_owned(),
+                        expected: &[$($text),+],
+                    })),
+                }
+            }
+        }
+    };
+}
+
+///
+/// This macro creates the wrappers of [`wrap!`](crate::wrap) for a fieldless enum in a Postgres enum column (`CREATE TYPE ... AS ENUM`),
+/// the variants are listed like for [`wrap_enum!`](crate::wrap_enum) with their Postgres labels.
+/// Requires the `postgres` feature.\
+/// The `sql_type` section either declares the sql type of the Postgres type with the given name
+/// (`sql_type = pub struct Mood = "mood";`, which derives `SqlType` and `QueryId` and implements `HasSqlType` for `Pg`)
+/// or names an existing one, for example the type `diesel print-schema` generates in `schema::sql_types`.
+/// The labels are written and read as text for that sql type, so `str` implements `ToSql` and `*const str` implements `FromSql` for it.\
+/// The `target`, `sql_type` and `variants` sections have to come first, all other sections of [`wrap!`](crate::wrap) can follow in any order.
+/// The syntax for using this macro is the following:\
+/// ```text
+/// wrap_pg_enum! {
+///     target = $enum_to_wrap$;
+///     sql_type = $visability$ struct $sql_type_name$ = $postgres_type_name$; | sql_type = $existing_sql_type$;
+///     variants { $variant$ => $label$, ... }
+///     $module_visability$ mod $name_of_wrappers_module$;
+///     [more sections of wrap!]
+/// }
+/// ```
+///
+/// # Example:
+#[cfg_attr(feature = "postgres", doc = "```")]
+#[cfg_attr(not(feature = "postgres"), doc = "```ignore")]
+/// # use diesel::prelude::*;
+/// # mod schema {
+/// #     diesel::table! {
+/// #         use diesel::sql_types::*;
+/// #         use crate::Mood;
+/// #
+/// #         people (id) {
+/// #             id -> Integer,
+/// #             mood -> Mood,
+/// #             previous_mood -> Nullable<Mood>,
+/// #         }
+/// #     }
+/// # }
+/// use diesel_as_wrap::wrap_pg_enum;
+///
+/// // CREATE TYPE mood AS ENUM ('happy', 'sad')
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// pub enum Feeling {
+///     Happy,
+///     Sad,
+/// }
+///
+/// wrap_pg_enum! {
+///     target = crate::Feeling;
+///     sql_type = pub struct Mood = "mood";
+///     variants {
+///         Happy => "happy",
+///         Sad => "sad",
+///     }
+///     pub mod feeling_wrap;
+/// }
+///
+/// #[derive(Debug, Queryable, Selectable, Insertable)]
+/// #[diesel(table_name = schema::people)]
+/// #[diesel(check_for_backend(diesel::pg::Pg))]
+/// pub struct Person {
+///     id: i32,
+///     #[diesel(deserialize_as = feeling_wrap::As)]
+///     #[diesel(serialize_as = feeling_wrap::As)]
+///     mood: Feeling,
+///     #[diesel(deserialize_as = feeling_wrap::AsOption)]
+///     #[diesel(serialize_as = feeling_wrap::AsOption)]
+///     previous_mood: Option<Feeling>,
+/// }
+///
+/// # fn main() {
+/// let query = schema::people::table
+///     .filter(schema::people::mood.eq(feeling_wrap::As(Feeling::Sad)))
+///     .select(Person::as_select());
+/// let sql = diesel::debug_query::<diesel::pg::Pg, _>(&query).to_string();
+/// assert!(sql.ends_with(r#"WHERE ("people"."mood" = $1) -- binds: [As(Sad)]"#));
+/// # }
+/// ```
+///
+/// The labels are written and read as the text of the enum value, other labels are an [`UnknownVariant`](crate::UnknownVariant) error:
+#[cfg_attr(feature = "postgres", doc = "```")]
+#[cfg_attr(not(feature = "postgres"), doc = "```ignore")]
+/// # use diesel::deserialize::FromSql;
+/// # use diesel::pg::{Pg, PgMetadataLookup, PgTypeMetadata, PgValue};
+/// # use diesel::query_builder::bind_collector::RawBytesBindCollector;
+/// # use diesel::query_builder::BindCollector;
+/// # use diesel::serialize::ToSql;
+/// # use diesel::sql_types::HasSqlType;
+/// # struct Types;
+/// # impl PgMetadataLookup for Types {
+/// #     fn lookup_type(&mut self, _: &str, _: Option<&str>) -> PgTypeMetadata {
+/// #         PgTypeMetadata::new(100_000, 100_001)
+/// #     }
+/// # }
+/// # fn encode<ST, T: ToSql<ST, Pg>>(value: &T) -> Option<Vec<u8>> where Pg: HasSqlType<ST> {
+/// #     let mut collector = RawBytesBindCollector::<Pg>::new();
+/// #     collector.push_bound_value::<ST, T>(value, &mut Types).unwrap();
+/// #     collector.binds.pop().unwrap()
+/// # }
+/// # fn decode<ST, T: FromSql<ST, Pg>>(bytes: Option<&[u8]>) -> diesel::deserialize::Result<T> where Pg: HasSqlType<ST> {
+/// #     let oid = std::num::NonZeroU32::new(<Pg as HasSqlType<ST>>::metadata(&mut Types).oid().unwrap()).unwrap();
+/// #     T::from_nullable_sql(bytes.map(|bytes| PgValue::new(bytes, &oid)))
+/// # }
+/// use diesel::sql_types::Nullable;
+/// use diesel_as_wrap::{wrap_pg_enum, UnknownVariant};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// pub enum Feeling {
+///     Happy,
+///     Sad,
+/// }
+///
+/// wrap_pg_enum! {
+///     target = crate::Feeling;
+///     sql_type = pub struct Mood = "mood";
+///     variants {
+///         Happy => "happy",
+///         Sad => "sad",
+///     }
+///     pub mod feeling_wrap;
+/// }
+///
+/// # fn main() {
+/// let bytes = encode::<Mood, _>(&feeling_wrap::As(Feeling::Sad)).unwrap();
+/// assert_eq!(bytes, b"sad");
+/// assert_eq!(decode::<Mood, feeling_wrap::As>(Some(b"happy")).unwrap().0, Feeling::Happy);
+/// assert!(decode::<Nullable<Mood>, feeling_wrap::AsOption>(None).unwrap().0.is_none());
+///
+/// let error = decode::<Mood, feeling_wrap::As>(Some(b"angry")).unwrap_err();
+/// let unknown = error.downcast_ref::<UnknownVariant>().unwrap();
+/// assert_eq!(unknown.value, "angry");
+/// assert_eq!(unknown.expected, ["happy", "sad"]);
+/// // labels are case sensitive like in Postgres
+/// assert!(decode::<Mood, feeling_wrap::As>(Some(b"Happy")).is_err());
+/// # }
+/// ```
+///
+#[cfg(feature = "postgres")]
+#[macro_export]
+macro_rules! wrap_pg_enum {
+    (target = $target:ty; sql_type = $sql_type:ty; $($rest:tt)*) => {
+        $crate::wrap_pg_enum! { @text_impls $sql_type }
+        $crate::wrap_enum! { @text $target; $sql_type; $($rest)* }
+    };
+
+    (target = $target:ty; sql_type = $visablity:vis struct $name:ident = $pg_name:literal; $($rest:tt)*) => {
+        #[doc = concat!("The Postgres enum type `", $pg_name, "`.")]
+        #[derive(Debug, Clone, Copy, Default, diesel::sql_types::SqlType, diesel::query_builder::QueryId)]
+        #[diesel(postgres_type(name = $pg_name))]
+        $visablity struct $name;
+
+        $crate::wrap_pg_enum! { @text_impls $name }
+        // the wrappers live in a child module
+        $crate::wrap_enum! { @text $target; super::$name; $($rest)* }
+    };
+
+    (@text_impls $sql_type:ty) => {
+        impl diesel::serialize::ToSql<$sql_type, diesel::pg::Pg> for str {
+            fn to_sql<'b>(&'b self, out: &mut diesel::serialize::Output<'b, '_, diesel::pg::Pg>) -> diesel::serialize::Result {
+                diesel::serialize::ToSql::<diesel::sql_types::Text, diesel::pg::Pg>::to_sql(self, out)
+            }
+        }
+
+        impl diesel::deserialize::FromSql<$sql_type, diesel::pg::Pg> for *const str {
+            fn from_sql(bytes: diesel::pg::PgValue<'_>) -> diesel::deserialize::Result<Self> {
+                <*const str as diesel::deserialize::FromSql<diesel::sql_types::Text, diesel::pg::Pg>>::from_sql(bytes)
+            }
+        }
+    };
+}
+
+///
+/// This macro creates the wrappers of [`wrap!`](crate::wrap) for a fieldless enum in a `SmallInt`, `Integer` or `BigInt` column,
+/// the `to_sql` and `from_sql` sections are generated from the listed variants and their values.\
+/// The values are integer literals of the column's type (`i16`, `i32` or `i64`), so a value out of its range is a compile error
+/// instead of being truncated.
+/// Writing matches the variants exhaustively, so a variant missing from the list is a compile error.
+/// Reading a value that belongs to no variant fails with an [`UnknownVariant`](crate::UnknownVariant) error.\
+/// The `target`, `sql_type` and `variants` sections have to come first, all other sections of [`wrap!`](crate::wrap) can follow in any order.
+/// The syntax for using this macro is the following:\
+/// ```text
+/// wrap_enum_int! {
+///     target = $enum_to_wrap$;
+///     sql_type = SmallInt | Integer | BigInt;
+///     variants { $variant$ => $value$, ... }
+///     $module_visability$ mod $name_of_wrappers_module$;
+///     [more sections of wrap!]
+/// }
+/// ```
+///
+/// # Example:
+#[cfg_attr(feature = "sqlite", doc = "```")]
+#[cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+/// use diesel::prelude::*;
+/// use diesel::sql_types::SmallInt;
+/// use diesel_as_wrap::wrap_enum_int;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// pub enum Priority {
+///     Low,
+///     High,
+///     Urgent,
+/// }
+///
+/// wrap_enum_int! {
+///     target = crate::Priority;
+///     sql_type = SmallInt;
+///     variants {
+///         Low => 0,
+///         High => 10,
+///         Urgent => -1,
+///     }
+///     pub mod priority_wrap;
+/// }
+///
+/// # fn main() {
+/// let mut conn = SqliteConnection::establish(":memory:").unwrap();
+/// let raw: i16 = diesel::select(priority_wrap::As(Priority::Urgent).into_sql::<SmallInt>()).get_result(&mut conn).unwrap();
+/// assert_eq!(raw, -1);
+///
+/// let loaded: priority_wrap::As = diesel::select(10i16.into_sql::<SmallInt>()).get_result(&mut conn).unwrap();
+/// assert_eq!(loaded.0, Priority::High);
+///
+/// let unknown = diesel::select(3i16.into_sql::<SmallInt>()).get_result::<priority_wrap::As>(&mut conn);
+/// assert!(unknown
+///     .unwrap_err()
+///     .to_string()
+///     .ends_with(r#""3" is not a variant of `crate::Priority`, expected one of "0", "10", "-1""#));
+/// # }
+/// ```
+///
+/// Values that don't fit the column's type are rejected:
+/// ```compile_fail
+/// # use diesel_as_wrap::wrap_enum_int;
+/// pub enum Priority {
+///     Low,
+/// }
+///
+/// wrap_enum_int! {
+///     target = crate::Priority;
+///     sql_type = SmallInt;
+///     variants { Low => 40000 }
+///     pub mod priority_wrap;
+/// }
+/// # fn main() {}
+/// ```
+///
+#[macro_export]
+macro_rules! wrap_enum_int {
+    (target = $target:ty; sql_type = SmallInt; $($rest:tt)*) => {
+        $crate::wrap_enum_int! { @int $target; SmallInt, i16; $($rest)* }
+    };
+
+    (target = $target:ty; sql_type = Integer; $($rest:tt)*) => {
+        $crate::wrap_enum_int! { @int $target; Integer, i32; $($rest)* }
+    };
+
+    (target = $target:ty; sql_type = BigInt; $($rest:tt)*) => {
+        $crate::wrap_enum_int! { @int $target; BigInt, i64; $($rest)* }
+    };
+
+    (@int $target:ty; $sql_type:ident, $int:ty; variants { $($variant:ident => $value:literal),+ $(,)? } $($rest:tt)*) => {
+        $crate::wrap! {
+            target = $target;
+            sql_type = $sql_type;
+            $($rest)*
+            fn to_sql<$int>(self, out) {
+                // the literals are promoted to `'static`, so they can be written by reference
+                let value: &'static $int = match &self.0 {
+                    $(Target::$variant => &$value,)+
+                };
+                value.to_sql(out)
+            }
+            fn from_sql<$int>(bytes) {
+                match <$int>::from_sql(bytes)? {
+                    $($value => Ok(As(Target::$variant)),)+
+                    value => Err(Box::new($crate::UnknownVariant {
+                        target: stringify!($target),
+                        value: value.to_string(),
+                        expected: &[$(stringify!($value)),+],
+                    })),
+                }
+            }
+        }
+    };
+
+    (target = $target:ty; sql_type = $($rest:tt)*) => {
+        compile_error!("`wrap_enum_int!` supports the sql types `SmallInt`, `Integer` and `BigInt`");
+    };
+}
@@ -0,0 +1,148 @@
+//! Wrappers storing [`flatbuffers`](::flatbuffers) messages in `Binary` columns.
+//!
+//! [`FlatBuffer`] owns the bytes of a message that were verified against its root type,
+//! [`FlatBuffer::root`] gives access to the typed accessor object without parsing again.\
+//! The root type is named through [`FlatBufferRoot`], implement it for the tables generated by `flatc`
+//! (`impl FlatBufferRoot for Monster<'static> { type Root<'buf> = Monster<'buf>; }`).\
+//! [`wrap_flatbuffer!`](crate::wrap_flatbuffer) creates the wrappers of [`wrap!`](crate::wrap) for a `FlatBuffer`,
+//! bytes that don't verify produce a deserialization error.
+//!
+//! # Example:
+#![cfg_attr(feature = "sqlite", doc = "```")]
+#![cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+//! use diesel::prelude::*;
+//! use diesel::sql_types::Binary;
+//! use diesel_as_wrap::flatbuffers::{FlatBuffer, FlatBufferRoot};
+//! use diesel_as_wrap::wrap_flatbuffer;
+//!
+//! // a message consisting of a single string, generated tables work the same
+//! pub struct Greeting;
+//!
+//! impl FlatBufferRoot for Greeting {
+//!     type Root<'buf> = &'buf str;
+//! }
+//!
+//! wrap_flatbuffer! {
+//!     root = crate::Greeting;
+//!     pub mod greeting_fb;
+//! }
+//!
+//! # fn main() {
+//! let mut builder = flatbuffers::FlatBufferBuilder::new();
+//! let text = builder.create_string("hello");
+//! builder.finish(text, None);
+//! let message = FlatBuffer::<Greeting>::new(builder.finished_data().to_vec()).unwrap();
+//!
+//! let mut conn = SqliteConnection::establish(":memory:").unwrap();
+//! let loaded: greeting_fb::As = diesel::select(greeting_fb::As(message).into_sql::<Binary>()).get_result(&mut conn).unwrap();
+//! assert_eq!(loaded.0.root(), "hello");
+//!
+//! let garbage = diesel::select(vec![1u8, 2, 3].into_sql::<Binary>()).get_result::<greeting_fb::As>(&mut conn);
+//! assert!(garbage.is_err());
+//! # }
+//! ```
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use ::flatbuffers::{Follow, InvalidFlatbuffer, Verifiable};
+
+///
+/// Names the root type of a flatbuffers message for [`FlatBuffer`].\
+/// The root type borrows from the message bytes, so it is given as generic associated type.
+///
+pub trait FlatBufferRoot {
+    /// The root type borrowing from a buffer with lifetime `'buf`.
+    type Root<'buf>: Follow<'buf, Inner = Self::Root<'buf>> + Verifiable + 'buf;
+}
+
+///
+/// The bytes of a flatbuffers message that were verified against the root type `R`.
+///
+pub struct FlatBuffer<R: FlatBufferRoot> {
+    bytes: Vec<u8>,
+    root: PhantomData<fn() -> R>,
+}
+
+impl<R: FlatBufferRoot> FlatBuffer<R> {
+    /// Verifies the bytes against the root type.
+    pub fn new(bytes: Vec<u8>) -> Result<Self, InvalidFlatbuffer> {
+        ::flatbuffers::root::<R::Root<'_>>(&bytes)?;
+        Ok(FlatBuffer { bytes, root: PhantomData })
+    }
+
+    /// Returns the typed accessor object of the message.
+    pub fn root(&self) -> R::Root<'_> {
+        // SAFETY: the bytes were verified against the root type in `new` and can't be changed afterwards.
+        unsafe { ::flatbuffers::root_unchecked::<R::Root<'_>>(&self.bytes) }
+    }
+
+    /// Returns the bytes of the message.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Returns the bytes of the message.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+impl<R: FlatBufferRoot> Clone for FlatBuffer<R> {
+    fn clone(&self) -> Self {
+        FlatBuffer { bytes: self.bytes.clone(), root: PhantomData }
+    }
+}
+
+impl<R: FlatBufferRoot> fmt::Debug for FlatBuffer<R>
+where
+    for<'buf> R::Root<'buf>: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("FlatBuffer").field(&self.root()).finish()
+    }
+}
+
+impl<R: FlatBufferRoot> PartialEq for FlatBuffer<R> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bytes == other.bytes
+    }
+}
+
+impl<R: FlatBufferRoot> Eq for FlatBuffer<R> {}
+
+///
+/// This macro creates the wrappers of [`wrap!`](crate::wrap) for a [`FlatBuffer`](crate::flatbuffers::FlatBuffer) in a `Binary` column,
+/// the `target`, `sql_type`, `to_sql` and `from_sql` sections are generated.\
+/// The `root` section (a type implementing [`FlatBufferRoot`](crate::flatbuffers::FlatBufferRoot)) has to come first,
+/// all other sections of [`wrap!`](crate::wrap) can follow in any order.
+/// Requires the `flatbuffers` feature, see the [module documentation](crate::flatbuffers) for an example.
+/// The syntax for using this macro is the following:\
+/// ```text
+/// wrap_flatbuffer! {
+///     root = $root_type$;
+///     $module_visability$ mod $name_of_wrappers_module$;
+///     [more sections of wrap!]
+/// }
+/// ```
+///
+#[macro_export]
+macro_rules! wrap_flatbuffer {
+    (root = $root:ty; $($rest:tt)*) => {
+        $crate::wrap! {
+            target = $crate::flatbuffers::FlatBuffer<$root>;
+            sql_type = Binary;
+            $($rest)*
+            fn to_sql<[u8]>(self, out) {
+                let bytes: &[u8] = self.0.as_bytes();
+                bytes.to_sql(out)
+            }
+            fn from_sql<Vec<u8>>(bytes) {
+                let bytes = <Vec<u8>>::from_sql(bytes)?;
+                <$crate::flatbuffers::FlatBuffer<$root>>::new(bytes)
+                    .map(As)
+                    .map_err(Into::into)
+            }
+        }
+    };
+}
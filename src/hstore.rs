@@ -0,0 +1,221 @@
+//! Wrappers for the Postgres [`hstore`](https://www.postgresql.org/docs/current/hstore.html) extension type.
+//!
+//! [`Hstore`] is the sql type of `hstore` columns, use it inside `table!` (`attributes -> diesel_as_wrap::hstore::Hstore`).\
+//! [`hash_map`] maps an `hstore` column to a `HashMap<String, Option<String>>` and [`btree_map`] to a `BTreeMap<String, Option<String>>`,
+//! `None` values are stored as sql `NULL` values of their key.
+//! The extension has to be installed in the database (`CREATE EXTENSION hstore`), its type oid is looked up on first use.
+//!
+//! # Example:
+#![cfg_attr(feature = "postgres", doc = "```")]
+#![cfg_attr(not(feature = "postgres"), doc = "```ignore")]
+//! # use diesel::prelude::*;
+//! # mod schema {
+//! #     diesel::table! {
+//! #         use diesel::sql_types::*;
+//! #         use diesel_as_wrap::hstore::Hstore;
+//! #
+//! #         products (id) {
+//! #             id -> Integer,
+//! #             attributes -> Hstore,
+//! #             overrides -> Nullable<Hstore>,
+//! #         }
+//! #     }
+//! # }
+//! use std::collections::{BTreeMap, HashMap};
+//! use diesel_as_wrap::hstore::{btree_map, hash_map};
+//!
+//! #[derive(Debug, Queryable, Selectable, Insertable)]
+//! #[diesel(table_name = schema::products)]
+//! #[diesel(check_for_backend(diesel::pg::Pg))]
+//! pub struct Product {
+//!     id: i32,
+//!     #[diesel(deserialize_as = hash_map::As)]
+//!     #[diesel(serialize_as = hash_map::As)]
+//!     attributes: HashMap<String, Option<String>>,
+//!     #[diesel(deserialize_as = btree_map::AsOption)]
+//!     #[diesel(serialize_as = btree_map::AsOption)]
+//!     overrides: Option<BTreeMap<String, Option<String>>>,
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! The binary format is the pair count followed by the length and bytes of every key and value, `-1` as the length of `NULL`:
+#![cfg_attr(feature = "postgres", doc = "```")]
+#![cfg_attr(not(feature = "postgres"), doc = "```ignore")]
+//! # use diesel::deserialize::FromSql;
+//! # use diesel::pg::{Pg, PgMetadataLookup, PgTypeMetadata, PgValue};
+//! # use diesel::query_builder::bind_collector::RawBytesBindCollector;
+//! # use diesel::query_builder::BindCollector;
+//! # use diesel::serialize::ToSql;
+//! # use diesel::sql_types::HasSqlType;
+//! # struct Types;
+//! # impl PgMetadataLookup for Types {
+//! #     fn lookup_type(&mut self, _: &str, _: Option<&str>) -> PgTypeMetadata {
+//! #         PgTypeMetadata::new(100_000, 100_001)
+//! #     }
+//! # }
+//! # fn encode<ST, T: ToSql<ST, Pg>>(value: &T) -> Option<Vec<u8>> where Pg: HasSqlType<ST> {
+//! #     let mut collector = RawBytesBindCollector::<Pg>::new();
+//! #     collector.push_bound_value::<ST, T>(value, &mut Types).unwrap();
+//! #     collector.binds.pop().unwrap()
+//! # }
+//! # fn decode<ST, T: FromSql<ST, Pg>>(bytes: Option<&[u8]>) -> diesel::deserialize::Result<T> where Pg: HasSqlType<ST> {
+//! #     let oid = std::num::NonZeroU32::new(<Pg as HasSqlType<ST>>::metadata(&mut Types).oid().unwrap()).unwrap();
+//! #     T::from_nullable_sql(bytes.map(|bytes| PgValue::new(bytes, &oid)))
+//! # }
+//! use std::collections::{BTreeMap, HashMap};
+//! use diesel::sql_types::Nullable;
+//! use diesel_as_wrap::hstore::{btree_map, hash_map, Hstore};
+//!
+//! let attributes = BTreeMap::from([("color".to_string(), Some("red".to_string())), ("size".to_string(), None)]);
+//! let bytes = encode::<Hstore, _>(&btree_map::As(attributes.clone())).unwrap();
+//! let expected = [
+//!     &[0, 0, 0, 2, 0, 0, 0, 5][..], b"color", &[0, 0, 0, 3], b"red",
+//!     &[0, 0, 0, 4], b"size", &[255, 255, 255, 255],
+//! ].concat();
+//! assert_eq!(bytes, expected);
+//! let read: btree_map::As = decode::<Hstore, _>(Some(&bytes)).unwrap();
+//! assert_eq!(read.0, attributes);
+//! let read: hash_map::As = decode::<Hstore, _>(Some(&bytes)).unwrap();
+//! assert_eq!(read.0, attributes.clone().into_iter().collect::<HashMap<_, _>>());
+//!
+//! let bytes = encode::<Hstore, _>(&hash_map::As(Default::default())).unwrap();
+//! assert_eq!(bytes, [0, 0, 0, 0]);
+//! let read: hash_map::As = decode::<Hstore, _>(Some(&bytes)).unwrap();
+//! assert!(read.0.is_empty());
+//! let read: btree_map::AsOption = decode::<Nullable<Hstore>, _>(None).unwrap();
+//! assert!(read.0.is_none());
+//!
+//! let truncated = decode::<Hstore, btree_map::As>(Some(&expected[..expected.len() - 1])).unwrap_err();
+//! assert_eq!(truncated.to_string(), "unexpected end of hstore value");
+//! let truncated = decode::<Hstore, btree_map::As>(Some(&expected[..10])).unwrap_err();
+//! assert_eq!(truncated.to_string(), "unexpected end of hstore value");
+//! let trailing = decode::<Hstore, btree_map::As>(Some(&[&expected[..], &[0]].concat())).unwrap_err();
+//! assert_eq!(trailing.to_string(), "1 unexpected bytes after the hstore pairs");
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+
+use diesel::deserialize::{self, FromSql};
+use diesel::pg::{Pg, PgValue};
+use diesel::serialize::{self, IsNull, Output, ToSql};
+
+///
+/// The sql type of the Postgres `hstore` extension.
+///
+#[derive(Debug, Clone, Copy, Default, diesel::sql_types::SqlType, diesel::query_builder::QueryId)]
+#[diesel(postgres_type(name = "hstore"))]
+pub struct Hstore;
+
+fn write_length(out: &mut Output<'_, '_, Pg>, length: usize, what: &str) -> serialize::Result {
+    let length = i32::try_from(length).map_err(|_| format!("hstore {what} of {length} bytes is too long"))?;
+    out.write_all(&length.to_be_bytes())?;
+    Ok(IsNull::No)
+}
+
+fn write_pairs<'a>(
+    out: &mut Output<'_, '_, Pg>,
+    len: usize,
+    pairs: impl Iterator<Item = (&'a String, &'a Option<String>)>,
+) -> serialize::Result {
+    write_length(out, len, "pair count")?;
+    for (key, value) in pairs {
+        write_length(out, key.len(), "key")?;
+        out.write_all(key.as_bytes())?;
+        match value {
+            Some(value) => {
+                write_length(out, value.len(), "value")?;
+                out.write_all(value.as_bytes())?;
+            }
+            None => out.write_all(&(-1_i32).to_be_bytes())?,
+        }
+    }
+    Ok(IsNull::No)
+}
+
+fn read_length(bytes: &mut &[u8]) -> deserialize::Result<i32> {
+    let (length, rest) = bytes.split_first_chunk::<4>().ok_or("unexpected end of hstore value")?;
+    *bytes = rest;
+    Ok(i32::from_be_bytes(*length))
+}
+
+fn read_text(bytes: &mut &[u8], length: i32) -> deserialize::Result<String> {
+    let length = usize::try_from(length).map_err(|_| format!("invalid hstore length {length}"))?;
+    if bytes.len() < length {
+        return Err("unexpected end of hstore value".into());
+    }
+    let (text, rest) = bytes.split_at(length);
+    *bytes = rest;
+    Ok(String::from_utf8(text.to_vec())?)
+}
+
+fn read_pairs(bytes: PgValue<'_>) -> deserialize::Result<Vec<(String, Option<String>)>> {
+    let mut bytes = bytes.as_bytes();
+    let count = read_length(&mut bytes)?;
+    let count = usize::try_from(count).map_err(|_| format!("invalid hstore pair count {count}"))?;
+    let mut pairs = Vec::with_capacity(count.min(bytes.len() / 8));
+    for _ in 0..count {
+        let length = read_length(&mut bytes)?;
+        let key = read_text(&mut bytes, length)?;
+        let value = match read_length(&mut bytes)? {
+            -1 => None,
+            length => Some(read_text(&mut bytes, length)?),
+        };
+        pairs.push((key, value));
+    }
+    if !bytes.is_empty() {
+        return Err(format!("{} unexpected bytes after the hstore pairs", bytes.len()).into());
+    }
+    Ok(pairs)
+}
+
+impl ToSql<Hstore, Pg> for HashMap<String, Option<String>> {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        write_pairs(out, self.len(), self.iter())
+    }
+}
+
+impl FromSql<Hstore, Pg> for HashMap<String, Option<String>> {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        read_pairs(bytes).map(|pairs| pairs.into_iter().collect())
+    }
+}
+
+impl ToSql<Hstore, Pg> for BTreeMap<String, Option<String>> {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        write_pairs(out, self.len(), self.iter())
+    }
+}
+
+impl FromSql<Hstore, Pg> for BTreeMap<String, Option<String>> {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        read_pairs(bytes).map(|pairs| pairs.into_iter().collect())
+    }
+}
+
+crate::wrap! {
+    target = ::std::collections::HashMap<String, Option<String>>;
+    sql_type = crate::hstore::Hstore;
+    pub mod hash_map;
+    fn to_sql<::std::collections::HashMap<String, Option<String>>>(self, out) {
+        let map: &::std::collections::HashMap<String, Option<String>> = &self.0;
+        map.to_sql(out)
+    }
+    fn from_sql<::std::collections::HashMap<String, Option<String>>>(bytes) {
+        <::std::collections::HashMap<String, Option<String>>>::from_sql(bytes).map(As)
+    }
+}
+
+crate::wrap! {
+    target = ::std::collections::BTreeMap<String, Option<String>>;
+    sql_type = crate::hstore::Hstore;
+    pub mod btree_map;
+    fn to_sql<::std::collections::BTreeMap<String, Option<String>>>(self, out) {
+        let map: &::std::collections::BTreeMap<String, Option<String>> = &self.0;
+        map.to_sql(out)
+    }
+    fn from_sql<::std::collections::BTreeMap<String, Option<String>>>(bytes) {
+        <::std::collections::BTreeMap<String, Option<String>>>::from_sql(bytes).map(As)
+    }
+}
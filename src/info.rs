@@ -0,0 +1,50 @@
+///
+/// Describes a module created by [`wrap!`](crate::wrap) or [`wrap_pointer!`](crate::wrap_pointer).\
+/// Every created module contains this description as `INFO` constant
+/// (and registers it for [`WrapperInfo::registered`] with the `inventory` feature),
+/// so tooling (schema linters, admin UIs, ...) can list which rust types back which sql types at build time.\
+/// The type names are the tokens given to the macro, they are not resolved or normalized.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WrapperInfo {
+    /// The full path of the created module.
+    pub module: &'static str,
+    /// The wrapped type as written in `target = ...;`.
+    pub target: &'static str,
+    /// The sql type as written in `sql_type = ...;`.
+    pub sql_type: &'static str,
+}
+
+#[cfg(feature = "inventory")]
+inventory::collect!(WrapperInfo);
+
+#[cfg(feature = "inventory")]
+impl WrapperInfo {
+    ///
+    /// Returns all wrapper modules linked into the running binary, across all crates using this one.\
+    /// The order is unspecified.
+    /// Requires the `inventory` feature.
+    ///
+    /// ```
+    /// # use diesel_as_wrap::{wrap, WrapperInfo};
+    /// wrap! {
+    ///     target = u8;
+    ///     sql_type = SmallInt;
+    ///     pub mod u8_wrap;
+    ///     fn into_sql<i16>(self) {
+    ///         i16::from(self.0)
+    ///     }
+    ///     fn from_sql<i16>(bytes) {
+    ///         let value = i16::from_sql(bytes)?;
+    ///         u8::try_from(value).map(As).map_err(Into::into)
+    ///     }
+    /// }
+    ///
+    /// # fn main() {
+    /// assert!(WrapperInfo::registered().any(|info| *info == u8_wrap::INFO));
+    /// # }
+    /// ```
+    pub fn registered() -> impl Iterator<Item = &'static WrapperInfo> {
+        inventory::iter::<WrapperInfo>.into_iter()
+    }
+}
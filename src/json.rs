@@ -0,0 +1,725 @@
+//! Wrappers for json columns based on [`serde_json`](::serde_json).
+//!
+//! [`json_value`] and [`jsonb_value`] read schemaless `Json` and `Jsonb` columns into a raw [`serde_json::Value`](::serde_json::Value)
+//! without defining a typed struct for their content.
+//! `Json` is supported by Postgres and MySQL, `Jsonb` only by Postgres (requires the `postgres` feature).
+//!
+//! | backend  | sql type | stored as                                                              |
+//! |----------|----------|------------------------------------------------------------------------|
+//! | Postgres | `Json`   | the json text as written, whitespace, key order and duplicates are kept |
+//! | Postgres | `Jsonb`  | decomposed binary, keys are sorted and deduplicated                     |
+//! | MySQL    | `Json`   | MySQL's binary json format, keys are sorted and deduplicated            |
+//!
+//! The wrappers are generic over the backend, so the same field of a struct works with Postgres and MySQL,
+//! the encoding is picked by the backend's implementation for `serde_json::Value`.
+//! A `Value` read back from MySQL or from `Jsonb` therefore equals the one written, but its text may differ.
+//!
+//! # Example:
+#![cfg_attr(feature = "postgres", doc = "```")]
+#![cfg_attr(not(feature = "postgres"), doc = "```ignore")]
+//! # use diesel::prelude::*;
+//! # mod schema {
+//! #     diesel::table! {
+//! #         webhooks (id) {
+//! #             id -> Integer,
+//! #             payload -> Jsonb,
+//! #             response -> Nullable<Jsonb>,
+//! #         }
+//! #     }
+//! # }
+//! use diesel_as_wrap::json::jsonb_value;
+//! use serde_json::Value;
+//!
+//! #[derive(Debug, Queryable, Selectable, Insertable)]
+//! #[diesel(table_name = schema::webhooks)]
+//! #[diesel(check_for_backend(diesel::pg::Pg))]
+//! pub struct Webhook {
+//!     id: i32,
+//!     #[diesel(deserialize_as = jsonb_value::As)]
+//!     #[diesel(serialize_as = jsonb_value::As)]
+//!     payload: Value,
+//!     #[diesel(deserialize_as = jsonb_value::AsOption)]
+//!     #[diesel(serialize_as = jsonb_value::AsOption)]
+//!     response: Option<Value>,
+//! }
+//! ```
+//!
+//! The same wrappers with MySQL:
+#![cfg_attr(feature = "mysql", doc = "```")]
+#![cfg_attr(not(feature = "mysql"), doc = "```ignore")]
+//! # use diesel::prelude::*;
+//! # mod schema {
+//! #     diesel::table! {
+//! #         events (id) {
+//! #             id -> Integer,
+//! #             payload -> Json,
+//! #             context -> Nullable<Json>,
+//! #         }
+//! #     }
+//! # }
+//! use diesel_as_wrap::json::json_value;
+//! use serde_json::Value;
+//!
+//! #[derive(Debug, Queryable, Selectable, Insertable)]
+//! #[diesel(table_name = schema::events)]
+//! #[diesel(check_for_backend(diesel::mysql::Mysql))]
+//! pub struct Event {
+//!     id: i32,
+//!     #[diesel(deserialize_as = json_value::As)]
+//!     #[diesel(serialize_as = json_value::As)]
+//!     payload: Value,
+//!     #[diesel(deserialize_as = json_value::AsOption)]
+//!     #[diesel(serialize_as = json_value::AsOption)]
+//!     context: Option<Value>,
+//! }
+//! ```
+//!
+//! The string map modules store a `HashMap<String, String>` or `BTreeMap<String, String>` as json object,
+//! for the key-value metadata columns found in many schemas:
+//! [`hash_map_json`] and [`btree_map_json`] use `Json` columns, [`hash_map_jsonb`] and [`btree_map_jsonb`] `Jsonb` columns
+//! (requires the `postgres` feature) and [`hash_map_text`] and [`btree_map_text`] `Text` columns on every backend.
+//! A json document that isn't an object of strings produces a deserialization error.
+//!
+#![cfg_attr(feature = "sqlite", doc = "```")]
+#![cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+//! # use diesel::prelude::*;
+//! # mod schema {
+//! #     diesel::table! {
+//! #         assets (id) {
+//! #             id -> Integer,
+//! #             labels -> Text,
+//! #             annotations -> Nullable<Text>,
+//! #         }
+//! #     }
+//! # }
+//! use std::collections::{BTreeMap, HashMap};
+//! use diesel_as_wrap::json::{btree_map_text, hash_map_text};
+//!
+//! #[derive(Debug, Clone, PartialEq, Queryable, Selectable, Insertable)]
+//! #[diesel(table_name = schema::assets)]
+//! pub struct Asset {
+//!     id: i32,
+//!     #[diesel(deserialize_as = btree_map_text::As)]
+//!     #[diesel(serialize_as = btree_map_text::As)]
+//!     labels: BTreeMap<String, String>,
+//!     #[diesel(deserialize_as = hash_map_text::AsOption)]
+//!     #[diesel(serialize_as = hash_map_text::AsOption)]
+//!     annotations: Option<HashMap<String, String>>,
+//! }
+//!
+//! # fn main() {
+//! let mut conn = SqliteConnection::establish(":memory:").unwrap();
+//! diesel::sql_query("CREATE TABLE assets (id INTEGER PRIMARY KEY, labels TEXT NOT NULL, annotations TEXT)")
+//!     .execute(&mut conn)
+//!     .unwrap();
+//!
+//! let labels = BTreeMap::from([("team".to_string(), "storage".to_string()), ("env".to_string(), "prod".to_string())]);
+//! let assets = vec![
+//!     Asset { id: 1, labels, annotations: Some(HashMap::from([("owner".to_string(), "ops".to_string())])) },
+//!     Asset { id: 2, labels: BTreeMap::new(), annotations: None },
+//! ];
+//! diesel::insert_into(schema::assets::table).values(assets.clone()).execute(&mut conn).unwrap();
+//!
+//! let stored: String = schema::assets::table.select(schema::assets::labels).first(&mut conn).unwrap();
+//! assert_eq!(stored, r#"{"env":"prod","team":"storage"}"#);
+//! let loaded: Vec<Asset> = schema::assets::table.select(Asset::as_select()).load(&mut conn).unwrap();
+//! assert_eq!(loaded, assets);
+//!
+//! diesel::sql_query(r#"UPDATE assets SET labels = '{"replicas":3}' WHERE id = 2"#).execute(&mut conn).unwrap();
+//! assert!(schema::assets::table.select(Asset::as_select()).load(&mut conn).is_err());
+//! # }
+//! ```
+//!
+//! [`AsJson`] stores any `serde` type as json document in `Text`, `Json` or `Jsonb` columns,
+//! [`wrap_json!`](crate::wrap_json) creates the wrappers of `wrap!` for such a type without writing the `to_sql` and `from_sql` sections.
+//!
+//! [`JsonVec`] and [`JsonOptionVec`] store a `Vec` of any `serde` type as json array in `Text` or `Json` columns,
+//! for collection valued fields on MySQL and SQLite, which don't have native arrays.
+
+use std::fmt;
+
+use ::serde::de::DeserializeOwned;
+use ::serde::Serialize;
+use diesel::backend::Backend;
+use diesel::deserialize::{self, FromSql};
+use diesel::serialize::{self, Output, ToSql};
+#[cfg(feature = "postgres")]
+use diesel::sql_types::Jsonb;
+use diesel::sql_types::{Json, Nullable, Text};
+
+use crate::{FillError, Payload, ToSqlBuffered, ToSqlOwned};
+
+crate::wrap! {
+    target = ::serde_json::Value;
+    sql_type = Json;
+    pub mod json_value;
+    fn to_sql<::serde_json::Value>(self, out) {
+        let value: &::serde_json::Value = &self.0;
+        value.to_sql(out)
+    }
+    fn from_sql<::serde_json::Value>(bytes) {
+        <::serde_json::Value>::from_sql(bytes).map(As)
+    }
+}
+
+#[cfg(feature = "postgres")]
+crate::wrap! {
+    target = ::serde_json::Value;
+    sql_type = Jsonb;
+    pub mod jsonb_value;
+    fn to_sql<::serde_json::Value>(self, out) {
+        let value: &::serde_json::Value = &self.0;
+        value.to_sql(out)
+    }
+    fn from_sql<::serde_json::Value>(bytes) {
+        <::serde_json::Value>::from_sql(bytes).map(As)
+    }
+}
+
+#[cfg(feature = "postgres")]
+crate::assert_wrapper_backends!(json_value, [diesel::pg::Pg]);
+
+#[cfg(feature = "postgres")]
+crate::assert_wrapper_backends!(jsonb_value, [diesel::pg::Pg]);
+
+#[cfg(feature = "mysql")]
+crate::assert_wrapper_backends!(json_value, [diesel::mysql::Mysql]);
+
+macro_rules! string_map {
+    ($($(#[$attr:meta])* $name:ident: $map:ident, $sql_type:ident;)+) => {
+        $(
+            $(#[$attr])*
+            crate::wrap_json! {
+                target = ::std::collections::$map<String, String>;
+                sql_type = $sql_type;
+                pub mod $name;
+            }
+        )+
+    };
+}
+
+string_map! {
+    hash_map_text: HashMap, Text;
+    hash_map_json: HashMap, Json;
+    #[cfg(feature = "postgres")]
+    hash_map_jsonb: HashMap, Jsonb;
+    btree_map_text: BTreeMap, Text;
+    btree_map_json: BTreeMap, Json;
+    #[cfg(feature = "postgres")]
+    btree_map_jsonb: BTreeMap, Jsonb;
+}
+
+///
+/// A `Vec` stored as json array, for collection valued fields on backends without native arrays.\
+/// It is written to and read from `Text` columns on every backend and `Json` columns on Postgres and MySQL,
+/// the elements are encoded with their `serde` implementations.
+/// On Postgres the `AsVec` wrapper of a module created by [`wrap!`](crate::wrap) stores the same field as native array.\
+/// It is `repr(transparent)`, so a `Vec` can be borrowed as JsonVec with [`JsonVec::from_ref`].
+///
+/// # Example:
+#[cfg_attr(feature = "sqlite", doc = "```")]
+#[cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+/// # use diesel::prelude::*;
+/// # mod schema {
+/// #     diesel::table! {
+/// #         articles (id) {
+/// #             id -> Integer,
+/// #             tags -> Text,
+/// #             ratings -> Nullable<Text>,
+/// #         }
+/// #     }
+/// # }
+/// use diesel_as_wrap::json::{JsonOptionVec, JsonVec};
+///
+/// #[derive(Debug, Clone, PartialEq, Queryable, Selectable, Insertable)]
+/// #[diesel(table_name = schema::articles)]
+/// pub struct Article {
+///     id: i32,
+///     #[diesel(deserialize_as = JsonVec<String>)]
+///     #[diesel(serialize_as = JsonVec<String>)]
+///     tags: Vec<String>,
+///     #[diesel(deserialize_as = JsonOptionVec<u8>)]
+///     #[diesel(serialize_as = JsonOptionVec<u8>)]
+///     ratings: Option<Vec<u8>>,
+/// }
+///
+/// # fn main() {
+/// let mut conn = SqliteConnection::establish(":memory:").unwrap();
+/// diesel::sql_query("CREATE TABLE articles (id INTEGER PRIMARY KEY, tags TEXT NOT NULL, ratings TEXT)")
+///     .execute(&mut conn)
+///     .unwrap();
+///
+/// let articles = vec![
+///     Article { id: 1, tags: vec!["rust".to_string(), "\"quoted\"".to_string()], ratings: Some(vec![4, 5]) },
+///     Article { id: 2, tags: vec![], ratings: None },
+/// ];
+/// diesel::insert_into(schema::articles::table).values(articles.clone()).execute(&mut conn).unwrap();
+///
+/// let stored: String = schema::articles::table.select(schema::articles::tags).first(&mut conn).unwrap();
+/// assert_eq!(stored, r#"["rust","\"quoted\""]"#);
+/// let loaded: Vec<Article> = schema::articles::table.select(Article::as_select()).load(&mut conn).unwrap();
+/// assert_eq!(loaded, articles);
+/// # }
+/// ```
+///
+/// The same field in a MySQL `Json` column:
+#[cfg_attr(feature = "mysql", doc = "```")]
+#[cfg_attr(not(feature = "mysql"), doc = "```ignore")]
+/// # use diesel::prelude::*;
+/// # mod schema {
+/// #     diesel::table! {
+/// #         articles (id) {
+/// #             id -> Integer,
+/// #             tags -> Json,
+/// #         }
+/// #     }
+/// # }
+/// use diesel_as_wrap::json::JsonVec;
+///
+/// #[derive(Debug, Queryable, Selectable, Insertable)]
+/// #[diesel(table_name = schema::articles)]
+/// #[diesel(check_for_backend(diesel::mysql::Mysql))]
+/// pub struct Article {
+///     id: i32,
+///     #[diesel(deserialize_as = JsonVec<String>)]
+///     #[diesel(serialize_as = JsonVec<String>)]
+///     tags: Vec<String>,
+/// }
+/// ```
+///
+#[derive(Debug, Clone, PartialEq, Eq, diesel::deserialize::FromSqlRow, diesel::expression::AsExpression)]
+#[diesel(sql_type = Text)]
+#[diesel(sql_type = Json)]
+#[repr(transparent)]
+pub struct JsonVec<T>(pub Vec<T>);
+
+///
+/// A nullable [`JsonVec`], `None` is stored as `NULL`.
+///
+#[derive(Debug, Clone, PartialEq, Eq, diesel::deserialize::FromSqlRow, diesel::expression::AsExpression)]
+#[diesel(sql_type = Nullable<Text>)]
+#[diesel(sql_type = Nullable<Json>)]
+pub struct JsonOptionVec<T>(pub Option<Vec<T>>);
+
+impl<T> JsonVec<T> {
+    /// Borrows the `Vec` as JsonVec without moving or cloning it.
+    pub fn from_ref(values: &Vec<T>) -> &Self {
+        // SAFETY: JsonVec is repr(transparent) over the Vec.
+        unsafe { &*(values as *const Vec<T> as *const Self) }
+    }
+}
+
+impl<T> From<JsonVec<T>> for Vec<T> {
+    fn from(s: JsonVec<T>) -> Self {
+        s.0
+    }
+}
+
+impl<T> From<Vec<T>> for JsonVec<T> {
+    fn from(s: Vec<T>) -> Self {
+        JsonVec(s)
+    }
+}
+
+impl<T> From<JsonOptionVec<T>> for Option<Vec<T>> {
+    fn from(s: JsonOptionVec<T>) -> Self {
+        s.0
+    }
+}
+
+impl<T> From<Option<Vec<T>>> for JsonOptionVec<T> {
+    fn from(s: Option<Vec<T>>) -> Self {
+        JsonOptionVec(s)
+    }
+}
+
+impl<T, B> FromSql<Text, B> for JsonVec<T>
+where
+    B: Backend,
+    T: DeserializeOwned,
+    String: FromSql<Text, B>,
+{
+    fn from_sql(bytes: B::RawValue<'_>) -> deserialize::Result<Self> {
+        let text = String::from_sql(bytes)?;
+        ::serde_json::from_str(&text).map(JsonVec).map_err(Into::into)
+    }
+}
+
+impl<T, B> ToSql<Text, B> for JsonVec<T>
+where
+    B: Backend,
+    T: Serialize + fmt::Debug,
+    String: ToSqlBuffered<Text, B>,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, B>) -> serialize::Result {
+        String::to_sql_buffered(out, |buffer| {
+            // SAFETY: serde_json only writes valid UTF-8.
+            ::serde_json::to_writer(unsafe { buffer.as_mut_vec() }, &self.0)?;
+            Ok(())
+        })
+    }
+}
+
+impl<T, B> FromSql<Json, B> for JsonVec<T>
+where
+    B: Backend,
+    T: DeserializeOwned,
+    ::serde_json::Value: FromSql<Json, B>,
+{
+    fn from_sql(bytes: B::RawValue<'_>) -> deserialize::Result<Self> {
+        let value = <::serde_json::Value>::from_sql(bytes)?;
+        ::serde_json::from_value(value).map(JsonVec).map_err(Into::into)
+    }
+}
+
+impl<T, B> ToSql<Json, B> for JsonVec<T>
+where
+    B: Backend,
+    T: Serialize + fmt::Debug,
+    ::serde_json::Value: ToSqlOwned<Json, B>,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, B>) -> serialize::Result {
+        ::serde_json::to_value(&self.0)?.to_sql_owned(out)
+    }
+}
+
+macro_rules! json_option_vec {
+    ($($sql_type:ty),+) => {
+        $(
+            impl<T, B> FromSql<Nullable<$sql_type>, B> for JsonOptionVec<T>
+            where
+                B: Backend,
+                JsonVec<T>: FromSql<$sql_type, B>,
+            {
+                fn from_sql(bytes: B::RawValue<'_>) -> deserialize::Result<Self> {
+                    Ok(JsonOptionVec(Some(JsonVec::from_sql(bytes)?.0)))
+                }
+
+                fn from_nullable_sql(bytes: Option<B::RawValue<'_>>) -> deserialize::Result<Self> {
+                    match bytes {
+                        Some(bytes) => Self::from_sql(bytes),
+                        None => Ok(JsonOptionVec(None)),
+                    }
+                }
+            }
+
+            impl<T, B> ToSql<Nullable<$sql_type>, B> for JsonOptionVec<T>
+            where
+                B: Backend,
+                T: fmt::Debug,
+                JsonVec<T>: ToSql<$sql_type, B>,
+            {
+                fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, B>) -> serialize::Result {
+                    match &self.0 {
+                        Some(values) => JsonVec::from_ref(values).to_sql(out),
+                        None => Ok(serialize::IsNull::Yes),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+json_option_vec!(Text, Json);
+
+///
+/// Any `serde` type stored as json document.\
+/// It is written to and read from `Text` columns on every backend, `Json` columns on Postgres and MySQL
+/// and `Jsonb` columns on Postgres, the value is encoded with its `Serialize` implementation
+/// and decoded with its `Deserialize` implementation, a document that doesn't match the type is a deserialize error.\
+/// Use it as type of the field, `Option<AsJson<T>>` for nullable columns, or create wrappers for
+/// `deserialize_as` and `serialize_as` with [`wrap_json!`](crate::wrap_json) to keep the plain type in the struct.\
+/// It is `repr(transparent)`, so a value can be borrowed as AsJson with [`AsJson::from_ref`].
+///
+/// # Example:
+#[cfg_attr(feature = "sqlite", doc = "```")]
+#[cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+/// # use diesel::prelude::*;
+/// # mod schema {
+/// #     diesel::table! {
+/// #         users (id) {
+/// #             id -> Integer,
+/// #             settings -> Text,
+/// #             address -> Nullable<Text>,
+/// #         }
+/// #     }
+/// # }
+/// use diesel_as_wrap::json::AsJson;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// pub struct Settings {
+///     theme: String,
+///     notifications: bool,
+/// }
+///
+/// #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// pub struct Address {
+///     street: String,
+///     city: String,
+/// }
+///
+/// #[derive(Debug, Clone, PartialEq, Queryable, Selectable, Insertable)]
+/// #[diesel(table_name = schema::users)]
+/// pub struct User {
+///     id: i32,
+///     settings: AsJson<Settings>,
+///     address: Option<AsJson<Address>>,
+/// }
+///
+/// # fn main() {
+/// let mut conn = SqliteConnection::establish(":memory:").unwrap();
+/// diesel::sql_query("CREATE TABLE users (id INTEGER PRIMARY KEY, settings TEXT NOT NULL, address TEXT)")
+///     .execute(&mut conn)
+///     .unwrap();
+///
+/// let settings = AsJson(Settings { theme: "dark".to_string(), notifications: true });
+/// let users = vec![
+///     User {
+///         id: 1,
+///         settings: settings.clone(),
+///         address: Some(AsJson(Address { street: "Main Street 1".to_string(), city: "Springfield".to_string() })),
+///     },
+///     User { id: 2, settings, address: None },
+/// ];
+/// diesel::insert_into(schema::users::table).values(users.clone()).execute(&mut conn).unwrap();
+///
+/// let stored: String = schema::users::table.select(schema::users::settings).first(&mut conn).unwrap();
+/// assert_eq!(stored, r#"{"theme":"dark","notifications":true}"#);
+/// let loaded: Vec<User> = schema::users::table.select(User::as_select()).load(&mut conn).unwrap();
+/// assert_eq!(loaded, users);
+///
+/// diesel::sql_query(r#"UPDATE users SET settings = '{"theme":"dark"}' WHERE id = 2"#).execute(&mut conn).unwrap();
+/// let error = schema::users::table.select(User::as_select()).load(&mut conn).unwrap_err();
+/// assert!(error.to_string().contains("missing field `notifications`"));
+/// # }
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, diesel::deserialize::FromSqlRow, diesel::expression::AsExpression)]
+#[diesel(sql_type = Text)]
+#[diesel(sql_type = Json)]
+#[cfg_attr(feature = "postgres", diesel(sql_type = Jsonb))]
+#[repr(transparent)]
+pub struct AsJson<T>(pub T);
+
+impl<T> AsJson<T> {
+    /// Borrows the value as AsJson without moving or cloning it.
+    pub fn from_ref(value: &T) -> &Self {
+        // SAFETY: AsJson is repr(transparent) over the value.
+        unsafe { &*(value as *const T as *const Self) }
+    }
+}
+
+impl<T> From<T> for AsJson<T> {
+    fn from(s: T) -> Self {
+        AsJson(s)
+    }
+}
+
+impl<T, B> FromSql<Text, B> for AsJson<T>
+where
+    B: Backend,
+    T: DeserializeOwned,
+    String: FromSql<Text, B>,
+{
+    fn from_sql(bytes: B::RawValue<'_>) -> deserialize::Result<Self> {
+        let text = String::from_sql(bytes)?;
+        ::serde_json::from_str(&text).map(AsJson).map_err(Into::into)
+    }
+}
+
+impl<T, B> ToSql<Text, B> for AsJson<T>
+where
+    B: Backend,
+    T: Serialize + fmt::Debug,
+    String: ToSqlBuffered<Text, B>,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, B>) -> serialize::Result {
+        String::to_sql_buffered(out, |buffer| {
+            // SAFETY: serde_json only writes valid UTF-8.
+            ::serde_json::to_writer(unsafe { buffer.as_mut_vec() }, &self.0)?;
+            Ok(())
+        })
+    }
+}
+
+macro_rules! as_json {
+    ($($sql_type:ty),+) => {
+        $(
+            impl<T, B> FromSql<$sql_type, B> for AsJson<T>
+            where
+                B: Backend,
+                T: DeserializeOwned,
+                ::serde_json::Value: FromSql<$sql_type, B>,
+            {
+                fn from_sql(bytes: B::RawValue<'_>) -> deserialize::Result<Self> {
+                    let value = <::serde_json::Value>::from_sql(bytes)?;
+                    ::serde_json::from_value(value).map(AsJson).map_err(Into::into)
+                }
+            }
+
+            impl<T, B> ToSql<$sql_type, B> for AsJson<T>
+            where
+                B: Backend,
+                T: Serialize + fmt::Debug,
+                ::serde_json::Value: ToSqlOwned<$sql_type, B>,
+            {
+                fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, B>) -> serialize::Result {
+                    ::serde_json::to_value(&self.0)?.to_sql_owned(out)
+                }
+            }
+        )+
+    };
+}
+
+as_json!(Json);
+#[cfg(feature = "postgres")]
+as_json!(Jsonb);
+
+impl<T> Payload for AsJson<T>
+where
+    T: Serialize + DeserializeOwned,
+{
+    fn write_payload(&self, buffer: &mut Vec<u8>) -> Result<(), FillError> {
+        ::serde_json::to_writer(buffer, &self.0)?;
+        Ok(())
+    }
+
+    fn read_payload(bytes: Vec<u8>) -> deserialize::Result<Self> {
+        ::serde_json::from_slice(&bytes).map(AsJson).map_err(Into::into)
+    }
+}
+
+///
+/// This macro creates the wrappers of [`wrap!`](crate::wrap) for a `serde` type stored as json document
+/// in a `Text`, `Json` or `Jsonb` column, the `to_sql` and `from_sql` sections are generated and encode the value with [`AsJson`].\
+/// The `target` and `sql_type` sections have to come first, all other sections of [`wrap!`](crate::wrap) can follow in any order.
+/// The syntax for using this macro is the following:\
+/// ```text
+/// wrap_json! {
+///     target = $type_to_wrap$;
+///     sql_type = $Text_Json_or_Jsonb$;
+///     $module_visability$ mod $name_of_wrappers_module$;
+///     [more sections of wrap!]
+/// }
+/// ```
+///
+/// # Example:
+#[cfg_attr(feature = "sqlite", doc = "```")]
+#[cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+/// # use diesel::prelude::*;
+/// # mod schema {
+/// #     diesel::table! {
+/// #         users (id) {
+/// #             id -> Integer,
+/// #             settings -> Text,
+/// #             previous -> Nullable<Text>,
+/// #         }
+/// #     }
+/// # }
+/// use diesel_as_wrap::wrap_json;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// pub struct Settings {
+///     theme: String,
+///     notifications: bool,
+/// }
+///
+/// wrap_json! {
+///     target = crate::Settings;
+///     sql_type = Text;
+///     pub mod settings_json;
+/// }
+///
+/// #[derive(Debug, Clone, PartialEq, Queryable, Selectable, Insertable)]
+/// #[diesel(table_name = schema::users)]
+/// pub struct User {
+///     id: i32,
+///     #[diesel(deserialize_as = settings_json::As)]
+///     #[diesel(serialize_as = settings_json::As)]
+///     settings: Settings,
+///     #[diesel(deserialize_as = settings_json::AsOption)]
+///     #[diesel(serialize_as = settings_json::AsOption)]
+///     previous: Option<Settings>,
+/// }
+///
+/// # fn main() {
+/// let mut conn = SqliteConnection::establish(":memory:").unwrap();
+/// diesel::sql_query("CREATE TABLE users (id INTEGER PRIMARY KEY, settings TEXT NOT NULL, previous TEXT)")
+///     .execute(&mut conn)
+///     .unwrap();
+///
+/// let settings = Settings { theme: "dark".to_string(), notifications: true };
+/// let users = vec![
+///     User { id: 1, settings: settings.clone(), previous: Some(Settings { theme: "light".to_string(), notifications: false }) },
+///     User { id: 2, settings, previous: None },
+/// ];
+/// diesel::insert_into(schema::users::table).values(users.clone()).execute(&mut conn).unwrap();
+///
+/// let stored: Option<String> = schema::users::table.select(schema::users::previous).first(&mut conn).unwrap();
+/// assert_eq!(stored.as_deref(), Some(r#"{"theme":"light","notifications":false}"#));
+/// let loaded: Vec<User> = schema::users::table.select(User::as_select()).load(&mut conn).unwrap();
+/// assert_eq!(loaded, users);
+/// # }
+/// ```
+///
+/// The same wrappers for a Postgres `Jsonb` column:
+#[cfg_attr(feature = "postgres", doc = "```")]
+#[cfg_attr(not(feature = "postgres"), doc = "```ignore")]
+/// # use diesel::prelude::*;
+/// # mod schema {
+/// #     diesel::table! {
+/// #         users (id) {
+/// #             id -> Integer,
+/// #             settings -> Jsonb,
+/// #             previous -> Nullable<Jsonb>,
+/// #         }
+/// #     }
+/// # }
+/// use diesel_as_wrap::wrap_json;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, Serialize, Deserialize)]
+/// pub struct Settings {
+///     theme: String,
+///     notifications: bool,
+/// }
+///
+/// wrap_json! {
+///     target = crate::Settings;
+///     sql_type = Jsonb;
+///     pub mod settings_json;
+/// }
+///
+/// #[derive(Debug, Queryable, Selectable, Insertable)]
+/// #[diesel(table_name = schema::users)]
+/// #[diesel(check_for_backend(diesel::pg::Pg))]
+/// pub struct User {
+///     id: i32,
+///     #[diesel(deserialize_as = settings_json::As)]
+///     #[diesel(serialize_as = settings_json::As)]
+///     settings: Settings,
+///     #[diesel(deserialize_as = settings_json::AsOption)]
+///     #[diesel(serialize_as = settings_json::AsOption)]
+///     previous: Option<Settings>,
+/// }
+/// # fn main() {}
+/// ```
+///
+#[macro_export]
+macro_rules! wrap_json {
+    (target = $target:ty; sql_type = $sql_type:ty; $($rest:tt)*) => {
+        $crate::wrap! {
+            target = $target;
+            sql_type = $sql_type;
+            $($rest)*
+            fn to_sql<$crate::json::AsJson<Target>>(self, out) {
+                $crate::json::AsJson::from_ref(&self.0).to_sql(out)
+            }
+            fn from_sql<$crate::json::AsJson<Target>>(bytes) {
+                <$crate::json::AsJson<Target>>::from_sql(bytes).map(|json| As(json.0))
+            }
+        }
+    };
+}
@@ -1,4 +1,249 @@
+mod adapters;
+mod array;
+mod backends;
+mod buffer;
+mod builder;
+mod columns;
+mod debug;
+mod enums;
+mod info;
+mod money;
+mod owned;
+mod payload;
+mod round_trip;
 
+pub use adapters::{AsBinary, AsI16, AsI32, AsI64, AsText};
+pub use backends::BoundValue;
+pub use buffer::{FillError, ToSqlBuffered, MAX_RETAINED_CAPACITY};
+pub use builder::MissingField;
+pub use debug::QuoteLiteral;
+pub use enums::UnknownVariant;
+pub use info::WrapperInfo;
+pub use money::{MinorUnits, MinorUnitsError};
+pub use owned::ToSqlOwned;
+pub use payload::Payload;
+pub use round_trip::RoundTrip;
+#[doc(hidden)]
+pub use round_trip::ToSqlFn;
+
+///
+/// Derives the impls of the `impl;` mode of [`wrap!`](crate::wrap) for a struct with a single field,
+/// so fields of the struct need no `deserialize_as`/`serialize_as` and `Option<struct>` works for nullable columns.
+/// The struct has to implement `Debug` (required by `ToSql`), using it requires the `derive` feature.\
+/// The attributes are checked by the derive itself, mistakes are reported at the attribute or field they belong to.
+///
+/// - `#[as_wrap(sql_type = ...)]` is the sql type the struct is represented as (required).
+///   The field is written and read as this sql type.
+/// - `#[as_wrap(via = ...)]` optionally converts the struct itself `Into` and `TryFrom` the given type instead,
+///   like the `via` section of [`wrap!`](crate::wrap) (the struct has to implement `Clone`).
+/// - `#[as_wrap(backend = ...)]` optionally restricts the impls to a single backend.
+#[cfg_attr(feature = "sqlite", doc = "```")]
+#[cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+/// # use diesel::prelude::*;
+/// # use diesel::sql_types::Text;
+/// use diesel_as_wrap::AsWrap;
+///
+/// #[derive(Debug, Clone, PartialEq, AsWrap)]
+/// #[as_wrap(sql_type = Text)]
+/// pub struct Email(String);
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, AsWrap)]
+/// #[as_wrap(sql_type = diesel::sql_types::Integer, via = i32)]
+/// pub struct Port(u16);
+///
+/// impl From<Port> for i32 {
+///     fn from(port: Port) -> Self {
+///         port.0.into()
+///     }
+/// }
+///
+/// impl TryFrom<i32> for Port {
+///     type Error = std::num::TryFromIntError;
+///
+///     fn try_from(value: i32) -> Result<Self, Self::Error> {
+///         u16::try_from(value).map(Port)
+///     }
+/// }
+///
+/// # fn main() {
+/// let mut conn = SqliteConnection::establish(":memory:").unwrap();
+/// let email = Email("someone@example.com".to_string());
+/// let loaded: Email = diesel::select(email.clone().into_sql::<Text>()).get_result(&mut conn).unwrap();
+/// assert_eq!(loaded, email);
+///
+/// let loaded: Option<Port> = diesel::select(Some(Port(5432)).into_sql::<diesel::sql_types::Nullable<diesel::sql_types::Integer>>())
+///     .get_result(&mut conn)
+///     .unwrap();
+/// assert_eq!(loaded, Some(Port(5432)));
+/// # }
+/// ```
+///
+/// Unknown attributes are rejected:
+/// ```compile_fail
+/// # use diesel_as_wrap::AsWrap;
+/// #[derive(AsWrap)]
+/// #[as_wrap(sql_type = Text, via_type = String)]
+/// // error: unknown `as_wrap` attribute, expected `sql_type`, `via` or `backend`
+/// pub struct Email(String);
+/// ```
+///
+#[cfg(feature = "derive")]
+pub use diesel_as_wrap_derive::AsWrap;
+
+///
+/// Adds the `#[diesel(deserialize_as = ..., serialize_as = ...)]` attributes for wrapped fields of a model struct.
+/// Requires the `derive` feature and has to be placed above the `#[derive(...)]` of the struct.\
+/// The wrapper modules are given as `Type => path::to::module` arguments, a macro can't see the modules created by [`wrap!`](crate::wrap).
+/// Fields of the type use the module's As and fields of `Option<Type>` its AsOption,
+/// types are compared by their last path segment (`Uuid` matches a field of `uuid::Uuid`).
+///
+/// - `#[as_wrap(path::to::module)]` on a field uses the module for that field regardless of its type.
+/// - Fields that already have `deserialize_as` or `serialize_as` are left unchanged.
+/// - An argument no field matches is reported as error.
+#[cfg_attr(feature = "sqlite", doc = "```")]
+#[cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+/// # use diesel::prelude::*;
+/// # mod schema {
+/// #     diesel::table! {
+/// #         users (id) {
+/// #             id -> Integer,
+/// #             uuid -> Binary,
+/// #             referrer -> Nullable<Binary>,
+/// #             nickname -> Nullable<Text>,
+/// #         }
+/// #     }
+/// # }
+/// # diesel_as_wrap::wrap! {
+/// #     target = uuid::Uuid;
+/// #     sql_type = Binary;
+/// #     pub mod uuid_wrap;
+/// #     fn to_sql<[u8]>(self, out){
+/// #         let bytes: &[u8] = self.0.as_bytes();
+/// #         bytes.to_sql(out)
+/// #     }
+/// #     fn from_sql<Vec<u8>>(bytes){
+/// #         let value = <Vec<u8>>::from_sql(bytes)?;
+/// #         uuid::Uuid::from_slice(&value)
+/// #             .map(As)
+/// #             .map_err(|e| e.into())
+/// #     }
+/// # }
+/// use uuid::Uuid;
+///
+/// #[diesel_as_wrap::model(Uuid => uuid_wrap)]
+/// #[derive(Debug, Clone, PartialEq, Queryable, Selectable, Insertable)]
+/// #[diesel(table_name = schema::users)]
+/// pub struct User {
+///     id: i32,
+///     uuid: Uuid,
+///     referrer: Option<Uuid>,
+///     nickname: Option<String>,
+/// }
+///
+/// # fn main() {
+/// let mut conn = SqliteConnection::establish(":memory:").unwrap();
+/// diesel::sql_query("CREATE TABLE users (id INTEGER PRIMARY KEY, uuid BLOB NOT NULL, referrer BLOB, nickname TEXT)")
+///     .execute(&mut conn)
+///     .unwrap();
+///
+/// let user = User { id: 1, uuid: Uuid::from_u128(7), referrer: Some(Uuid::nil()), nickname: Some("ferris".to_string()) };
+/// diesel::insert_into(schema::users::table).values(user.clone()).execute(&mut conn).unwrap();
+/// let loaded: User = schema::users::table.select(User::as_select()).first(&mut conn).unwrap();
+/// assert_eq!(loaded, user);
+/// # }
+/// ```
+///
+#[cfg(feature = "derive")]
+pub use diesel_as_wrap_derive::model;
+
+#[cfg(feature = "bigdecimal")]
+pub mod bigdecimal;
+#[cfg(feature = "bytes")]
+pub mod bytes;
+#[cfg(feature = "chrono")]
+pub mod chrono;
+#[cfg(feature = "citext")]
+pub mod citext;
+#[cfg(any(feature = "zstd", feature = "deflate"))]
+pub mod compressed;
+#[cfg(feature = "duration")]
+pub mod duration;
+#[cfg(feature = "email")]
+pub mod email;
+#[cfg(feature = "encrypted")]
+pub mod encrypted;
+#[cfg(feature = "flatbuffers")]
+pub mod flatbuffers;
+#[cfg(feature = "hstore")]
+pub mod hstore;
+#[cfg(feature = "json")]
+pub mod json;
+#[cfg(feature = "ltree")]
+pub mod ltree;
+#[cfg(feature = "macaddr")]
+pub mod macaddr;
+#[cfg(feature = "mime")]
+pub mod mime;
+#[cfg(feature = "net")]
+pub mod net;
+#[cfg(feature = "path")]
+pub mod path;
+#[cfg(feature = "phonenumber")]
+pub mod phonenumber;
+#[cfg(feature = "postgres")]
+pub mod record;
+#[cfg(feature = "rkyv")]
+pub mod rkyv;
+#[cfg(feature = "rust_decimal")]
+pub mod rust_decimal;
+#[cfg(feature = "secrecy")]
+pub mod secrecy;
+#[cfg(feature = "semver")]
+pub mod semver;
+#[cfg(feature = "snowflake")]
+pub mod snowflake;
+#[cfg(feature = "string")]
+pub mod string;
+#[cfg(feature = "system_time")]
+pub mod system_time;
+#[cfg(feature = "time")]
+pub mod time;
+#[cfg(feature = "ulid")]
+pub mod ulid;
+#[cfg(feature = "unsigned")]
+pub mod unsigned;
+#[cfg(feature = "url")]
+pub mod url;
+#[cfg(feature = "uuid")]
+pub mod uuid;
+#[cfg(feature = "xml")]
+pub mod xml;
+
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "inventory")]
+    pub use ::inventory;
+    #[cfg(feature = "rkyv")]
+    pub use ::rkyv;
+    #[cfg(feature = "utoipa")]
+    pub use ::utoipa;
+}
+
+#[cfg(feature = "inventory")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __register {
+    ($info:expr) => {
+        $crate::__private::inventory::submit! { $info }
+    };
+}
+
+#[cfg(not(feature = "inventory"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __register {
+    ($info:expr) => {};
+}
 
 ///
 /// This macro helps creating wrapper types for use of not natively supported types in structs.
@@ -9,169 +254,3462 @@
 /// The As type can be used for NonNullable sqlTypes and the AsOption for Nullable.\
 /// The types of the targeted struct fields should be your type or Option<your type> respectively.\
 /// The syntax for using this macro is the following:\
-/// ```
-/// wrap! {
-///     target = $type_to_wrap$;
+/// ```text
+/// wrap! {
+///     target = $type_to_wrap$ [where $generic_param$: $bounds$, ...];
+///     sql_type = $type_represented_as_sql$; | sql_type(pg = $sql_type$, mysql = $sql_type$, sqlite = $sql_type$);
+///     [#[$attribute$] ...] $module_visability$ mod $name_of_wrappers_module$; | impl;
+///     [vis(As) = [$visability$];]
+///     [vis(AsOption) = [$visability$];]
+///     [attrs(As) = [#[$attribute$], ...];]
+///     [attrs(AsOption) = [#[$attribute$], ...];]
+///     [derive($derive$, ...);]
+///     [alias(As) = $alias_visability$ $alias_name$;]
+///     [alias(AsOption) = $alias_visability$ $alias_name$;]
+///     [types = $as_name$, $as_option_name$;]
+///     [delegate(As) = [$trait$, ...];]
+///     [delegate(AsOption) = [$trait$, ...];]
+///     [debug = display | redacted;]
+///     [backend = $backend$;]
+///     [round_trip_check;]
+///     fn to_sql<$parsing_type$[where 'a,'b,...]>(self, out){
+///         ...
+///         let foo: &$parsing_type$ = ...;
+///         foo.to_sql(out)
+///     }
+///     fn from_sql<$parsing_type$[where 'a,'b,...]>(bytes){
+///         let value = <$parsing_type$>::from_sql(bytes)?;
+///         ...
+///     }
+///     | to<$parsing_type$> = |value, out| ...; from<$parsing_type$> = |bytes| ...;
+///     | via = $parsing_type$;
+/// }
+/// ```
+///
+/// - type_to_wrap is the type you like to wrap (Needs to be fully qualified).
+/// - [where ...] makes the wrappers generic over the parameters bounded in it, see [generic targets](#generic-targets).
+/// - type_represented_as_sql is the sql type your type should be represented as.
+///   It has to be the non nullable type (`Text`, not `Nullable<Text>`), AsOption is the wrapper for `Nullable<sql_type>`.
+///   AsOption writes and reads `None` as `NULL` and is only an expression of `Nullable<sql_type>`.
+///   Passing `Nullable<...>` is rejected with a compile error.
+/// - name_of_wrappers_module is the module created by this macro containing the wrappers.
+/// - [#[$attribute$] ...] are optional outer attributes and doc comments forwarded to the created module
+///   (for example `#[cfg(feature = "uuid")]` or `#[allow(missing_docs)]`).
+///   `cfg` attributes are applied to the aliases as well, attributes of the types belong in `attrs(As)` and `attrs(AsOption)`.
+/// - impl; implements the traits on the target itself instead of creating a module, see [existing newtypes](#existing-newtypes).
+/// - [where 'a,'b,...] is an optional list of lifetime specifiers for higher rank trait bounds for the parsing type.
+/// - parsing_type is the type that can already be parsed by diesel and is closest to your type.
+///   It is used to deserialise the raw bytes from diesel and is used to return the bytes when serializing.
+///   `fn from_sql` and `from` also accept the borrowed parsing types `&[u8]` (for `Binary`) and `&str` (for `Text`),
+///   optionally with a lifetime (`&'a [u8]`) that can't be named in the body.
+///   `bytes` is then the borrowed value itself instead of the raw value, so rows are decoded without allocating.
+///   The borrow only lives as long as the row and can't be kept in the target.
+/// - [vis(As) = ...] and [vis(AsOption) = ...] optionally change the visibility of the As and AsOption type (`pub` by default).
+///   The visibility is relative to the created module, so `pub(super)` limits a type to the module invoking the macro.
+///   This allows exposing only one of the types even though both live inside the same module.
+/// - [attrs(As) = ...] and [attrs(AsOption) = ...] are optional lists of attributes forwarded to the As and AsOption type.
+///   They can be used for derives and attributes of crates this crate doesn't know about
+///   (for example `#[derive(utoipa::ToSchema)]` or `#[serde(transparent)]`).
+/// - [derive(...)] is an optional list of derives forwarded to both the As and AsOption type (for example `derive(Clone, PartialEq, Eq, Hash);`).
+///   It is a shorthand for the same `#[derive(...)]` in `attrs(As)` and `attrs(AsOption)` and can be combined with them.
+/// - [alias(As) = ...] and [alias(AsOption) = ...] optionally emit a `use` next to the created module
+///   re-exporting the As or AsOption type under the given name (for example `alias(As) = pub UuidAs;`).
+///   This keeps attribute values and imports short.
+/// - [types = ...] optionally names both types at once, it is a shorthand for `alias(As) = pub ...;` and `alias(AsOption) = pub ...;`
+///   and can't be combined with them.
+///   The names live next to the created module, so the wrappers of several targets can be used from the same module.
+/// - [delegate(As) = ...] and [delegate(AsOption) = ...] optionally implement traits for the As and AsOption type by delegating to the target.
+///   Supported are `PartialEq`, `Eq`, `PartialOrd`, `Ord` and `Hash`.
+///   Sorted or hashed collections of wrappers then behave exactly like collections of the target.
+///   With the `utoipa` feature `ToSchema` is supported as well, implementing `utoipa::ToSchema` and `utoipa::PartialSchema`
+///   with the schema of the target (or `Option<target>` for AsOption).
+/// - [debug = ...] optionally changes how As (and the other wrappers containing it) implement `Debug`.
+///   By default the target's `Debug` is used, `display` uses the target's `Display` and `redacted` hides the value.
+///   Diesel prints bound values through `Debug` in [`debug_query`](diesel::debug_query) and the query instrumentation,
+///   so this decides how wrapped values appear in query logs.
+/// - via = ... replaces the `fn to_sql` and `fn from_sql` sections when the target converts `Into` and `TryFrom` the parsing type.
+///   The target has to implement `Clone` and the `TryFrom` error is returned as deserialization error.
+/// - [backend = ...] optionally restricts the `FromSql` and `ToSql` impls of As to a single backend (for example `diesel::pg::Pg`).
+///   Without it they are generic over every backend.
+/// - [round_trip_check] optionally reads every value written by `to_sql` back with `from_sql` in builds with debug assertions
+///   and panics if the result differs, see [round trip checks](#round-trip-checks).
+///
+/// The sections can be given in any order and stray `;` or `,` between them are ignored.
+/// Missing or duplicate sections are reported by name:
+/// ```
+/// # use diesel_as_wrap::wrap;
+/// wrap! {
+///     pub mod u8_wrap;
+///     fn from_sql<i16>(bytes) {
+///         let value = i16::from_sql(bytes)?;
+///         u8::try_from(value).map(As).map_err(Into::into)
+///     };
+///     fn into_sql<i16>(self) {
+///         i16::from(self.0)
+///     };
+///     delegate(As) = [PartialEq, Eq,];
+///     sql_type = SmallInt;
+///     target = u8;
+/// }
+///
+/// assert!(u8_wrap::As(1) == u8_wrap::As(1));
+/// ```
+/// ```compile_fail
+/// # use diesel_as_wrap::wrap;
+/// wrap! {
+///     target = u8;
+///     sql_type = SmallInt;
+///     pub mod u8_wrap;
+///     fn into_sql<i16>(self) {
+///         i16::from(self.0)
+///     }
+///     // error: missing `fn from_sql` section
+/// }
+/// ```
+///
+/// If the parsing type can't be borrowed from self (because it has to be computed first)
+/// `fn into_sql<$parsing_type$>(self){ ... }` can be used instead of `to_sql`.
+/// Its body returns the owned parsing type which is then written using [`ToSqlOwned`].
+/// This requires the `postgres`, `mysql` or `sqlite` feature for the used backend.
+///
+/// Wrappers that build text or bytes in a buffer (base64, json, compression, ...) can use
+/// `fn buffered_sql<String or Vec<u8>>(self, buffer){ ... }` instead.
+/// Its body fills the cleared `buffer` and returns `Result<(), FillError>`,
+/// the buffer is a reused thread local on Postgres and MySQL (see [`ToSqlBuffered`]), so bulk inserts don't allocate per value:
+#[cfg_attr(feature = "sqlite", doc = "```")]
+#[cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+/// # use diesel::prelude::*;
+/// # use diesel::sql_types::Text;
+/// # use diesel_as_wrap::wrap;
+/// wrap! {
+///     target = Vec<u32>;
+///     sql_type = Text;
+///     pub mod csv_wrap;
+///     fn buffered_sql<String>(self, buffer) {
+///         use std::fmt::Write;
+///         for (i, value) in self.0.iter().enumerate() {
+///             if i > 0 {
+///                 buffer.push(',');
+///             }
+///             write!(buffer, "{value}")?;
+///         }
+///         Ok(())
+///     }
+///     fn from_sql<String>(bytes) {
+///         let text = String::from_sql(bytes)?;
+///         text.split(',')
+///             .filter(|value| !value.is_empty())
+///             .map(str::parse)
+///             .collect::<Result<_, _>>()
+///             .map(As)
+///             .map_err(Into::into)
+///     }
+/// }
+///
+/// # fn main() {
+/// let mut conn = SqliteConnection::establish(":memory:").unwrap();
+/// let stored: String = diesel::select(csv_wrap::As(vec![1, 2, 3]).into_sql::<Text>()).get_result(&mut conn).unwrap();
+/// assert_eq!(stored, "1,2,3");
+/// # }
+/// ```
+///
+/// The sections can be written as closures as well, the parameters are named freely and the bodies are the same.
+/// `to<$parsing_type$> = |value, out| ...;` is `fn to_sql` with `value` borrowing the target,
+/// `to<$parsing_type$> = |value| ...;` is `fn into_sql` and `from<$parsing_type$> = |bytes| ...;` is `fn from_sql`.
+/// A closure with a different number of parameters is reported with the expected form:
+#[cfg_attr(feature = "sqlite", doc = "```")]
+#[cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+/// # use diesel::prelude::*;
+/// # use diesel::sql_types::SmallInt;
+/// # use diesel_as_wrap::wrap;
+/// wrap! {
+///     target = u8;
+///     sql_type = SmallInt;
+///     pub mod u8_wrap;
+///     to<i16> = |value| i16::from(*value);
+///     from<i16> = |bytes| u8::try_from(i16::from_sql(bytes)?).map(As).map_err(Into::into);
+/// }
+///
+/// # fn main() {
+/// let mut conn = SqliteConnection::establish(":memory:").unwrap();
+/// let loaded: u8_wrap::As = diesel::select(u8_wrap::As(7).into_sql::<SmallInt>()).get_result(&mut conn).unwrap();
+/// assert_eq!(loaded.0, 7);
+/// # }
+/// ```
+/// ```compile_fail
+/// # use diesel_as_wrap::wrap;
+/// wrap! {
+///     target = String;
+///     sql_type = Text;
+///     pub mod string_wrap;
+///     to<String> = |value, out| value.to_sql(out);
+///     from<String> = |bytes, extra| String::from_sql(bytes).map(As);
+///     // error: `from` takes a closure with `bytes` as only parameter: `from<$parsing_type$> = |bytes| ...;`
+/// }
+/// ```
+///
+/// Targets that already convert to and from the parsing type can use `via = $parsing_type$;` instead of both sections.
+/// It writes the value like `fn into_sql` (so it needs the same backend features) and reads it through `TryFrom`:
+#[cfg_attr(feature = "sqlite", doc = "```")]
+#[cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+/// # use diesel::prelude::*;
+/// # use diesel::sql_types::BigInt;
+/// # use diesel_as_wrap::wrap;
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// pub struct UserId(u32);
+///
+/// impl From<UserId> for i64 {
+///     fn from(id: UserId) -> Self {
+///         id.0.into()
+///     }
+/// }
+///
+/// impl TryFrom<i64> for UserId {
+///     type Error = std::num::TryFromIntError;
+///
+///     fn try_from(value: i64) -> Result<Self, Self::Error> {
+///         u32::try_from(value).map(UserId)
+///     }
+/// }
+///
+/// wrap! {
+///     target = crate::UserId;
+///     sql_type = BigInt;
+///     via = i64;
+///     pub mod user_id_wrap;
+/// }
+///
+/// # fn main() {
+/// let mut conn = SqliteConnection::establish(":memory:").unwrap();
+/// let loaded: user_id_wrap::As = diesel::select(user_id_wrap::As(UserId(7)).into_sql::<BigInt>()).get_result(&mut conn).unwrap();
+/// assert_eq!(loaded.0, UserId(7));
+///
+/// let negative = diesel::select((-1i64).into_sql::<BigInt>()).get_result::<user_id_wrap::As>(&mut conn);
+/// assert!(negative.is_err());
+/// # }
+/// ```
+///
+/// # Round trip checks
+/// `round_trip_check;` makes `to_sql` of As decode the bytes it wrote with `from_sql` and `debug_assert!` that
+/// the result equals the value, so asymmetric `to_sql`/`from_sql` pairs fail at the first write during development.
+/// The check is compiled only with debug assertions (`#[cfg(debug_assertions)]`), release builds write the value once.
+/// The target has to implement `PartialEq` and the backend [`RoundTrip`](crate::RoundTrip).
+/// Decoding without a database needs the `round_trip_check` feature and works on postgres and mysql,
+/// sqlite (and postgres and mysql without the feature) skip the check, use [`debug_assert_round_trip!`](crate::debug_assert_round_trip) there.
+/// It can't be combined with `impl;` or `wrapper = ...;`.
+#[cfg_attr(all(feature = "postgres", feature = "round_trip_check"), doc = "```")]
+#[cfg_attr(not(all(feature = "postgres", feature = "round_trip_check")), doc = "```ignore")]
+/// # use diesel::pg::{Pg, PgMetadataLookup, PgTypeMetadata};
+/// # use diesel::query_builder::bind_collector::RawBytesBindCollector;
+/// # use diesel::query_builder::BindCollector;
+/// # use diesel::sql_types::Text;
+/// # use diesel_as_wrap::wrap;
+/// # struct NoTypes;
+/// # impl PgMetadataLookup for NoTypes {
+/// #     fn lookup_type(&mut self, _: &str, _: Option<&str>) -> PgTypeMetadata {
+/// #         unreachable!()
+/// #     }
+/// # }
+/// #[derive(Debug, Clone, PartialEq)]
+/// pub struct Name(String);
+///
+/// wrap! {
+///     target = crate::Name;
+///     sql_type = Text;
+///     pub mod name_wrap;
+///     round_trip_check;
+///     fn into_sql<String>(self) {
+///         self.0.0.to_lowercase()
+///     }
+///     fn from_sql<String>(bytes) {
+///         String::from_sql(bytes).map(|name| As(crate::Name(name)))
+///     }
+/// }
+///
+/// # fn main() {
+/// // binding the value (like every query does) runs `to_sql`
+/// let bind = |name: &str| {
+///     RawBytesBindCollector::<Pg>::new().push_bound_value::<Text, _>(&name_wrap::As(Name(name.to_string())), &mut NoTypes)
+/// };
+/// assert!(bind("ferris").is_ok());
+/// // `to_sql` lowercases, so "Ferris" is read back as "ferris"
+/// assert!(std::panic::catch_unwind(|| bind("Ferris")).is_err());
+/// # }
+/// ```
+///
+/// # Generic targets
+/// A target with a where clause (`target = crate::Tagged<T> where T: Clone;`) creates wrappers that are generic
+/// over the parameters bounded in it (`T`), every parameter has to appear once at the start of a bound.
+/// The bounds are added to As, AsOption and all of their impls, the bodies can use the parameters.
+/// Fields use the wrappers with the concrete parameters (`#[diesel(deserialize_as = tagged_wrap::As<String>)]`).
+/// Such a module contains As, AsOption and AsStrict, the aliases `SqlType`, `Target` and `Inner` (generic over the parameters)
+/// and `Expression`, the constants and the functions `expression` and `nullable_expression`, it can't be combined with `sql_type(...)`, `wrapper`, `backend`, `delegate` or `debug`.
+#[cfg_attr(feature = "sqlite", doc = "```")]
+#[cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+/// # use diesel::prelude::*;
+/// # use diesel::sql_types::Text;
+/// # use diesel_as_wrap::wrap;
+/// #[derive(Debug, Clone, PartialEq)]
+/// pub struct Tagged<T>(pub T);
+///
+/// wrap! {
+///     target = crate::Tagged<T> where T: Clone + Into<String> + TryFrom<String>, T::Error: std::error::Error + Send + Sync + 'static;
+///     sql_type = Text;
+///     pub mod tagged_wrap;
+///     fn into_sql<String>(self) {
+///         self.0 .0.clone().into()
+///     }
+///     fn from_sql<String>(bytes) {
+///         let text = String::from_sql(bytes)?;
+///         T::try_from(text).map(|value| As(crate::Tagged(value))).map_err(Into::into)
+///     }
+/// }
+///
+/// # fn main() {
+/// let mut conn = SqliteConnection::establish(":memory:").unwrap();
+/// let tag = tagged_wrap::As(Tagged("rust".to_string()));
+/// let loaded: tagged_wrap::As<String> = diesel::select(tag.into_sql::<Text>()).get_result(&mut conn).unwrap();
+/// assert_eq!(loaded.0, Tagged("rust".to_string()));
+///
+/// let inner: tagged_wrap::Inner<String> = loaded.0;
+/// let none = tagged_wrap::nullable_expression(None::<String>.into_sql::<diesel::sql_types::Nullable<Text>>());
+/// let loaded: tagged_wrap::AsOption<String> = diesel::select(none).get_result(&mut conn).unwrap();
+/// assert_eq!(inner.0, "rust");
+/// assert!(loaded.0.is_none());
+/// # }
+/// ```
+///
+/// # Existing newtypes
+/// `impl;` instead of the `mod` section implements `FromSqlRow`, `AsExpression`, `FromSql` and `ToSql` directly on the target,
+/// no module and no wrappers are created, so fields of the target need no `deserialize_as`/`serialize_as`
+/// and `Option<target>` works for nullable columns.
+/// The target has to be a type of the invoking crate implementing `Debug` (required by `ToSql`), in the bodies `As` names the target itself,
+/// so `self.0` is the field of the newtype and `from_sql` constructs it with `Self(...)` (`As` is only a type alias there).
+/// It can be combined with `backend = ...;` and `via = ...;` (converting the target itself) but not with the sections configuring the wrappers.
+#[cfg_attr(feature = "sqlite", doc = "```")]
+#[cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+/// # use diesel::prelude::*;
+/// # use diesel_as_wrap::wrap;
+/// # mod schema {
+/// #     diesel::table! {
+/// #         users (id) {
+/// #             id -> BigInt,
+/// #             invited_by -> Nullable<BigInt>,
+/// #         }
+/// #     }
+/// # }
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// pub struct UserId(i64);
+///
+/// wrap! {
+///     target = crate::UserId;
+///     sql_type = BigInt;
+///     impl;
+///     fn to_sql<i64>(self, out) {
+///         self.0.to_sql(out)
+///     }
+///     fn from_sql<i64>(bytes) {
+///         i64::from_sql(bytes).map(Self)
+///     }
+/// }
+///
+/// #[derive(Debug, PartialEq, Queryable, Selectable, Insertable)]
+/// #[diesel(table_name = schema::users)]
+/// pub struct User {
+///     id: UserId,
+///     invited_by: Option<UserId>,
+/// }
+///
+/// # fn main() {
+/// let mut conn = SqliteConnection::establish(":memory:").unwrap();
+/// diesel::sql_query("CREATE TABLE users (id INTEGER PRIMARY KEY, invited_by INTEGER)").execute(&mut conn).unwrap();
+///
+/// let users = [User { id: UserId(1), invited_by: None }, User { id: UserId(2), invited_by: Some(UserId(1)) }];
+/// diesel::insert_into(schema::users::table).values(&users[..]).execute(&mut conn).unwrap();
+///
+/// let invited: Vec<User> = schema::users::table
+///     .filter(schema::users::invited_by.eq(UserId(1)))
+///     .select(User::as_select())
+///     .load(&mut conn)
+///     .unwrap();
+/// assert_eq!(invited, [User { id: UserId(2), invited_by: Some(UserId(1)) }]);
+/// # }
+/// ```
+///
+/// When a backend represents the target with a different sql type, `sql_type(pg = ..., mysql = ..., sqlite = ...);`
+/// can be given instead of `sql_type = ...;`.
+/// The wrappers then implement `FromSql` and `ToSql` only for the listed backends, each with its own sql type,
+/// and can be used as expression of every sql type (a wrong one fails when the query is built for a backend, see [`BoundValue`]).
+/// The bodies are used for every listed backend, so the parsing type has to support all of their sql types.
+/// Listed backends require the `postgres`, `mysql` or `sqlite` feature of this crate, the others are skipped.
+/// Because there is no single sql type such a module contains no `SqlType` alias, `expression` functions,
+/// `as_sql_debug` method or array wrappers.
+#[cfg_attr(all(feature = "postgres", feature = "json"), doc = "```")]
+#[cfg_attr(not(all(feature = "postgres", feature = "json")), doc = "```ignore")]
+/// # use diesel::prelude::*;
+/// # use diesel_as_wrap::wrap;
+/// # mod schema {
+/// #     diesel::table! {
+/// #         documents (id) {
+/// #             id -> Integer,
+/// #             body -> Jsonb,
+/// #         }
+/// #     }
+/// # }
+/// wrap! {
+///     target = serde_json::Value;
+///     sql_type(pg = Jsonb, mysql = Json);
+///     pub mod document_wrap;
+///     fn to_sql<serde_json::Value>(self, out) {
+///         let value: &serde_json::Value = &self.0;
+///         value.to_sql(out)
+///     }
+///     fn from_sql<serde_json::Value>(bytes) {
+///         serde_json::Value::from_sql(bytes).map(As)
+///     }
+/// }
+///
+/// #[derive(Debug, Queryable, Selectable, Insertable)]
+/// #[diesel(table_name = schema::documents)]
+/// #[diesel(check_for_backend(diesel::pg::Pg))]
+/// pub struct Document {
+///     id: i32,
+///     #[diesel(deserialize_as = document_wrap::As)]
+///     #[diesel(serialize_as = document_wrap::As)]
+///     body: serde_json::Value,
+/// }
+///
+/// assert_eq!(document_wrap::SQL_TYPE_NAME, "pg = Jsonb, mysql = Json");
+/// ```
+/// ```compile_fail
+/// # use diesel_as_wrap::wrap;
+/// wrap! {
+///     target = u8;
+///     sql_type(oracle = SmallInt);
+///     pub mod u8_wrap;
+///     fn into_sql<i16>(self) {
+///         i16::from(self.0)
+///     }
+///     fn from_sql<i16>(bytes) {
+///         let value = i16::from_sql(bytes)?;
+///         u8::try_from(value).map(As).map_err(Into::into)
+///     }
+///     // error: unknown backend `oracle` in `sql_type(...)`, expected `pg`, `mysql` or `sqlite`
+/// }
+/// ```
+///
+/// With `backend = ...;` the bodies are compiled for that backend only,
+/// so `bytes` and `out` have its concrete types and backend specific APIs (like `PgValue::as_bytes`)
+/// can be used inside of them.
+/// This can't be combined with `sql_type(...)` or `wrapper = ...;`.
+#[cfg_attr(feature = "postgres", doc = "```")]
+#[cfg_attr(not(feature = "postgres"), doc = "```ignore")]
+/// # use diesel_as_wrap::wrap;
+/// #[derive(Debug, PartialEq)]
+/// pub struct Digest(Vec<u8>);
+///
+/// wrap! {
+///     target = crate::Digest;
+///     sql_type = Binary;
+///     backend = diesel::pg::Pg;
+///     pub mod digest_wrap;
+///     fn to_sql<[u8]>(self, out) {
+///         let bytes: &[u8] = &self.0 .0;
+///         bytes.to_sql(out)
+///     }
+///     fn from_sql<Vec<u8>>(bytes) {
+///         // `bytes` is a `PgValue`, its raw bytes are copied without going through `Vec<u8>`'s `FromSql`
+///         Ok(As(crate::Digest(bytes.as_bytes().to_vec())))
+///     }
+/// }
+/// # fn main() {}
+/// ```
+///
+/// The As type is `repr(transparent)` over the target.
+/// This allows borrowing a target as As (`As::from_ref`) and converting slices and Vecs
+/// between both types without copying or reallocating (`As::from_target_slice`, `As::as_target_slice`,
+/// `As::from_targets` and `As::into_targets`).
+/// ```
+/// # use diesel_as_wrap::wrap;
+/// # wrap! {
+/// #     target = uuid::Uuid;
+/// #     sql_type = Binary;
+/// #     pub mod uuid_wrap;
+/// #     fn to_sql<[u8]>(self, out){
+/// #         let bytes: &[u8] = self.0.as_bytes();
+/// #         bytes.to_sql(out)
+/// #     }
+/// #     fn from_sql<Vec<u8>>(bytes){
+/// #         let value = <Vec<u8>>::from_sql(bytes)?;
+/// #         uuid::Uuid::from_slice(&value)
+/// #             .map(As)
+/// #             .map_err(|e| e.into())
+/// #     }
+/// # }
+/// let rows: Vec<uuid_wrap::As> = (0..1000).map(|_| uuid_wrap::As(uuid::Uuid::nil())).collect();
+/// let pointer = rows.as_ptr() as *const uuid::Uuid;
+/// let ids: Vec<uuid::Uuid> = uuid_wrap::As::into_targets(rows);
+/// assert_eq!(ids.as_ptr(), pointer);
+/// assert_eq!(uuid_wrap::As::from_target_slice(&ids).len(), 1000);
+/// ```
+///
+/// Borrowed wrappers (`&As` and `&AsOption`) are expressions as well,
+/// so one value can be bound in several query fragments without moving or cloning it.
+/// Together with `As::from_ref` a borrowed target can be used directly:
+#[cfg_attr(feature = "sqlite", doc = "```")]
+#[cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+/// # use diesel::prelude::*;
+/// # use diesel_as_wrap::wrap;
+/// # mod schema {
+/// #     diesel::table! {
+/// #         friendships (user_id, friend_id) {
+/// #             user_id -> Binary,
+/// #             friend_id -> Binary,
+/// #         }
+/// #     }
+/// # }
+/// # use schema::friendships;
+/// # wrap! {
+/// #     target = uuid::Uuid;
+/// #     sql_type = Binary;
+/// #     pub mod uuid_wrap;
+/// #     fn to_sql<[u8]>(self, out){
+/// #         let bytes: &[u8] = self.0.as_bytes();
+/// #         bytes.to_sql(out)
+/// #     }
+/// #     fn from_sql<Vec<u8>>(bytes){
+/// #         let value = <Vec<u8>>::from_sql(bytes)?;
+/// #         uuid::Uuid::from_slice(&value)
+/// #             .map(As)
+/// #             .map_err(|e| e.into())
+/// #     }
+/// # }
+/// # fn main() {
+/// let mut conn = SqliteConnection::establish(":memory:").unwrap();
+/// # diesel::sql_query("CREATE TABLE friendships (user_id BLOB, friend_id BLOB, PRIMARY KEY (user_id, friend_id))")
+/// #     .execute(&mut conn)
+/// #     .unwrap();
+/// let user = uuid::Uuid::from_u128(1);
+/// let id = uuid_wrap::As::from_ref(&user);
+/// let count: i64 = friendships::table
+///     .filter(friendships::user_id.eq(id).or(friendships::friend_id.eq(id)))
+///     .count()
+///     .get_result(&mut conn)
+///     .unwrap();
+/// assert_eq!(count, 0);
+/// # }
+/// ```
+///
+/// `#[diesel(serialize_as = ...)]` moves the field into As, so inserting large values from a borrowed model would need a clone.
+/// `AsRef<'a>` wraps a `&'a target` and writes it like As, use it for fields of borrowing insert structs:
+#[cfg_attr(feature = "sqlite", doc = "```")]
+#[cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+/// # use diesel::prelude::*;
+/// # use diesel_as_wrap::wrap;
+/// # mod schema {
+/// #     diesel::table! {
+/// #         documents (id) {
+/// #             id -> Integer,
+/// #             body -> Text,
+/// #         }
+/// #     }
+/// # }
+/// #[derive(Debug, PartialEq)]
+/// pub struct Body(String);
+///
+/// wrap! {
+///     target = crate::Body;
+///     sql_type = Text;
+///     pub mod body_wrap;
+///     fn to_sql<str>(self, out) {
+///         let text: &str = &self.0 .0;
+///         text.to_sql(out)
+///     }
+///     fn from_sql<&str>(bytes) {
+///         Ok(As(crate::Body(bytes.to_owned())))
+///     }
+/// }
+///
+/// #[derive(Insertable)]
+/// #[diesel(table_name = schema::documents)]
+/// pub struct NewDocument<'a> {
+///     id: i32,
+///     #[diesel(serialize_as = body_wrap::AsRef<'a>)]
+///     body: &'a Body,
+/// }
+///
+/// # fn main() {
+/// let mut conn = SqliteConnection::establish(":memory:").unwrap();
+/// diesel::sql_query("CREATE TABLE documents (id INTEGER PRIMARY KEY, body TEXT NOT NULL)").execute(&mut conn).unwrap();
+///
+/// let body = Body("a large document".repeat(1000));
+/// diesel::insert_into(schema::documents::table).values(NewDocument { id: 1, body: &body }).execute(&mut conn).unwrap();
+///
+/// let loaded: body_wrap::As = schema::documents::table.select(schema::documents::body).first(&mut conn).unwrap();
+/// assert_eq!(loaded.0, body);
+/// # }
+/// ```
+///
+/// As, AsOption and AsRef (and references to As and AsOption) are expressions of their sql type,
+/// so they can be bound as parameters anywhere in the query dsl, in `filter`, `values`, `set` and `eq_any`:
+#[cfg_attr(feature = "sqlite", doc = "```")]
+#[cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+/// # use diesel::prelude::*;
+/// # use diesel_as_wrap::wrap;
+/// # mod schema {
+/// #     diesel::table! {
+/// #         users (id) {
+/// #             id -> Binary,
+/// #             referrer -> Nullable<Binary>,
+/// #         }
+/// #     }
+/// # }
+/// # use schema::users;
+/// # wrap! {
+/// #     target = uuid::Uuid;
+/// #     sql_type = Binary;
+/// #     pub mod uuid_wrap;
+/// #     fn to_sql<[u8]>(self, out){
+/// #         let bytes: &[u8] = self.0.as_bytes();
+/// #         bytes.to_sql(out)
+/// #     }
+/// #     fn from_sql<&[u8]>(bytes){
+/// #         uuid::Uuid::from_slice(bytes)
+/// #             .map(As)
+/// #             .map_err(|e| e.into())
+/// #     }
+/// # }
+/// use uuid_wrap::{As, AsOption};
+///
+/// # fn main() {
+/// let mut conn = SqliteConnection::establish(":memory:").unwrap();
+/// # diesel::sql_query("CREATE TABLE users (id BLOB PRIMARY KEY, referrer BLOB)").execute(&mut conn).unwrap();
+/// let (alice, bob, carol) = (uuid::Uuid::from_u128(1), uuid::Uuid::from_u128(2), uuid::Uuid::from_u128(3));
+/// diesel::insert_into(users::table)
+///     .values(&vec![
+///         (users::id.eq(As(alice)), users::referrer.eq(AsOption(None))),
+///         (users::id.eq(As(bob)), users::referrer.eq(AsOption(Some(As(alice))))),
+///     ])
+///     .execute(&mut conn)
+///     .unwrap();
+///
+/// diesel::update(users::table.filter(users::id.eq(As(alice))))
+///     .set(users::referrer.eq(As(carol)))
+///     .execute(&mut conn)
+///     .unwrap();
+///
+/// let invited: Vec<As> = users::table
+///     .filter(users::referrer.eq(As(alice)))
+///     .or_filter(users::referrer.eq(&AsOption(Some(As(carol)))))
+///     .select(users::id)
+///     .order(users::id)
+///     .load(&mut conn)
+///     .unwrap();
+/// assert_eq!(invited.iter().map(|id| id.0).collect::<Vec<_>>(), [alice, bob]);
+///
+/// let known: i64 = users::table
+///     .filter(users::id.eq_any(As::from_targets(vec![alice, carol])))
+///     .count()
+///     .get_result(&mut conn)
+///     .unwrap();
+/// assert_eq!(known, 1);
+/// # }
+/// ```
+///
+/// `As::as_sql_debug` shows the serialized value as sql literal, rendered by the database itself.
+/// This shows exactly which text or bytes a wrapper writes (requires the feature of the used backend):
+#[cfg_attr(feature = "sqlite", doc = "```")]
+#[cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+/// # use diesel::prelude::*;
+/// # use diesel_as_wrap::wrap;
+/// # wrap! {
+/// #     target = uuid::Uuid;
+/// #     sql_type = Binary;
+/// #     pub mod uuid_wrap;
+/// #     fn to_sql<[u8]>(self, out){
+/// #         let bytes: &[u8] = self.0.as_bytes();
+/// #         bytes.to_sql(out)
+/// #     }
+/// #     fn from_sql<Vec<u8>>(bytes){
+/// #         let value = <Vec<u8>>::from_sql(bytes)?;
+/// #         uuid::Uuid::from_slice(&value)
+/// #             .map(As)
+/// #             .map_err(|e| e.into())
+/// #     }
+/// # }
+/// # fn main() {
+/// let mut conn = SqliteConnection::establish(":memory:").unwrap();
+/// let id = uuid_wrap::As(uuid::Uuid::from_u128(0xff));
+/// assert_eq!(id.as_sql_debug(&mut conn).unwrap(), "X'000000000000000000000000000000FF'");
+/// # }
+/// ```
+///
+/// Bound values are printed in query logs through the `Debug` implementation of the wrapper,
+/// which the `debug` section switches to the target's `Display` or to a redacted placeholder:
+#[cfg_attr(feature = "sqlite", doc = "```")]
+#[cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+/// # use diesel::prelude::*;
+/// # use diesel::sql_types::{Binary, Text};
+/// # use diesel::sqlite::Sqlite;
+/// # use diesel_as_wrap::wrap;
+/// pub struct Password(String);
+///
+/// wrap! {
+///     target = uuid::Uuid;
+///     sql_type = Binary;
+///     pub mod uuid_wrap;
+///     debug = display;
+///     fn to_sql<[u8]>(self, out){
+///         let bytes: &[u8] = self.0.as_bytes();
+///         bytes.to_sql(out)
+///     }
+///     fn from_sql<Vec<u8>>(bytes){
+///         let value = <Vec<u8>>::from_sql(bytes)?;
+///         uuid::Uuid::from_slice(&value)
+///             .map(As)
+///             .map_err(|e| e.into())
+///     }
+/// }
+///
+/// wrap! {
+///     target = crate::Password;
+///     sql_type = Text;
+///     pub mod password_wrap;
+///     debug = redacted;
+///     fn to_sql<str>(self, out){
+///         let password: &str = &self.0.0;
+///         password.to_sql(out)
+///     }
+///     fn from_sql<String>(bytes){
+///         String::from_sql(bytes).map(|password| As(crate::Password(password)))
+///     }
+/// }
+///
+/// # fn main() {
+/// let id = uuid_wrap::As(uuid::Uuid::from_u128(0xff));
+/// let password = password_wrap::As(Password("hunter2".to_string()));
+/// let query = diesel::select((id.into_sql::<Binary>(), password.into_sql::<Text>()));
+/// let logged = diesel::debug_query::<Sqlite, _>(&query).to_string();
+/// assert!(logged.contains("As(00000000-0000-0000-0000-0000000000ff)"));
+/// assert!(logged.contains("As(<redacted>)"));
+/// assert!(!logged.contains("hunter2"));
+/// # }
+/// ```
+///
+/// The names of the target and sql type are available as `TARGET_TYPE_NAME` and `SQL_TYPE_NAME` constants
+/// and together with the module path as [`WrapperInfo`] in the `INFO` constant:
+/// ```
+/// # use diesel_as_wrap::wrap;
+/// # wrap! {
+/// #     target = uuid::Uuid;
+/// #     sql_type = Binary;
+/// #     pub mod uuid_wrap;
+/// #     fn to_sql<[u8]>(self, out){
+/// #         let bytes: &[u8] = self.0.as_bytes();
+/// #         bytes.to_sql(out)
+/// #     }
+/// #     fn from_sql<Vec<u8>>(bytes){
+/// #         let value = <Vec<u8>>::from_sql(bytes)?;
+/// #         uuid::Uuid::from_slice(&value)
+/// #             .map(As)
+/// #             .map_err(|e| e.into())
+/// #     }
+/// # }
+/// assert_eq!(uuid_wrap::SQL_TYPE_NAME, "Binary");
+/// assert_eq!(uuid_wrap::INFO.target, uuid_wrap::TARGET_TYPE_NAME);
+/// assert!(uuid_wrap::INFO.module.ends_with("::uuid_wrap"));
+/// ```
+///
+/// The created module also contains a `SqlType` alias for the sql type of the wrappers
+/// and `Target` (or `Inner`) aliases for the wrapped type, so generic code and macros don't need to repeat their paths.
+/// `SqlType` can be used to declare sql functions taking or returning wrapped values without repeating the sql type:
+/// ```
+/// # use diesel::prelude::*;
+/// # use diesel_as_wrap::wrap;
+/// # wrap! {
+/// #     target = uuid::Uuid;
+/// #     sql_type = Binary;
+/// #     pub mod uuid_wrap;
+/// #     fn to_sql<[u8]>(self, out){
+/// #         let bytes: &[u8] = self.0.as_bytes();
+/// #         bytes.to_sql(out)
+/// #     }
+/// #     fn from_sql<Vec<u8>>(bytes){
+/// #         let value = <Vec<u8>>::from_sql(bytes)?;
+/// #         uuid::Uuid::from_slice(&value)
+/// #             .map(As)
+/// #             .map_err(|e| e.into())
+/// #     }
+/// # }
+/// diesel::define_sql_function!(fn uuid_generate_v7() -> uuid_wrap::SqlType);
+/// diesel::define_sql_function!(fn uuid_namespace(id: uuid_wrap::SqlType) -> uuid_wrap::SqlType);
+///
+/// fn generate(conn: &mut SqliteConnection) -> QueryResult<uuid::Uuid> {
+///     diesel::select(uuid_generate_v7())
+///         .get_result::<uuid_wrap::As>(conn)
+///         .map(Into::into)
+/// }
+///
+/// fn namespace(conn: &mut SqliteConnection, id: uuid_wrap::Target) -> QueryResult<uuid_wrap::Target> {
+///     diesel::select(uuid_namespace(uuid_wrap::As(id)))
+///         .get_result::<uuid_wrap::As>(conn)
+///         .map(Into::into)
+/// }
+/// # fn main() {}
+/// ```
+///
+/// For fields computed by an sql expression (`#[diesel(select_expression = ...)]`) the module contains the
+/// `expression` and `nullable_expression` functions and the `Expression` type alias.
+/// They return the expression unchanged but fail to compile if its sql type doesn't match the wrapper:
+/// ```
+/// # use diesel::prelude::*;
+/// # use diesel_as_wrap::wrap;
+/// # mod schema {
+/// #     diesel::table! {
+/// #         users (id) {
+/// #             id -> Integer,
+/// #             email -> Text,
+/// #         }
+/// #     }
+/// # }
+/// use diesel::sql_types::Text;
+/// use schema::users;
+///
+/// #[derive(Debug)]
+/// pub struct NormalizedEmail(String);
+///
+/// wrap! {
+///     target = crate::NormalizedEmail;
+///     sql_type = Text;
+///     pub mod email_wrap;
+///     fn to_sql<str>(self, out) {
+///         let email: &str = &self.0.0;
+///         email.to_sql(out)
+///     }
+///     fn from_sql<String>(bytes) {
+///         let email = String::from_sql(bytes)?;
+///         if email.chars().any(char::is_uppercase) {
+///             return Err(format!("{email} is not normalized").into());
+///         }
+///         Ok(As(crate::NormalizedEmail(email)))
+///     }
+/// }
+///
+/// diesel::define_sql_function!(fn lower(x: Text) -> Text);
+///
+/// #[derive(Queryable, Selectable)]
+/// #[diesel(table_name = users)]
+/// pub struct UserView {
+///     id: i32,
+///     #[diesel(select_expression = email_wrap::expression(lower(users::email)))]
+///     #[diesel(select_expression_type = email_wrap::Expression<lower<users::email>>)]
+///     #[diesel(deserialize_as = email_wrap::As)]
+///     email: NormalizedEmail,
+/// }
+///
+/// # fn main() {
+/// let mut conn = SqliteConnection::establish(":memory:").unwrap();
+/// diesel::sql_query("CREATE TABLE users (id INTEGER PRIMARY KEY, email TEXT NOT NULL)")
+///     .execute(&mut conn)
+///     .unwrap();
+/// diesel::insert_into(users::table)
+///     .values((users::id.eq(1), users::email.eq("Foo@Example.com")))
+///     .execute(&mut conn)
+///     .unwrap();
+/// let user = users::table
+///     .select(UserView::as_select())
+///     .first(&mut conn)
+///     .unwrap();
+/// assert_eq!(user.email.0, "foo@example.com");
+/// # }
+/// ```
+///
+/// For fields that should tolerate bad data the module contains `AsLenient`, which reads values that can't be deserialized
+/// (and `NULL`) as `None` instead of failing the whole query, for fields of type `Option<target>`.
+/// `AsStrict` is an alias of As naming its fail fast policy, so two fields of the same target can pick their policy by attribute:
+/// ```
+/// # use diesel::prelude::*;
+/// # use diesel::sql_types::{Nullable, SmallInt};
+/// # use diesel_as_wrap::wrap;
+/// wrap! {
+///     target = u8;
+///     sql_type = SmallInt;
+///     pub mod u8_wrap;
+///     fn into_sql<i16>(self) {
+///         i16::from(self.0)
+///     }
+///     fn from_sql<i16>(bytes) {
+///         let value = i16::from_sql(bytes)?;
+///         u8::try_from(value).map(As).map_err(Into::into)
+///     }
+/// }
+///
+/// # fn main() {
+/// let mut conn = SqliteConnection::establish(":memory:").unwrap();
+/// let lenient: u8_wrap::AsLenient = diesel::select(300i16.into_sql::<SmallInt>()).get_result(&mut conn).unwrap();
+/// assert_eq!(lenient.0, None);
+/// let null: u8_wrap::AsLenient = diesel::select(None::<i16>.into_sql::<Nullable<SmallInt>>()).get_result(&mut conn).unwrap();
+/// assert_eq!(null.0, None);
+/// let strict = diesel::select(300i16.into_sql::<SmallInt>()).get_result::<u8_wrap::AsStrict>(&mut conn);
+/// assert!(strict.is_err());
+/// # }
+/// ```
+///
+/// With the `postgres` feature the module also contains wrappers for every array shape of the target:
+///
+/// | wrapper             | field type                   | sql type                                |
+/// |---------------------|------------------------------|-----------------------------------------|
+/// | `AsVec`             | `Vec<target>`                | `Array<sql_type>`                       |
+/// | `AsOptionVec`       | `Option<Vec<target>>`        | `Nullable<Array<sql_type>>`             |
+/// | `AsVecOption`       | `Vec<Option<target>>`        | `Array<Nullable<sql_type>>`             |
+/// | `AsOptionVecOption` | `Option<Vec<Option<target>>>`| `Nullable<Array<Nullable<sql_type>>>`   |
+#[cfg_attr(feature = "postgres", doc = "```")]
+#[cfg_attr(not(feature = "postgres"), doc = "```ignore")]
+/// # use diesel::prelude::*;
+/// # use diesel_as_wrap::wrap;
+/// # mod schema {
+/// #     diesel::table! {
+/// #         posts (id) {
+/// #             id -> Integer,
+/// #             tags -> Array<Text>,
+/// #             aliases -> Nullable<Array<Text>>,
+/// #             votes -> Array<Nullable<Text>>,
+/// #             history -> Nullable<Array<Nullable<Text>>>,
+/// #         }
+/// #     }
+/// # }
+/// #[derive(Debug)]
+/// pub struct Tag(String);
+///
+/// wrap! {
+///     target = crate::Tag;
+///     sql_type = Text;
+///     pub mod tag_wrap;
+///     fn to_sql<str>(self, out) {
+///         let tag: &str = &self.0.0;
+///         tag.to_sql(out)
+///     }
+///     fn from_sql<String>(bytes) {
+///         String::from_sql(bytes).map(|tag| As(crate::Tag(tag)))
+///     }
+/// }
+///
+/// #[derive(Debug, Queryable, Selectable, Insertable)]
+/// #[diesel(table_name = schema::posts)]
+/// #[diesel(check_for_backend(diesel::pg::Pg))]
+/// pub struct Post {
+///     id: i32,
+///     #[diesel(deserialize_as = tag_wrap::AsVec)]
+///     #[diesel(serialize_as = tag_wrap::AsVec)]
+///     tags: Vec<Tag>,
+///     #[diesel(deserialize_as = tag_wrap::AsOptionVec)]
+///     #[diesel(serialize_as = tag_wrap::AsOptionVec)]
+///     aliases: Option<Vec<Tag>>,
+///     #[diesel(deserialize_as = tag_wrap::AsVecOption)]
+///     #[diesel(serialize_as = tag_wrap::AsVecOption)]
+///     votes: Vec<Option<Tag>>,
+///     #[diesel(deserialize_as = tag_wrap::AsOptionVecOption)]
+///     #[diesel(serialize_as = tag_wrap::AsOptionVecOption)]
+///     history: Option<Vec<Option<Tag>>>,
+/// }
+/// # fn main() {}
+/// ```
+///
+/// The arrays are written in the binary format of Postgres, `None` elements as `NULL` and `None` arrays as `NULL` values:
+#[cfg_attr(feature = "postgres", doc = "```")]
+#[cfg_attr(not(feature = "postgres"), doc = "```ignore")]
+/// # use diesel::deserialize::FromSql;
+/// # use diesel::pg::{Pg, PgMetadataLookup, PgTypeMetadata, PgValue};
+/// # use diesel::query_builder::bind_collector::RawBytesBindCollector;
+/// # use diesel::query_builder::BindCollector;
+/// # use diesel::serialize::ToSql;
+/// # use diesel::sql_types::{Array, HasSqlType, Nullable, Text};
+/// # use diesel_as_wrap::wrap;
+/// # struct NoTypes;
+/// # impl PgMetadataLookup for NoTypes {
+/// #     fn lookup_type(&mut self, _: &str, _: Option<&str>) -> PgTypeMetadata {
+/// #         unreachable!()
+/// #     }
+/// # }
+/// # fn encode<ST, T: ToSql<ST, Pg>>(value: &T) -> Option<Vec<u8>> where Pg: HasSqlType<ST> {
+/// #     let mut collector = RawBytesBindCollector::<Pg>::new();
+/// #     collector.push_bound_value::<ST, T>(value, &mut NoTypes).unwrap();
+/// #     collector.binds.pop().unwrap()
+/// # }
+/// # fn decode<ST, T: FromSql<ST, Pg>>(bytes: Option<&[u8]>) -> T where Pg: HasSqlType<ST> {
+/// #     let oid = std::num::NonZeroU32::new(<Pg as HasSqlType<ST>>::metadata(&mut NoTypes).oid().unwrap()).unwrap();
+/// #     T::from_nullable_sql(bytes.map(|bytes| PgValue::new(bytes, &oid))).unwrap()
+/// # }
+/// #[derive(Debug, Clone, PartialEq)]
+/// pub struct Tag(String);
+///
+/// wrap! {
+///     target = crate::Tag;
+///     sql_type = Text;
+///     pub mod tag_wrap;
+///     fn to_sql<str>(self, out) {
+///         let tag: &str = &self.0.0;
+///         tag.to_sql(out)
+///     }
+///     fn from_sql<String>(bytes) {
+///         String::from_sql(bytes).map(|tag| As(crate::Tag(tag)))
+///     }
+/// }
+///
+/// # fn main() {
+/// let votes = vec![Some(Tag("up".to_string())), None];
+/// let mut bytes = encode::<Array<Nullable<Text>>, _>(&tag_wrap::AsVecOption::from(votes.clone())).unwrap();
+/// // 1 dimension, no has-null flag (Postgres doesn't need it), text elements, 2 elements from index 1
+/// let header = [0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 25, 0, 0, 0, 2, 0, 0, 0, 1];
+/// assert_eq!(bytes, [&header[..], &[0, 0, 0, 2], b"up", &[255, 255, 255, 255]].concat());
+/// // Postgres sends arrays containing NULL with the has-null flag
+/// bytes[7] = 1;
+/// let read: tag_wrap::AsVecOption = decode::<Array<Nullable<Text>>, _>(Some(&bytes));
+/// assert_eq!(Vec::<Option<Tag>>::from(read), votes);
+///
+/// let history = Some(votes);
+/// let mut bytes = encode::<Nullable<Array<Nullable<Text>>>, _>(&tag_wrap::AsOptionVecOption::from(history.clone())).unwrap();
+/// bytes[7] = 1;
+/// let read: tag_wrap::AsOptionVecOption = decode::<Nullable<Array<Nullable<Text>>>, _>(Some(&bytes));
+/// assert_eq!(Option::<Vec<Option<Tag>>>::from(read), history);
+/// assert_eq!(encode::<Nullable<Array<Nullable<Text>>>, _>(&tag_wrap::AsOptionVecOption(None)), None);
+/// let read: tag_wrap::AsOptionVecOption = decode::<Nullable<Array<Nullable<Text>>>, _>(None);
+/// assert!(read.0.is_none());
+///
+/// let tags = vec![Tag("rust".to_string()), Tag(String::new())];
+/// let bytes = encode::<Array<Text>, _>(&tag_wrap::AsVec::from(tags.clone()));
+/// let read: tag_wrap::AsVec = decode::<Array<Text>, _>(bytes.as_deref());
+/// assert_eq!(Vec::<Tag>::from(read), tags);
+/// let bytes = encode::<Nullable<Array<Text>>, _>(&tag_wrap::AsOptionVec::from(Some(tags.clone())));
+/// let read: tag_wrap::AsOptionVec = decode::<Nullable<Array<Text>>, _>(bytes.as_deref());
+/// assert_eq!(Option::<Vec<Tag>>::from(read), Some(tags));
+/// let read: tag_wrap::AsOptionVec = decode::<Nullable<Array<Text>>, _>(None);
+/// assert!(read.0.is_none());
+///
+/// // an array with a NULL element can't be read into AsVec
+/// let mut bytes = encode::<Array<Nullable<Text>>, _>(&tag_wrap::AsVecOption(vec![None])).unwrap();
+/// bytes[7] = 1;
+/// let oid = std::num::NonZeroU32::new(1009).unwrap();
+/// assert!(<tag_wrap::AsVec as FromSql<Array<Text>, Pg>>::from_sql(PgValue::new(&bytes, &oid)).is_err());
+/// # }
+/// ```
+///
+/// Changing the visibility of, forwarding attributes to, creating aliases for and delegating traits of the generated types:
+/// ```
+/// # use diesel_as_wrap::wrap;
+/// wrap! {
+///     target = u8;
+///     sql_type = SmallInt;
+///     pub mod u8_wrap;
+///     vis(AsOption) = [pub(super)];
+///     attrs(As) = [#[derive(Clone, Copy)], #[doc = "A u8 stored as SmallInt."]];
+///     attrs(AsOption) = [#[derive(Clone, PartialEq)]];
+///     alias(As) = pub U8As;
+///     delegate(As) = [PartialEq, Eq, PartialOrd, Ord, Hash];
+///     fn into_sql<i16>(self) {
+///         i16::from(self.0)
+///     }
+///     fn from_sql<i16>(bytes) {
+///         let value = i16::from_sql(bytes)?;
+///         u8::try_from(value).map(As).map_err(Into::into)
+///     }
+/// }
+///
+/// let value = U8As(7);
+/// assert!(value == value.clone());
+///
+/// let mut values = vec![U8As(3), U8As(1), U8As(2)];
+/// values.sort();
+/// assert_eq!(U8As::into_targets(values), vec![1, 2, 3]);
+/// let set: std::collections::HashSet<U8As> = [U8As(1), U8As(1)].into_iter().collect();
+/// assert_eq!(set.len(), 1);
+/// ```
+///
+/// Deriving the same traits for both types:
+/// ```
+/// # use diesel_as_wrap::wrap;
+/// wrap! {
+///     target = String;
+///     sql_type = Text;
+///     pub mod name_wrap;
+///     derive(Clone, PartialEq, Eq, Hash);
+///     fn to_sql<String>(self, out) {
+///         let value: &String = &self.0;
+///         value.to_sql(out)
+///     }
+///     fn from_sql<String>(bytes) {
+///         String::from_sql(bytes).map(As)
+///     }
+/// }
+///
+/// let names: std::collections::HashSet<name_wrap::As> = ["a", "b", "a"].map(|name| name_wrap::As(name.to_string())).into();
+/// assert_eq!(names.len(), 2);
+/// let missing = name_wrap::AsOption(None);
+/// assert!(missing == missing.clone());
+/// ```
+/// ```compile_fail
+/// # use diesel::prelude::*;
+/// # use diesel::sql_types::Text;
+/// # use diesel_as_wrap::wrap;
+/// # wrap! {
+/// #     target = String;
+/// #     sql_type = Text;
+/// #     pub mod name_wrap;
+/// #     fn to_sql<String>(self, out) {
+/// #         let value: &String = &self.0;
+/// #         value.to_sql(out)
+/// #     }
+/// #     fn from_sql<String>(bytes) {
+/// #         String::from_sql(bytes).map(As)
+/// #     }
+/// # }
+/// // error: AsOption can't be written into a non nullable `Text`
+/// let _ = name_wrap::AsOption(None).into_sql::<Text>();
+/// ```
+///
+/// Documenting the created module and compiling it only with a feature:
+/// ```
+/// # #![deny(missing_docs)]
+/// # //! Doc test.
+/// # use diesel_as_wrap::wrap;
+/// wrap! {
+///     target = String;
+///     sql_type = Text;
+///     /// Wrappers storing email addresses as text.
+///     #[cfg(not(feature = "no_email"))]
+///     #[allow(missing_docs)]
+///     pub mod email_wrap;
+///     types = EmailAs, EmailAsOption;
+///     fn to_sql<String>(self, out) {
+///         let value: &String = &self.0;
+///         value.to_sql(out)
+///     }
+///     fn from_sql<String>(bytes) {
+///         String::from_sql(bytes).map(As)
+///     }
+/// }
+///
+/// # fn main() {
+/// assert_eq!(EmailAs("a@b.c".to_string()).0, "a@b.c");
+/// # }
+/// ```
+///
+/// Naming the wrappers of several targets so they can be used side by side:
+#[cfg_attr(feature = "sqlite", doc = "```")]
+#[cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+/// # use diesel::prelude::*;
+/// # use diesel_as_wrap::wrap;
+/// # mod schema {
+/// #     diesel::table! {
+/// #         parcels (id) {
+/// #             id -> Integer,
+/// #             weight -> SmallInt,
+/// #             fragile -> Nullable<Integer>,
+/// #         }
+/// #     }
+/// # }
+/// wrap! {
+///     target = u8;
+///     sql_type = SmallInt;
+///     mod u8_wrap;
+///     types = U8Wrap, U8WrapOpt;
+///     fn into_sql<i16>(self) {
+///         i16::from(self.0)
+///     }
+///     fn from_sql<i16>(bytes) {
+///         let value = i16::from_sql(bytes)?;
+///         u8::try_from(value).map(As).map_err(Into::into)
+///     }
+/// }
+///
+/// wrap! {
+///     target = u16;
+///     sql_type = Integer;
+///     mod u16_wrap;
+///     types = U16Wrap, U16WrapOpt;
+///     fn into_sql<i32>(self) {
+///         i32::from(self.0)
+///     }
+///     fn from_sql<i32>(bytes) {
+///         let value = i32::from_sql(bytes)?;
+///         u16::try_from(value).map(As).map_err(Into::into)
+///     }
+/// }
+///
+/// #[derive(Debug, Queryable, Selectable, Insertable)]
+/// #[diesel(table_name = schema::parcels)]
+/// pub struct Parcel {
+///     id: i32,
+///     #[diesel(deserialize_as = U8Wrap, serialize_as = U8Wrap)]
+///     weight: u8,
+///     #[diesel(deserialize_as = U16WrapOpt, serialize_as = U16WrapOpt)]
+///     fragile: Option<u16>,
+/// }
+///
+/// # fn main() {
+/// let mut conn = SqliteConnection::establish(":memory:").unwrap();
+/// let weight: U8Wrap = diesel::select(U8Wrap(7).into_sql::<diesel::sql_types::SmallInt>()).get_result(&mut conn).unwrap();
+/// assert_eq!(weight.0, 7);
+/// # }
+/// ```
+///
+/// Using the wrappers in OpenAPI schemas (requires the `utoipa` feature):
+#[cfg_attr(feature = "utoipa", doc = "```")]
+#[cfg_attr(not(feature = "utoipa"), doc = "```ignore")]
+/// # use diesel_as_wrap::wrap;
+/// use utoipa::{PartialSchema, ToSchema};
+///
+/// wrap! {
+///     target = String;
+///     sql_type = Text;
+///     pub mod name_wrap;
+///     delegate(As) = [ToSchema];
+///     delegate(AsOption) = [ToSchema];
+///     fn to_sql<str>(self, out) {
+///         let name: &str = &self.0;
+///         name.to_sql(out)
+///     }
+///     fn from_sql<String>(bytes) {
+///         String::from_sql(bytes).map(As)
+///     }
+/// }
+///
+/// #[derive(ToSchema)]
+/// struct User {
+///     name: name_wrap::As,
+///     nickname: name_wrap::AsOption,
+/// }
+///
+/// assert_eq!(name_wrap::As::name(), String::name());
+/// assert_eq!(
+///     serde_json::to_string(&name_wrap::As::schema()).unwrap(),
+///     serde_json::to_string(&String::schema()).unwrap(),
+/// );
+/// ```
+///
+/// # Example:
+/// ```
+/// # use diesel::prelude::*;
+/// # use diesel_as_wrap::wrap;
+/// # use uuid::Uuid;
+/// # mod schema {
+/// #     diesel::table! {
+/// #         foo (id) {
+/// #             id -> Binary,
+/// #             opt_id -> Nullable<Binary>,
+/// #         }
+/// #     }
+/// # }
+/// wrap! {
+///     target = uuid::Uuid;
+///     sql_type = Binary;
+///     pub mod uuid_wrap;
+///     fn to_sql<[u8]>(self, out){
+///         let bytes: &[u8] = self.0.as_bytes();
+///         bytes.to_sql(out)
+///     }
+///     fn from_sql<&[u8]>(bytes){
+///         uuid::Uuid::from_slice(bytes)
+///             .map(As)
+///             .map_err(|e| e.into())
+///     }
+/// }
+///
+///
+/// #[derive(Debug, Queryable, Selectable, Insertable)]
+/// #[diesel(table_name = schema::foo)]
+/// pub struct Foo {
+///     #[diesel(deserialize_as = uuid_wrap::As)]
+///     #[diesel(serialize_as = uuid_wrap::As)]
+///     id: Uuid,
+///     #[diesel(deserialize_as = uuid_wrap::AsOption)]
+///     #[diesel(serialize_as = uuid_wrap::AsOption)]
+///     opt_id: Option<Uuid>,
+/// }
+/// ```
+///
+#[macro_export]
+macro_rules! wrap {
+    (@parse $target:tt [] $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt sql_type = Nullable< $($rest:tt)*) => {
+        compile_error!("`sql_type` must not be `Nullable<...>`, pass the inner sql type instead (AsOption already maps to `Nullable<sql_type>`)");
+    };
+
+    (@parse $target:tt [] $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt sql_type = sql_types::Nullable< $($rest:tt)*) => {
+        compile_error!("`sql_type` must not be `Nullable<...>`, pass the inner sql type instead (AsOption already maps to `Nullable<sql_type>`)");
+    };
+
+    (@parse $target:tt [] $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt sql_type = diesel::sql_types::Nullable< $($rest:tt)*) => {
+        compile_error!("`sql_type` must not be `Nullable<...>`, pass the inner sql type instead (AsOption already maps to `Nullable<sql_type>`)");
+    };
+
+    (@parse $target:tt [] $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt sql_type = ::diesel::sql_types::Nullable< $($rest:tt)*) => {
+        compile_error!("`sql_type` must not be `Nullable<...>`, pass the inner sql type instead (AsOption already maps to `Nullable<sql_type>`)");
+    };
+
+    (@parse [] $sql_type:tt $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt target = $value:ty; $($rest:tt)*) => {
+        $crate::wrap! { @parse [$value] $sql_type $module $to $from $wrapper $as_vis $option_vis $as_attrs $option_attrs $as_alias $option_alias $as_delegate $option_delegate $debug $backend $derive $generics $($rest)* }
+    };
+
+    (@parse [$($done:tt)+] $sql_type:tt $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt target = $($rest:tt)*) => {
+        compile_error!("duplicate `target` section");
+    };
+
+    (@parse $target:tt [] $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt sql_type = $value:ty; $($rest:tt)*) => {
+        $crate::wrap! { @parse $target [$value] $module $to $from $wrapper $as_vis $option_vis $as_attrs $option_attrs $as_alias $option_alias $as_delegate $option_delegate $debug $backend $derive $generics $($rest)* }
+    };
+
+    (@parse $target:tt [$($done:tt)+] $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt sql_type = $($rest:tt)*) => {
+        compile_error!("duplicate `sql_type` section");
+    };
+
+    (@parse $target:tt [] $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt sql_type($($db:ident = $value:ty),+ $(,)?); $($rest:tt)*) => {
+        $crate::wrap! { @parse $target [@backends $(($db $value))+] $module $to $from $wrapper $as_vis $option_vis $as_attrs $option_attrs $as_alias $option_alias $as_delegate $option_delegate $debug $backend $derive $generics $($rest)* }
+    };
+
+    (@parse $target:tt [$($done:tt)+] $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt sql_type($($args:tt)*) $($rest:tt)*) => {
+        compile_error!("duplicate `sql_type` section");
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt $from:tt [] $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt wrapper = $value:ty; $($rest:tt)*) => {
+        $crate::wrap! { @parse $target $sql_type $module $to $from [$value] $as_vis $option_vis $as_attrs $option_attrs $as_alias $option_alias $as_delegate $option_delegate $debug $backend $derive $generics $($rest)* }
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt $from:tt [$($done:tt)+] $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt wrapper = $($rest:tt)*) => {
+        compile_error!("duplicate `wrapper` section");
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt $from:tt $wrapper:tt [] $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt vis(As) = [$visablity:vis]; $($rest:tt)*) => {
+        $crate::wrap! { @parse $target $sql_type $module $to $from $wrapper [[$visablity]] $option_vis $as_attrs $option_attrs $as_alias $option_alias $as_delegate $option_delegate $debug $backend $derive $generics $($rest)* }
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt $from:tt $wrapper:tt [$($done:tt)+] $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt vis(As) = $($rest:tt)*) => {
+        compile_error!("duplicate `vis(As)` section");
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt [] $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt vis(AsOption) = [$visablity:vis]; $($rest:tt)*) => {
+        $crate::wrap! { @parse $target $sql_type $module $to $from $wrapper $as_vis [[$visablity]] $as_attrs $option_attrs $as_alias $option_alias $as_delegate $option_delegate $debug $backend $derive $generics $($rest)* }
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt [$($done:tt)+] $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt vis(AsOption) = $($rest:tt)*) => {
+        compile_error!("duplicate `vis(AsOption)` section");
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt [] $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt attrs(As) = [$(#[$attr:meta]),* $(,)?]; $($rest:tt)*) => {
+        $crate::wrap! { @parse $target $sql_type $module $to $from $wrapper $as_vis $option_vis [$(#[$attr])*] $option_attrs $as_alias $option_alias $as_delegate $option_delegate $debug $backend $derive $generics $($rest)* }
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt [$($done:tt)+] $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt attrs(As) = $($rest:tt)*) => {
+        compile_error!("duplicate `attrs(As)` section");
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt [] $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt attrs(AsOption) = [$(#[$attr:meta]),* $(,)?]; $($rest:tt)*) => {
+        $crate::wrap! { @parse $target $sql_type $module $to $from $wrapper $as_vis $option_vis $as_attrs [$(#[$attr])*] $as_alias $option_alias $as_delegate $option_delegate $debug $backend $derive $generics $($rest)* }
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt [$($done:tt)+] $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt attrs(AsOption) = $($rest:tt)*) => {
+        compile_error!("duplicate `attrs(AsOption)` section");
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt [] $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt alias(As) = $visablity:vis $alias:ident; $($rest:tt)*) => {
+        $crate::wrap! { @parse $target $sql_type $module $to $from $wrapper $as_vis $option_vis $as_attrs $option_attrs [[$visablity] $alias] $option_alias $as_delegate $option_delegate $debug $backend $derive $generics $($rest)* }
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt [$($done:tt)+] $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt alias(As) = $($rest:tt)*) => {
+        compile_error!("duplicate `alias(As)` section");
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt [] $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt alias(AsOption) = $visablity:vis $alias:ident; $($rest:tt)*) => {
+        $crate::wrap! { @parse $target $sql_type $module $to $from $wrapper $as_vis $option_vis $as_attrs $option_attrs $as_alias [[$visablity] $alias] $as_delegate $option_delegate $debug $backend $derive $generics $($rest)* }
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt [$($done:tt)+] $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt alias(AsOption) = $($rest:tt)*) => {
+        compile_error!("duplicate `alias(AsOption)` section");
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt [] $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt delegate(As) = [$($delegate:ident),* $(,)?]; $($rest:tt)*) => {
+        $crate::wrap! { @parse $target $sql_type $module $to $from $wrapper $as_vis $option_vis $as_attrs $option_attrs $as_alias $option_alias [$($delegate)*] $option_delegate $debug $backend $derive $generics $($rest)* }
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt [$($done:tt)+] $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt delegate(As) = $($rest:tt)*) => {
+        compile_error!("duplicate `delegate(As)` section");
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt [] $debug:tt $backend:tt $derive:tt $generics:tt delegate(AsOption) = [$($delegate:ident),* $(,)?]; $($rest:tt)*) => {
+        $crate::wrap! { @parse $target $sql_type $module $to $from $wrapper $as_vis $option_vis $as_attrs $option_attrs $as_alias $option_alias $as_delegate [$($delegate)*] $debug $backend $derive $generics $($rest)* }
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt [$($done:tt)+] $debug:tt $backend:tt $derive:tt $generics:tt delegate(AsOption) = $($rest:tt)*) => {
+        compile_error!("duplicate `delegate(AsOption)` section");
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt [$($check:ident)?] $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt fn to_sql<$intermediate:ty$(where $($lifetimes:lifetime),+)?>($self_:ident, $out:ident)$body:block $($rest:tt)*) => {
+        $crate::wrap! { @parse $target $sql_type $module [$($check)? fn to_sql<$intermediate $(where $($lifetimes),+)?>($self_, $out) $body] $from $wrapper $as_vis $option_vis $as_attrs $option_attrs $as_alias $option_alias $as_delegate $option_delegate $debug $backend $derive $generics $($rest)* }
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt [$($done:tt)+] $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt fn to_sql $($rest:tt)*) => {
+        compile_error!("duplicate `fn to_sql`/`fn into_sql`/`fn buffered_sql`/`to` section");
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt [$($check:ident)?] $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt fn into_sql<$intermediate:ty>($self_:ident)$body:block $($rest:tt)*) => {
+        $crate::wrap! { @parse $target $sql_type $module [$($check)? fn into_sql<$intermediate>($self_) $body] $from $wrapper $as_vis $option_vis $as_attrs $option_attrs $as_alias $option_alias $as_delegate $option_delegate $debug $backend $derive $generics $($rest)* }
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt [$($done:tt)+] $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt fn into_sql $($rest:tt)*) => {
+        compile_error!("duplicate `fn to_sql`/`fn into_sql`/`fn buffered_sql`/`to` section");
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt [$($check:ident)?] $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt fn buffered_sql<$intermediate:ty>($self_:ident, $buffer:ident)$body:block $($rest:tt)*) => {
+        $crate::wrap! { @parse $target $sql_type $module [$($check)? fn buffered_sql<$intermediate>($self_, $buffer) $body] $from $wrapper $as_vis $option_vis $as_attrs $option_attrs $as_alias $option_alias $as_delegate $option_delegate $debug $backend $derive $generics $($rest)* }
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt [$($done:tt)+] $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt fn buffered_sql $($rest:tt)*) => {
+        compile_error!("duplicate `fn to_sql`/`fn into_sql`/`fn buffered_sql`/`to` section");
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt [] $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt fn from_sql<& $lifetime:lifetime $borrowed:ty>($bytes:ident)$body:block $($rest:tt)*) => {
+        $crate::wrap! { @parse $target $sql_type $module $to [fn from_sql_borrowed<$borrowed>($bytes) $body] $wrapper $as_vis $option_vis $as_attrs $option_attrs $as_alias $option_alias $as_delegate $option_delegate $debug $backend $derive $generics $($rest)* }
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt [$($done:tt)+] $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt fn from_sql $($rest:tt)*) => {
+        compile_error!("duplicate `fn from_sql`/`from` section");
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt [] $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt fn from_sql<& $borrowed:ty>($bytes:ident)$body:block $($rest:tt)*) => {
+        $crate::wrap! { @parse $target $sql_type $module $to [fn from_sql_borrowed<$borrowed>($bytes) $body] $wrapper $as_vis $option_vis $as_attrs $option_attrs $as_alias $option_alias $as_delegate $option_delegate $debug $backend $derive $generics $($rest)* }
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt [] $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt fn from_sql<$intermediate:ty$(where $($lifetimes:lifetime),+)?>($bytes:ident)$body:block $($rest:tt)*) => {
+        $crate::wrap! { @parse $target $sql_type $module $to [fn from_sql<$intermediate $(where $($lifetimes),+)?>($bytes) $body] $wrapper $as_vis $option_vis $as_attrs $option_attrs $as_alias $option_alias $as_delegate $option_delegate $debug $backend $derive $generics $($rest)* }
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt [$($check:ident)?] $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt to<$intermediate:ty> = |$value:ident, $out_name:ident| $body:expr; $($rest:tt)*) => {
+        $crate::wrap! { @parse $target $sql_type $module [$($check)? fn to_sql<$intermediate>(self, out) { let $value = &self.0; let $out_name = out; $body }] $from $wrapper $as_vis $option_vis $as_attrs $option_attrs $as_alias $option_alias $as_delegate $option_delegate $debug $backend $derive $generics $($rest)* }
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt [$($check:ident)?] $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt to<$intermediate:ty> = |$value:ident| $body:expr; $($rest:tt)*) => {
+        $crate::wrap! { @parse $target $sql_type $module [$($check)? fn into_sql<$intermediate>(self) { let $value = &self.0; $body }] $from $wrapper $as_vis $option_vis $as_attrs $option_attrs $as_alias $option_alias $as_delegate $option_delegate $debug $backend $derive $generics $($rest)* }
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt [$($done:tt)+] $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt to< $($rest:tt)*) => {
+        compile_error!("duplicate `fn to_sql`/`fn into_sql`/`fn buffered_sql`/`to` section");
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt [] $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt from<& $lifetime:lifetime $borrowed:ty> = |$bytes_name:ident| $body:expr; $($rest:tt)*) => {
+        $crate::wrap! { @parse $target $sql_type $module $to [fn from_sql_borrowed<$borrowed>(bytes) { let $bytes_name = bytes; $body }] $wrapper $as_vis $option_vis $as_attrs $option_attrs $as_alias $option_alias $as_delegate $option_delegate $debug $backend $derive $generics $($rest)* }
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt [$($done:tt)+] $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt from< $($rest:tt)*) => {
+        compile_error!("duplicate `fn from_sql`/`from` section");
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt [] $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt from<& $borrowed:ty> = |$bytes_name:ident| $body:expr; $($rest:tt)*) => {
+        $crate::wrap! { @parse $target $sql_type $module $to [fn from_sql_borrowed<$borrowed>(bytes) { let $bytes_name = bytes; $body }] $wrapper $as_vis $option_vis $as_attrs $option_attrs $as_alias $option_alias $as_delegate $option_delegate $debug $backend $derive $generics $($rest)* }
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt [] $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt from<$intermediate:ty> = |$bytes_name:ident| $body:expr; $($rest:tt)*) => {
+        $crate::wrap! { @parse $target $sql_type $module $to [fn from_sql<$intermediate>(bytes) { let $bytes_name = bytes; $body }] $wrapper $as_vis $option_vis $as_attrs $option_attrs $as_alias $option_alias $as_delegate $option_delegate $debug $backend $derive $generics $($rest)* }
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt [] $derive:tt $generics:tt backend = $value:ty; $($rest:tt)*) => {
+        $crate::wrap! { @parse $target $sql_type $module $to $from $wrapper $as_vis $option_vis $as_attrs $option_attrs $as_alias $option_alias $as_delegate $option_delegate $debug [$value] $derive $generics $($rest)* }
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt [$($done:tt)+] $derive:tt $generics:tt backend = $($rest:tt)*) => {
+        compile_error!("duplicate `backend` section");
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt [] $generics:tt derive($($derive_path:path),* $(,)?); $($rest:tt)*) => {
+        $crate::wrap! { @parse $target $sql_type $module $to $from $wrapper $as_vis $option_vis $as_attrs $option_attrs $as_alias $option_alias $as_delegate $option_delegate $debug $backend [#[derive($($derive_path),*)]] $generics $($rest)* }
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt [$($done:tt)+] $generics:tt derive($($args:tt)*) $($rest:tt)*) => {
+        compile_error!("duplicate `derive` section");
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt [] $backend:tt $derive:tt $generics:tt debug = display; $($rest:tt)*) => {
+        $crate::wrap! { @parse $target $sql_type $module $to $from $wrapper $as_vis $option_vis $as_attrs $option_attrs $as_alias $option_alias $as_delegate $option_delegate [display] $backend $derive $generics $($rest)* }
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt [$($done:tt)+] $backend:tt $derive:tt $generics:tt debug = $($rest:tt)*) => {
+        compile_error!("duplicate `debug` section");
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt [] $backend:tt $derive:tt $generics:tt debug = redacted; $($rest:tt)*) => {
+        $crate::wrap! { @parse $target $sql_type $module $to $from $wrapper $as_vis $option_vis $as_attrs $option_attrs $as_alias $option_alias $as_delegate $option_delegate [redacted] $backend $derive $generics $($rest)* }
+    };
+
+    (@parse $target:tt $sql_type:tt [] $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt impl; $($rest:tt)*) => {
+        $crate::wrap! { @parse $target $sql_type [impl] $to $from $wrapper $as_vis $option_vis $as_attrs $option_attrs $as_alias $option_alias $as_delegate $option_delegate $debug $backend $derive $generics $($rest)* }
+    };
+
+    (@parse $target:tt $sql_type:tt [$($done:tt)+] $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt impl $($rest:tt)*) => {
+        compile_error!("duplicate `mod` section");
+    };
+
+    (@parse $target:tt $sql_type:tt [] $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt $(#[$($mod_attr:tt)*])* $visablity:vis mod $name:ident; $($rest:tt)*) => {
+        $crate::wrap! { @parse $target $sql_type [$(#[$($mod_attr)*])* $visablity mod $name] $to $from $wrapper $as_vis $option_vis $as_attrs $option_attrs $as_alias $option_alias $as_delegate $option_delegate $debug $backend $derive $generics $($rest)* }
+    };
+
+    (@parse $target:tt $sql_type:tt [$($done:tt)+] $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt $(#[$($mod_attr:tt)*])* $visablity:vis mod $($rest:tt)*) => {
+        compile_error!("duplicate `mod` section");
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt [] [] $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt types = $as_name:ident, $option_name:ident; $($rest:tt)*) => {
+        $crate::wrap! { @parse $target $sql_type $module $to $from $wrapper $as_vis $option_vis $as_attrs $option_attrs [[pub] $as_name] [[pub] $option_name] $as_delegate $option_delegate $debug $backend $derive $generics $($rest)* }
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt [$($done:tt)+] $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt types = $($rest:tt)*) => {
+        compile_error!("duplicate `types` section or `types` combined with `alias(As)`/`alias(AsOption)`");
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt [$($done:tt)+] $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt types = $($rest:tt)*) => {
+        compile_error!("duplicate `types` section or `types` combined with `alias(As)`/`alias(AsOption)`");
+    };
+
+    (@parse [] $sql_type:tt $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt [] target = $value:ty where $($rest:tt)*) => {
+        $crate::wrap! { @target_where {[$value] $sql_type $module $to $from $wrapper $as_vis $option_vis $as_attrs $option_attrs $as_alias $option_alias $as_delegate $option_delegate $debug $backend $derive} [] [] [] start $($rest)* }
+    };
+
+    (@target_where {$($state:tt)*} [$($param:ident)*] [$($bounds:tt)*] [] start ; $($rest:tt)*) => {
+        $crate::wrap! { @parse $($state)* [[$($param),*] [$($bounds)*]] $($rest)* }
+    };
+
+    (@target_where {$($state:tt)*} [$($param:ident)*] [$($bounds:tt)*] [] in ; $($rest:tt)*) => {
+        $crate::wrap! { @parse $($state)* [[$($param),*] [$($bounds)* ,]] $($rest)* }
+    };
+
+    (@target_where {$($state:tt)*} [$($param:ident)*] [$($bounds:tt)*] [] start $new_param:ident : $($rest:tt)*) => {
+        $crate::wrap! { @target_where {$($state)*} [$($param)* $new_param] [$($bounds)* $new_param :] [] in $($rest)* }
+    };
+
+    (@target_where {$($state:tt)*} [$($param:ident)*] [$($bounds:tt)*] [] start $($rest:tt)*) => {
+        $crate::wrap! { @target_where {$($state)*} [$($param)*] [$($bounds)*] [] in $($rest)* }
+    };
+
+    (@target_where {$($state:tt)*} [$($param:ident)*] [$($bounds:tt)*] [] in , $($rest:tt)*) => {
+        $crate::wrap! { @target_where {$($state)*} [$($param)*] [$($bounds)* ,] [] start $($rest)* }
+    };
+
+    (@target_where {$($state:tt)*} [$($param:ident)*] [$($bounds:tt)*] [$($depth:tt)*] in < $($rest:tt)*) => {
+        $crate::wrap! { @target_where {$($state)*} [$($param)*] [$($bounds)* <] [@ $($depth)*] in $($rest)* }
+    };
+
+    (@target_where {$($state:tt)*} [$($param:ident)*] [$($bounds:tt)*] [@ $($depth:tt)*] in > $($rest:tt)*) => {
+        $crate::wrap! { @target_where {$($state)*} [$($param)*] [$($bounds)* >] [$($depth)*] in $($rest)* }
+    };
+
+    (@target_where {$($state:tt)*} [$($param:ident)*] [$($bounds:tt)*] [@ @ $($depth:tt)*] in >> $($rest:tt)*) => {
+        $crate::wrap! { @target_where {$($state)*} [$($param)*] [$($bounds)* >>] [$($depth)*] in $($rest)* }
+    };
+
+    (@target_where {$($state:tt)*} [$($param:ident)*] [$($bounds:tt)*] [$($depth:tt)*] in $token:tt $($rest:tt)*) => {
+        $crate::wrap! { @target_where {$($state)*} [$($param)*] [$($bounds)* $token] [$($depth)*] in $($rest)* }
+    };
+
+    (@target_where {$($state:tt)*} [$($param:ident)*] [$($bounds:tt)*] [$($depth:tt)*] $mode:ident) => {
+        compile_error!("missing `;` after the where clause of `target`");
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt to<$intermediate:ty> = $($rest:tt)*) => {
+        compile_error!("`to` takes a closure with the value and optionally `out` as parameters: `to<$parsing_type$> = |value, out| ...;` or `to<$parsing_type$> = |value| ...;`");
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt from<$intermediate:ty> = $($rest:tt)*) => {
+        compile_error!("`from` takes a closure with `bytes` as only parameter: `from<$parsing_type$> = |bytes| ...;`");
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt [$($check:ident)?] [] $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt via = $via:ty; $($rest:tt)*) => {
+        $crate::wrap! { @parse $target $sql_type $module [$($check)? via $via] [via $via] $wrapper $as_vis $option_vis $as_attrs $option_attrs $as_alias $option_alias $as_delegate $option_delegate $debug $backend $derive $generics $($rest)* }
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt [$($done:tt)+] $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt via = $($rest:tt)*) => {
+        compile_error!("duplicate `via` section or `via` combined with `fn to_sql`/`fn into_sql`/`fn buffered_sql`/`fn from_sql`");
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt [$($done:tt)+] $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt via = $($rest:tt)*) => {
+        compile_error!("duplicate `via` section or `via` combined with `fn to_sql`/`fn into_sql`/`fn buffered_sql`/`fn from_sql`");
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt [round_trip_check $($done:tt)*] $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt round_trip_check; $($rest:tt)*) => {
+        compile_error!("duplicate `round_trip_check` section");
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt [$($to:tt)*] $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt round_trip_check; $($rest:tt)*) => {
+        $crate::wrap! { @parse $target $sql_type $module [round_trip_check $($to)*] $from $wrapper $as_vis $option_vis $as_attrs $option_attrs $as_alias $option_alias $as_delegate $option_delegate $debug $backend $derive $generics $($rest)* }
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt ; $($rest:tt)*) => {
+        $crate::wrap! { @parse $target $sql_type $module $to $from $wrapper $as_vis $option_vis $as_attrs $option_attrs $as_alias $option_alias $as_delegate $option_delegate $debug $backend $derive $generics $($rest)* }
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt , $($rest:tt)*) => {
+        $crate::wrap! { @parse $target $sql_type $module $to $from $wrapper $as_vis $option_vis $as_attrs $option_attrs $as_alias $option_alias $as_delegate $option_delegate $debug $backend $derive $generics $($rest)* }
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt $unexpected:tt $($rest:tt)*) => {
+        compile_error!(concat!("unknown or malformed section starting with `", stringify!($unexpected), "`"));
+    };
+
+    (@parse [] $sql_type:tt $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt) => {
+        compile_error!("missing `target = ...;` section");
+    };
+
+    (@parse $target:tt [] $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt) => {
+        compile_error!("missing `sql_type = ...;` section");
+    };
+
+    (@parse $target:tt $sql_type:tt [] $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt) => {
+        compile_error!("missing `mod ...;` or `impl;` section");
+    };
+
+    (@parse [$target:ty] [@backends $(($db:ident $backend_sql_type:ty))+] [$(#[$($mod_attr:tt)*])* $visablity:vis mod $name:ident] [$($to:tt)+] [$($from:tt)+] [] [$($as_vis:tt)*] [$($option_vis:tt)*] [$($as_attrs:tt)*] [$($option_attrs:tt)*] [$([$as_alias_vis:vis] $as_alias:ident)?] [$([$option_alias_vis:vis] $option_alias:ident)?] [$($as_delegate:tt)*] [$($option_delegate:tt)*] [$($debug:ident)?] [] [$($derives:tt)*] []) => {
+        $crate::wrap! {
+            @module_backends [$(#[$($mod_attr)*])*] $visablity mod $name; $target; [$(($db $backend_sql_type))+] {$($as_vis)*} {$($option_vis)*} [$($derives)* $($as_attrs)*] [$($derives)* $($option_attrs)*] {$($as_alias_vis use $name::As as $as_alias;)? $($option_alias_vis use $name::AsOption as $option_alias;)?} [$($as_delegate)*] [$($option_delegate)*] [$($debug)?]
+            [$($to)*] [$($from)*]
+        }
+    };
+
+    (@parse $target:tt [@backends $($backends:tt)*] $module:tt $to:tt $from:tt [$($wrapper:tt)+] $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt) => {
+        compile_error!("`sql_type(...)` can't be combined with `wrapper = ...;`");
+    };
+
+    (@parse $target:tt [@backends $($backends:tt)*] $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt [$($backend:tt)+] $derive:tt $generics:tt) => {
+        compile_error!("`sql_type(...)` can't be combined with `backend = ...;`");
+    };
+
+    (@parse [$target:ty] [$sql_type:ty] [$(#[$($mod_attr:tt)*])* $visablity:vis mod $name:ident] [] [] [$wrapper:ty] [$($as_vis:tt)*] [$($option_vis:tt)*] [$($as_attrs:tt)*] [$($option_attrs:tt)*] [$([$as_alias_vis:vis] $as_alias:ident)?] [$([$option_alias_vis:vis] $option_alias:ident)?] [$($as_delegate:tt)*] [$($option_delegate:tt)*] [$($debug:ident)?] [] [$($derives:tt)*] []) => {
+        $crate::wrap! {
+            @module [$(#[$($mod_attr)*])*] $visablity mod $name; $target; $sql_type; {$($as_vis)*} {$($option_vis)*} [$($derives)* $($as_attrs)*] [$($derives)* $($option_attrs)*] {$($as_alias_vis use $name::As as $as_alias;)? $($option_alias_vis use $name::AsOption as $option_alias;)?} [$($as_delegate)*] [$($option_delegate)*] [$($debug)?]
+            {
+                impl<B> ToSql<$sql_type, B> for As
+                where
+                    B: Backend,
+                    $wrapper: ToSql<$sql_type, B>,
+                {
+                    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, B>) -> SResult {
+                        <$wrapper>::from_ref(::std::ops::Deref::deref(&self.0)).to_sql(out)
+                    }
+                }
+
+                $crate::wrap! {
+                    @from_impl {FromSql<$sql_type, B>} from_sql; <B> $sql_type, B;
+                    fn from_sql<$wrapper>(bytes) {
+                        let inner = <$wrapper>::from_sql(bytes)?;
+                        Ok(As(<$target>::from(inner.0)))
+                    }
+                }
+            }
+        }
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt $from:tt [$($wrapper:tt)+] $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt [$($backend:tt)+] $derive:tt $generics:tt) => {
+        compile_error!("`wrapper = ...;` can't be combined with `backend = ...;`");
+    };
+
+    (@parse [$target:ty] [$sql_type:ty] [$(#[$($mod_attr:tt)*])* $visablity:vis mod $name:ident] [$($to:tt)+] [$($from:tt)+] [] [$($as_vis:tt)*] [$($option_vis:tt)*] [$($as_attrs:tt)*] [$($option_attrs:tt)*] [$([$as_alias_vis:vis] $as_alias:ident)?] [$([$option_alias_vis:vis] $option_alias:ident)?] [$($as_delegate:tt)*] [$($option_delegate:tt)*] [$($debug:ident)?] [] [$($derives:tt)*] []) => {
+        $crate::wrap! {
+            @module [$(#[$($mod_attr)*])*] $visablity mod $name; $target; $sql_type; {$($as_vis)*} {$($option_vis)*} [$($derives)* $($as_attrs)*] [$($derives)* $($option_attrs)*] {$($as_alias_vis use $name::As as $as_alias;)? $($option_alias_vis use $name::AsOption as $option_alias;)?} [$($as_delegate)*] [$($option_delegate)*] [$($debug)?]
+            {
+                $crate::wrap! { @to_impl {ToSql<$sql_type, B>} to_sql; <B> $sql_type, B; $($to)* }
+                $crate::wrap! { @from_impl {FromSql<$sql_type, B>} from_sql; <B> $sql_type, B; $($from)* }
+            }
+        }
+    };
+
+    (@parse [$target:ty] [$sql_type:ty] [$(#[$($mod_attr:tt)*])* $visablity:vis mod $name:ident] [$($to:tt)+] [$($from:tt)+] [] [$($as_vis:tt)*] [$($option_vis:tt)*] [$($as_attrs:tt)*] [$($option_attrs:tt)*] [$([$as_alias_vis:vis] $as_alias:ident)?] [$([$option_alias_vis:vis] $option_alias:ident)?] [$($as_delegate:tt)*] [$($option_delegate:tt)*] [$($debug:ident)?] [$backend:ty] [$($derives:tt)*] []) => {
+        $crate::wrap! {
+            @module [$(#[$($mod_attr)*])*] $visablity mod $name; $target; $sql_type; {$($as_vis)*} {$($option_vis)*} [$($derives)* $($as_attrs)*] [$($derives)* $($option_attrs)*] {$($as_alias_vis use $name::As as $as_alias;)? $($option_alias_vis use $name::AsOption as $option_alias;)?} [$($as_delegate)*] [$($option_delegate)*] [$($debug)?]
+            { $crate::wrap! { @concrete_impls $backend; $sql_type; [$($to)*] [$($from)*] } }
+        }
+    };
+
+    (@parse [$target:ty] [$sql_type:ty] [$(#[$($mod_attr:tt)*])* $visablity:vis mod $name:ident] [$($to:tt)+] [$($from:tt)+] [] [$($as_vis:tt)*] [$($option_vis:tt)*] [$($as_attrs:tt)*] [$($option_attrs:tt)*] [$([$as_alias_vis:vis] $as_alias:ident)?] [$([$option_alias_vis:vis] $option_alias:ident)?] [] [] [] [] [$($derives:tt)*] [[$($param:ident),*] [$($bounds:tt)*]]) => {
+        $crate::wrap! {
+            @module_generic [$(#[$($mod_attr)*])*] $visablity mod $name; $target; $sql_type; [$($param),*] [$($bounds)*]; {$($as_vis)*} {$($option_vis)*} [$($derives)* $($as_attrs)*] [$($derives)* $($option_attrs)*] {$($as_alias_vis use $name::As as $as_alias;)? $($option_alias_vis use $name::AsOption as $option_alias;)?}
+            {
+                $crate::wrap! { @to_impl {ToSql<$sql_type, B>} to_sql; <B; $($param),*> $sql_type, B; [$($bounds)* $target: std::fmt::Debug,] $($to)* }
+                $crate::wrap! { @from_impl {FromSql<$sql_type, B>} from_sql; <B; $($param),*> $sql_type, B; [$($bounds)*] $($from)* }
+            }
+        }
+    };
+
+    (@parse $target:tt [@backends $($backends:tt)*] $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt [$($generics:tt)+]) => {
+        compile_error!("generic targets can't be combined with `sql_type(...)`");
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt $from:tt [$($done:tt)+] $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt [$($generics:tt)+]) => {
+        compile_error!("generic targets can't be combined with `wrapper = ...;`");
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt [$($done:tt)+] $derive:tt [$($generics:tt)+]) => {
+        compile_error!("generic targets can't be combined with `backend = ...;`");
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt [$($done:tt)+] $option_delegate:tt $debug:tt $backend:tt $derive:tt [$($generics:tt)+]) => {
+        compile_error!("generic targets can't be combined with `delegate(As)`");
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt [$($done:tt)+] $debug:tt $backend:tt $derive:tt [$($generics:tt)+]) => {
+        compile_error!("generic targets can't be combined with `delegate(AsOption)`");
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt [$($done:tt)+] $backend:tt $derive:tt [$($generics:tt)+]) => {
+        compile_error!("generic targets can't be combined with `debug = ...;`");
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt [$(round_trip_check)?] [] [] $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt) => {
+        compile_error!("missing `fn to_sql`/`fn into_sql`/`fn buffered_sql` and `fn from_sql` sections");
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt [$(round_trip_check)?] $from:tt [] $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt) => {
+        compile_error!("missing `fn to_sql`, `fn into_sql` or `fn buffered_sql` section");
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt [] [] $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt) => {
+        compile_error!("missing `fn from_sql` section");
+    };
+
+    (@parse $target:tt $sql_type:tt [impl] [round_trip_check $($to:tt)*] $($rest:tt)*) => {
+        compile_error!("`round_trip_check` compares the wrapped values, it can't be combined with `impl;`");
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt [round_trip_check] [] [$($wrapper:tt)+] $($rest:tt)*) => {
+        compile_error!("`round_trip_check` can't be combined with `wrapper = ...;`, the wrapper checks its own values");
+    };
+
+    (@parse [$target:ty] [$sql_type:ty] [impl] [via $via:ty] [via $from_via:ty] [] [] [] [] [] [] [] [] [] [] [] [] []) => {
+        $crate::wrap! {
+            @impls $target; $sql_type;
+            $crate::wrap! { @to_impl {ToSql<$sql_type, B>} to_sql; <B> $sql_type, B; via_target $via }
+            $crate::wrap! { @from_impl {FromSql<$sql_type, B>} from_sql; <B> $sql_type, B; via_target $via }
+        }
+    };
+
+    (@parse [$target:ty] [$sql_type:ty] [impl] [via $via:ty] [via $from_via:ty] [] [] [] [] [] [] [] [] [] [] [$backend:ty] [] []) => {
+        $crate::wrap! {
+            @impls $target; $sql_type;
+            $crate::wrap! { @concrete_impls $backend; $sql_type; [via_target $via] [via_target $via] }
+        }
+    };
+
+    (@parse [$target:ty] [$sql_type:ty] [impl] [$($to:tt)+] [$($from:tt)+] [] [] [] [] [] [] [] [] [] [] [] [] []) => {
+        $crate::wrap! {
+            @impls $target; $sql_type;
+            $crate::wrap! { @to_impl {ToSql<$sql_type, B>} to_sql; <B> $sql_type, B; $($to)* }
+            $crate::wrap! { @from_impl {FromSql<$sql_type, B>} from_sql; <B> $sql_type, B; $($from)* }
+        }
+    };
+
+    (@parse [$target:ty] [$sql_type:ty] [impl] [$($to:tt)+] [$($from:tt)+] [] [] [] [] [] [] [] [] [] [] [$backend:ty] [] []) => {
+        $crate::wrap! {
+            @impls $target; $sql_type;
+            $crate::wrap! { @concrete_impls $backend; $sql_type; [$($to)*] [$($from)*] }
+        }
+    };
+
+    (@parse $target:tt $sql_type:tt [impl] $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt) => {
+        compile_error!("`impl;` creates no wrappers, it can only be combined with `target`, `sql_type = ...;`, `backend`, `via` and the `to_sql`/`from_sql` sections");
+    };
+
+    (@parse $target:tt $sql_type:tt $module:tt $to:tt $from:tt $wrapper:tt $as_vis:tt $option_vis:tt $as_attrs:tt $option_attrs:tt $as_alias:tt $option_alias:tt $as_delegate:tt $option_delegate:tt $debug:tt $backend:tt $derive:tt $generics:tt) => {
+        compile_error!("`wrapper = ...;` can't be combined with `fn to_sql`, `fn into_sql`, `fn buffered_sql` or `fn from_sql`");
+    };
+
+    (@to_impl {$($trait:tt)*} $method:ident; <$generic:ident $(; $($param:ident),+)?> $sql_type:ty, $backend:ty; $([$($bounds:tt)*])? round_trip_check $($to:tt)*) => {
+        trait ToSqlUnchecked<ST, DB: Backend> {
+            fn to_sql_unchecked<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> SResult;
+        }
+
+        $crate::wrap! { @to_impl {ToSqlUnchecked<$sql_type, $backend>} to_sql_unchecked; <$generic $(; $($param),+)?> $sql_type, $backend; $([$($bounds)*])? $($to)* }
+
+        impl<$generic $($(, $param)+)?> $($trait)* for As$(<$($param),+>)?
+        where
+            $backend: $crate::RoundTrip + diesel::sql_types::HasSqlType<$sql_type>,
+            As$(<$($param),+>)?: ToSqlUnchecked<$sql_type, $backend> + FromSql<$sql_type, $backend> + std::fmt::Debug,
+            Target$(<$($param),+>)?: PartialEq,
+            $($($bounds)*)?
+        {
+            fn $method<'b>(&'b self, out: &mut Output<'b, '_, $backend>) -> SResult {
+                let is_null = ToSqlUnchecked::<$sql_type, $backend>::to_sql_unchecked(self, out)?;
+                #[cfg(debug_assertions)]
+                match <$backend as $crate::RoundTrip>::read_back::<$sql_type, Self>(self, ToSqlUnchecked::<$sql_type, $backend>::to_sql_unchecked, out.metadata_lookup()) {
+                    Some(Ok(read)) => debug_assert!(read.0 == self.0, "`from_sql` read back {read:?} for {self:?} written by `to_sql`"),
+                    Some(Err(e)) => panic!("`from_sql` can't read back {self:?} written by `to_sql`: {e}"),
+                    None => {}
+                }
+                Ok(is_null)
+            }
+        }
+    };
+
+    (@to_impl {$($trait:tt)*} $method:ident; <$generic:ident $(; $($param:ident),+)?> $sql_type:ty, $backend:ty; $([$($bounds:tt)*])? fn to_sql<$to_intermediate:ty$(where $($to_lifetimes:lifetime),+)?>($self:ident, $out:ident)$to:block) => {
+        impl<$generic $($(, $param)+)?> $($trait)* for As$(<$($param),+>)?
+        where
+            $backend: Backend,
+            $(for<$($to_lifetimes),+>)? $to_intermediate: ToSql<$sql_type, $backend>,
+            $($($bounds)*)?
+        {
+            fn $method<'b>(&'b $self, $out: &mut Output<'b, '_, $backend>) -> SResult $to
+        }
+    };
+
+    (@to_impl {$($trait:tt)*} $method:ident; <$generic:ident $(; $($param:ident),+)?> $sql_type:ty, $backend:ty; $([$($bounds:tt)*])? fn into_sql<$into_intermediate:ty>($self:ident)$into:block) => {
+        impl<$generic $($(, $param)+)?> $($trait)* for As$(<$($param),+>)?
+        where
+            $backend: Backend,
+            $into_intermediate: $crate::ToSqlOwned<$sql_type, $backend>,
+            $($($bounds)*)?
+        {
+            fn $method<'b>(&'b $self, out: &mut Output<'b, '_, $backend>) -> SResult {
+                let value: $into_intermediate = $into;
+                $crate::ToSqlOwned::<$sql_type, $backend>::to_sql_owned(value, out)
+            }
+        }
+    };
+
+    (@to_impl {$($trait:tt)*} $method:ident; <$generic:ident $(; $($param:ident),+)?> $sql_type:ty, $backend:ty; $([$($bounds:tt)*])? fn buffered_sql<$buffer_type:ty>($self:ident, $buffer:ident)$fill:block) => {
+        impl<$generic $($(, $param)+)?> $($trait)* for As$(<$($param),+>)?
+        where
+            $backend: Backend,
+            $buffer_type: $crate::ToSqlBuffered<$sql_type, $backend>,
+            $($($bounds)*)?
+        {
+            fn $method<'b>(&'b $self, out: &mut Output<'b, '_, $backend>) -> SResult {
+                $crate::ToSqlBuffered::<$sql_type, $backend>::to_sql_buffered(out, |$buffer: &mut $buffer_type| -> Result<(), $crate::FillError> { $fill })
+            }
+        }
+    };
+
+    (@to_impl {$($trait:tt)*} $method:ident; <$generic:ident $(; $($param:ident),+)?> $sql_type:ty, $backend:ty; $([$($bounds:tt)*])? via $via:ty) => {
+        $crate::wrap! { @to_impl {$($trait)*} $method; <$generic $(; $($param),+)?> $sql_type, $backend; $([$($bounds)*])? fn into_sql<$via>(self) { ::std::convert::Into::<$via>::into(::std::clone::Clone::clone(&self.0)) } }
+    };
+
+    (@to_impl {$($trait:tt)*} $method:ident; <$generic:ident $(; $($param:ident),+)?> $sql_type:ty, $backend:ty; $([$($bounds:tt)*])? via_target $via:ty) => {
+        $crate::wrap! { @to_impl {$($trait)*} $method; <$generic $(; $($param),+)?> $sql_type, $backend; $([$($bounds)*])? fn into_sql<$via>(self) { ::std::convert::Into::<$via>::into(::std::clone::Clone::clone(self)) } }
+    };
+
+    (@from_impl {$($trait:tt)*} $method:ident; <$generic:ident $(; $($param:ident),+)?> $sql_type:ty, $backend:ty; $([$($bounds:tt)*])? fn from_sql<$from_intermediate:ty$(where $($from_lifetimes:lifetime),+)?>($bytes:ident)$from:block) => {
+        impl<$generic $($(, $param)+)?> $($trait)* for As$(<$($param),+>)?
+        where
+            $backend: Backend,
+            $(for<$($from_lifetimes),+>)? $from_intermediate: FromSql<$sql_type, $backend>,
+            $($($bounds)*)?
+        {
+            fn $method($bytes: <$backend as Backend>::RawValue<'_>) -> DResult<Self> $from
+        }
+    };
+
+    (@from_impl {$($trait:tt)*} $method:ident; <$generic:ident $(; $($param:ident),+)?> $sql_type:ty, $backend:ty; $([$($bounds:tt)*])? fn from_sql_borrowed<$borrowed:ty>($bytes:ident)$from:block) => {
+        impl<$generic $($(, $param)+)?> $($trait)* for As$(<$($param),+>)?
+        where
+            $backend: Backend,
+            *const $borrowed: FromSql<$sql_type, $backend>,
+            $($($bounds)*)?
+        {
+            fn $method(bytes: <$backend as Backend>::RawValue<'_>) -> DResult<Self> {
+                // the closure can't keep the borrow, it only lives as long as the row
+                let from_borrowed = |$bytes: &$borrowed| -> DResult<Self> { $from };
+                let pointer = <*const $borrowed as FromSql<$sql_type, $backend>>::from_sql(bytes)?;
+                // SAFETY: diesel guarantees the pointer to be valid for the duration of `from_sql`.
+                from_borrowed(unsafe { &*pointer })
+            }
+        }
+    };
+
+    (@from_impl {$($trait:tt)*} $method:ident; <$generic:ident $(; $($param:ident),+)?> $sql_type:ty, $backend:ty; $([$($bounds:tt)*])? via $via:ty) => {
+        $crate::wrap! { @from_impl {$($trait)*} $method; <$generic $(; $($param),+)?> $sql_type, $backend; $([$($bounds)*])? fn from_sql<$via>(bytes) { let value = <$via>::from_sql(bytes)?; ::std::convert::TryFrom::try_from(value).map(As).map_err(Into::into) } }
+    };
+
+    (@from_impl {$($trait:tt)*} $method:ident; <$generic:ident $(; $($param:ident),+)?> $sql_type:ty, $backend:ty; $([$($bounds:tt)*])? via_target $via:ty) => {
+        $crate::wrap! { @from_impl {$($trait)*} $method; <$generic $(; $($param),+)?> $sql_type, $backend; $([$($bounds)*])? fn from_sql<$via>(bytes) { let value = <$via>::from_sql(bytes)?; ::std::convert::TryFrom::try_from(value).map_err(Into::into) } }
+    };
+
+
+    (@delegate PartialEq $wrapper:ident $inner:ty) => {
+        impl PartialEq for $wrapper {
+            fn eq(&self, other: &Self) -> bool {
+                PartialEq::eq(&self.0, &other.0)
+            }
+        }
+    };
+
+    (@delegate Eq $wrapper:ident $inner:ty) => {
+        impl Eq for $wrapper {}
+    };
+
+    (@delegate PartialOrd $wrapper:ident $inner:ty) => {
+        impl PartialOrd for $wrapper {
+            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                PartialOrd::partial_cmp(&self.0, &other.0)
+            }
+        }
+    };
+
+    (@delegate Ord $wrapper:ident $inner:ty) => {
+        impl Ord for $wrapper {
+            fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+                Ord::cmp(&self.0, &other.0)
+            }
+        }
+    };
+
+    (@delegate Hash $wrapper:ident $inner:ty) => {
+        impl std::hash::Hash for $wrapper {
+            fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                std::hash::Hash::hash(&self.0, state)
+            }
+        }
+    };
+
+    (@delegate ToSchema $wrapper:ident $inner:ty) => {
+        impl $crate::__private::utoipa::PartialSchema for $wrapper {
+            fn schema() -> $crate::__private::utoipa::openapi::RefOr<$crate::__private::utoipa::openapi::schema::Schema> {
+                <$inner as $crate::__private::utoipa::PartialSchema>::schema()
+            }
+        }
+
+        impl $crate::__private::utoipa::ToSchema for $wrapper {
+            fn name() -> std::borrow::Cow<'static, str> {
+                <$inner as $crate::__private::utoipa::ToSchema>::name()
+            }
+
+            fn schemas(schemas: &mut Vec<(String, $crate::__private::utoipa::openapi::RefOr<$crate::__private::utoipa::openapi::schema::Schema>)>) {
+                <$inner as $crate::__private::utoipa::ToSchema>::schemas(schemas)
+            }
+        }
+    };
+
+    (@debug []) => {
+        impl std::fmt::Debug for As {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_tuple("As").field(&self.0).finish()
+            }
+        }
+    };
+
+    (@debug [display]) => {
+        impl std::fmt::Debug for As {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_tuple("As").field(&format_args!("{}", self.0)).finish()
+            }
+        }
+    };
+
+    (@debug [redacted]) => {
+        impl std::fmt::Debug for As {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str("As(<redacted>)")
+            }
+        }
+    };
+
+    (@struct {} [$($attrs:tt)*] $($item:tt)*) => {
+        $($attrs)*
+        pub $($item)*
+    };
+
+    (@struct {[$item_visablity:vis]} [$($attrs:tt)*] $($item:tt)*) => {
+        $($attrs)*
+        $item_visablity $($item)*
+    };
+
+    (@module [$($mod_attrs:tt)*] $visablity:vis mod $name:ident; $target:ty; $sql_type:ty; {$([$as_vis:vis])?} {$([$option_vis:vis])?} [$(#[$as_attr:meta])*] [$(#[$option_attr:meta])*] {$($aliases:item)*} [$($as_delegate:ident)*] [$($option_delegate:ident)*] [$($debug:ident)?] {$($impls:tt)*}) => {
+
+        $($mod_attrs)*
+        $visablity mod $name {
+
+            use std::option::Option;
+            use diesel::sql_types::*;
+            use diesel::{
+                backend::Backend, deserialize::{
+                    FromSql, Result as DResult
+                }, serialize::{
+                    ToSql, Result as SResult, Output, IsNull
+                },
+                FromSqlRow, AsExpression
+            };
+
+            ///The sql type the wrappers are represented as.
+            pub type SqlType = $sql_type;
+
+            ///The type wrapped by As.
+            pub type Target = $target;
+
+            ///Same as [`Target`].
+            pub type Inner = $target;
+
+            ///The name of the sql type the wrappers are represented as.
+            pub const SQL_TYPE_NAME: &str = stringify!($sql_type);
+
+            ///The name of the type wrapped by As.
+            pub const TARGET_TYPE_NAME: &str = stringify!($target);
+
+            ///Describes this module.
+            pub const INFO: $crate::WrapperInfo = $crate::WrapperInfo {
+                module: module_path!(),
+                target: TARGET_TYPE_NAME,
+                sql_type: SQL_TYPE_NAME,
+            };
+
+            $crate::__register!(INFO);
+
+            ///Returns the expression unchanged, checking that it can be loaded into As.
+            ///Useful for `#[diesel(select_expression = ...)]` on fields deserialized as As.
+            pub fn expression<E>(expression: E) -> E
+            where
+                E: diesel::expression::Expression<SqlType = $sql_type>,
+            {
+                expression
+            }
+
+            ///Returns the expression unchanged, checking that it can be loaded into AsOption.
+            ///Useful for `#[diesel(select_expression = ...)]` on fields deserialized as AsOption.
+            pub fn nullable_expression<E>(expression: E) -> E
+            where
+                E: diesel::expression::Expression<SqlType = Nullable<$sql_type>>,
+            {
+                expression
+            }
+
+            ///The type of [`expression`] for `#[diesel(select_expression_type = ...)]`.
+            pub type Expression<E> = E;
+
+            $crate::wrap! {
+                @wrappers $target; {$([$as_vis])?} {$([$option_vis])?}
+                [#[derive(FromSqlRow, AsExpression)] #[diesel(sql_type = $sql_type)]]
+                [#[derive(FromSqlRow)]]
+                [#[derive(FromSqlRow, AsExpression)] #[diesel(sql_type = $sql_type)]]
+                [$(#[$as_attr])*] [$(#[$option_attr])*] [$($as_delegate)*] [$($option_delegate)*] [$($debug)?]
+            }
+
+            impl As {
+                ///Renders the serialized value as sql literal the way the database sees it.
+                ///The value is bound into a `SELECT quote(?)` query (see [`QuoteLiteral`]($crate::QuoteLiteral)), so it needs a connection.
+                pub fn as_sql_debug<'a, C>(&'a self, conn: &mut C) -> diesel::QueryResult<String>
+                where
+                    C: diesel::connection::LoadConnection,
+                    C::Backend: $crate::QuoteLiteral,
+                    &'a As: diesel::expression::AsExpression<$sql_type>,
+                    diesel::expression::SqlLiteral<
+                        Text,
+                        diesel::expression::UncheckedBind<
+                            diesel::expression::SqlLiteral<Text>,
+                            <&'a As as diesel::expression::AsExpression<$sql_type>>::Expression,
+                        >,
+                    >: diesel::query_dsl::LoadQuery<'a, C, String>,
+                {
+                    let function = <C::Backend as $crate::QuoteLiteral>::QUOTE_FUNCTION;
+                    let query = diesel::dsl::sql::<Text>(&format!("SELECT {function}("))
+                        .bind::<$sql_type, _>(self)
+                        .sql(")");
+                    diesel::RunQueryDsl::get_result(query, conn)
+                }
+            }
+
+            $($impls)*
+
+            impl<B> FromSql<Nullable<$sql_type>, B> for AsOption
+            where
+                B: Backend,
+                As: FromSql<$sql_type, B>,
+            {
+                fn from_sql(bytes: B::RawValue<'_>) -> DResult<Self> {
+                    Ok(AsOption(Some(<As as FromSql<$sql_type, B>>::from_sql(bytes)?)))
+                }
+
+                fn from_nullable_sql(bytes: Option<B::RawValue<'_>>) -> DResult<Self> {
+                    match bytes {
+                        Some(bytes) => <Self as FromSql<Nullable<$sql_type>, B>>::from_sql(bytes),
+                        None => Ok(AsOption(None)),
+                    }
+                }
+            }
+
+            impl<B> ToSql<Nullable<$sql_type>, B> for AsOption
+            where
+                B: Backend,
+                As: ToSql<$sql_type, B>,
+            {
+                fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, B>) -> SResult {
+                    match &self.0 {
+                        Some(value) => ToSql::<$sql_type, B>::to_sql(value, out),
+                        None => Ok(IsNull::Yes),
+                    }
+                }
+            }
+
+            // AsOption is only an expression of `Nullable<sql_type>`, a `None` can't be written into a non nullable column.
+            impl diesel::expression::AsExpression<Nullable<$sql_type>> for AsOption {
+                type Expression = $crate::BoundValue<Nullable<$sql_type>, Self>;
+
+                fn as_expression(self) -> Self::Expression {
+                    $crate::BoundValue::new(self)
+                }
+            }
+
+            impl<'a> diesel::expression::AsExpression<Nullable<$sql_type>> for &'a AsOption {
+                type Expression = $crate::BoundValue<Nullable<$sql_type>, Self>;
+
+                fn as_expression(self) -> Self::Expression {
+                    $crate::BoundValue::new(self)
+                }
+            }
+
+            impl<'a> diesel::expression::AsExpression<$sql_type> for AsRef<'a> {
+                type Expression = $crate::BoundValue<$sql_type, Self>;
+
+                fn as_expression(self) -> Self::Expression {
+                    $crate::BoundValue::new(self)
+                }
+            }
+
+            impl<'a> diesel::expression::AsExpression<Nullable<$sql_type>> for AsRef<'a> {
+                type Expression = $crate::BoundValue<Nullable<$sql_type>, Self>;
+
+                fn as_expression(self) -> Self::Expression {
+                    $crate::BoundValue::new(self)
+                }
+            }
+
+            impl<B> FromSql<$sql_type, B> for AsLenient
+            where
+                B: Backend,
+                As: FromSql<$sql_type, B>,
+            {
+                fn from_sql(bytes: B::RawValue<'_>) -> DResult<Self> {
+                    Ok(AsLenient(<As as FromSql<$sql_type, B>>::from_sql(bytes).ok().map(|w| w.0)))
+                }
+
+                fn from_nullable_sql(bytes: Option<B::RawValue<'_>>) -> DResult<Self> {
+                    match bytes {
+                        Some(bytes) => <Self as FromSql<$sql_type, B>>::from_sql(bytes),
+                        None => Ok(AsLenient(None)),
+                    }
+                }
+            }
+
+            impl<B> FromSql<Nullable<$sql_type>, B> for AsLenient
+            where
+                B: Backend,
+                As: FromSql<$sql_type, B>,
+            {
+                fn from_sql(bytes: B::RawValue<'_>) -> DResult<Self> {
+                    <Self as FromSql<$sql_type, B>>::from_sql(bytes)
+                }
+
+                fn from_nullable_sql(bytes: Option<B::RawValue<'_>>) -> DResult<Self> {
+                    <Self as FromSql<$sql_type, B>>::from_nullable_sql(bytes)
+                }
+            }
+
+            impl<B> ToSql<$sql_type, B> for AsLenient
+            where
+                B: Backend,
+                As: ToSql<$sql_type, B>,
+            {
+                fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, B>) -> SResult {
+                    match &self.0 {
+                        Some(target) => As::from_ref(target).to_sql(out),
+                        None => Ok(IsNull::Yes),
+                    }
+                }
+            }
+
+            $crate::__arrays! { $target; $sql_type }
+        }
+
+        $crate::wrap! { @aliases [$($mod_attrs)*] [] $($aliases)* }
+    };
+
+    (@module_backends [$($mod_attrs:tt)*] $visablity:vis mod $name:ident; $target:ty; [$(($backend:ident $sql_type:ty))+] {$([$as_vis:vis])?} {$([$option_vis:vis])?} [$(#[$as_attr:meta])*] [$(#[$option_attr:meta])*] {$($aliases:item)*} [$($as_delegate:ident)*] [$($option_delegate:ident)*] [$($debug:ident)?] [$($to:tt)*] [$($from:tt)*]) => {
+
+        $($mod_attrs)*
+        $visablity mod $name {
+
+            use std::option::Option;
+            use diesel::sql_types::*;
+            use diesel::{
+                backend::Backend, deserialize::{
+                    FromSql, Result as DResult
+                }, serialize::{
+                    ToSql, Result as SResult, Output, IsNull
+                },
+                FromSqlRow
+            };
+
+            ///The type wrapped by As.
+            pub type Target = $target;
+
+            ///Same as [`Target`].
+            pub type Inner = $target;
+
+            ///The names of the sql types the wrappers are represented as, for every backend.
+            pub const SQL_TYPE_NAME: &str = stringify!($($backend = $sql_type),+);
+
+            ///The name of the type wrapped by As.
+            pub const TARGET_TYPE_NAME: &str = stringify!($target);
+
+            ///Describes this module.
+            pub const INFO: $crate::WrapperInfo = $crate::WrapperInfo {
+                module: module_path!(),
+                target: TARGET_TYPE_NAME,
+                sql_type: SQL_TYPE_NAME,
+            };
+
+            $crate::__register!(INFO);
+
+            $crate::wrap! {
+                @wrappers $target; {$([$as_vis])?} {$([$option_vis])?}
+                [#[derive(FromSqlRow)]] [#[derive(FromSqlRow)]] [#[derive(FromSqlRow)]]
+                [$(#[$as_attr])*] [$(#[$option_attr])*] [$($as_delegate)*] [$($option_delegate)*] [$($debug)?]
+            }
+
+            $crate::wrap! { @bound_expression As }
+            $crate::wrap! { @bound_expression AsOption }
+            $crate::wrap! { @bound_expression AsLenient }
+
+            impl<'a, ST> diesel::expression::AsExpression<ST> for AsRef<'a>
+            where
+                ST: diesel::sql_types::SqlType + diesel::expression::TypedExpressionType,
+            {
+                type Expression = $crate::BoundValue<ST, Self>;
+
+                fn as_expression(self) -> Self::Expression {
+                    $crate::BoundValue::new(self)
+                }
+            }
+
+            $crate::wrap! { @backends [$($to)*] [$($from)*] $(($backend $sql_type))+ }
+        }
+
+        $crate::wrap! { @aliases [$($mod_attrs)*] [] $($aliases)* }
+    };
+
+    (@module_generic [$($mod_attrs:tt)*] $visablity:vis mod $name:ident; $target:ty; $sql_type:ty; [$($param:ident),*] [$($bounds:tt)*]; {$([$as_vis:vis])?} {$([$option_vis:vis])?} [$(#[$as_attr:meta])*] [$(#[$option_attr:meta])*] {$($aliases:item)*} {$($impls:tt)*}) => {
+
+        $($mod_attrs)*
+        $visablity mod $name {
+
+            use std::option::Option;
+            use diesel::sql_types::*;
+            use diesel::{
+                backend::Backend, deserialize::{
+                    FromSql, Result as DResult
+                }, serialize::{
+                    ToSql, Result as SResult, Output, IsNull
+                },
+                FromSqlRow, AsExpression
+            };
+
+            ///The sql type the wrappers are represented as.
+            pub type SqlType = $sql_type;
+
+            ///The type wrapped by As.
+            pub type Target<$($param),*> = $target;
+
+            ///Same as [`Target`].
+            pub type Inner<$($param),*> = $target;
+
+            ///The name of the sql type the wrappers are represented as.
+            pub const SQL_TYPE_NAME: &str = stringify!($sql_type);
+
+            ///The name of the type wrapped by As.
+            pub const TARGET_TYPE_NAME: &str = stringify!($target);
+
+            ///Describes this module.
+            pub const INFO: $crate::WrapperInfo = $crate::WrapperInfo {
+                module: module_path!(),
+                target: TARGET_TYPE_NAME,
+                sql_type: SQL_TYPE_NAME,
+            };
+
+            $crate::__register!(INFO);
+
+            ///Returns the expression unchanged, checking that it can be loaded into As.
+            ///Useful for `#[diesel(select_expression = ...)]` on fields deserialized as As.
+            pub fn expression<E>(expression: E) -> E
+            where
+                E: diesel::expression::Expression<SqlType = $sql_type>,
+            {
+                expression
+            }
+
+            ///Returns the expression unchanged, checking that it can be loaded into AsOption.
+            ///Useful for `#[diesel(select_expression = ...)]` on fields deserialized as AsOption.
+            pub fn nullable_expression<E>(expression: E) -> E
+            where
+                E: diesel::expression::Expression<SqlType = Nullable<$sql_type>>,
+            {
+                expression
+            }
+
+            ///The type of [`expression`] for `#[diesel(select_expression_type = ...)]`.
+            pub type Expression<E> = E;
+
+            $crate::wrap! {
+                @struct {$([$as_vis])?}
+                [
+                    ///Wrapper that can be used for #[diesel(serialize_as())] and #[diesel(deserialize_as())].
+                    #[derive(FromSqlRow, AsExpression)]
+                    #[diesel(sql_type = $sql_type)]
+                    #[repr(transparent)]
+                    $(#[$as_attr])*
+                ]
+                struct As<$($param),*>(pub $target) where $($bounds)*;
+            }
+
+            impl<$($param),*> As<$($param),*>
+            where
+                $($bounds)*
+            {
+                ///Borrows the target as wrapper without moving or cloning it.
+                pub fn from_ref(target: &$target) -> &Self {
+                    // SAFETY: As is repr(transparent) over the target.
+                    unsafe { &*(target as *const $target as *const Self) }
+                }
+            }
+
+            impl<$($param),*> std::fmt::Debug for As<$($param),*>
+            where
+                $target: std::fmt::Debug,
+                $($bounds)*
+            {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    f.debug_tuple("As").field(&self.0).finish()
+                }
+            }
+
+            impl<$($param),*> From<As<$($param),*>> for $target
+            where
+                $($bounds)*
+            {
+                fn from(s: As<$($param),*>) -> Self {
+                    s.0
+                }
+            }
+
+            impl<$($param),*> From<$target> for As<$($param),*>
+            where
+                $($bounds)*
+            {
+                fn from(s: $target) -> Self {
+                    As(s)
+                }
+            }
+
+            $crate::wrap! {
+                @struct {$([$option_vis])?}
+                [
+                    ///Wrapper that can be used for #[diesel(serialize_as())] and #[diesel(deserialize_as())] for an optional database entry.
+                    #[derive(FromSqlRow)]
+                    $(#[$option_attr])*
+                ]
+                struct AsOption<$($param),*>(pub Option<As<$($param),*>>) where $($bounds)*;
+            }
+
+            impl<$($param),*> std::fmt::Debug for AsOption<$($param),*>
+            where
+                $target: std::fmt::Debug,
+                $($bounds)*
+            {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    f.debug_tuple("AsOption").field(&self.0).finish()
+                }
+            }
+
+            impl<$($param),*> From<AsOption<$($param),*>> for Option<$target>
+            where
+                $($bounds)*
+            {
+                fn from(s: AsOption<$($param),*>) -> Self {
+                    s.0.map(|w| w.0)
+                }
+            }
+
+            impl<$($param),*> From<Option<$target>> for AsOption<$($param),*>
+            where
+                $($bounds)*
+            {
+                fn from(s: Option<$target>) -> Self {
+                    AsOption(s.map(|u| As(u)))
+                }
+            }
+
+            ///Same as As, names the default error policy: values that can't be read fail the whole query.
+            pub type AsStrict<$($param),*> = As<$($param),*>;
+
+            $($impls)*
+
+            impl<B, $($param),*> FromSql<Nullable<$sql_type>, B> for AsOption<$($param),*>
+            where
+                B: Backend,
+                As<$($param),*>: FromSql<$sql_type, B>,
+                $($bounds)*
+            {
+                fn from_sql(bytes: B::RawValue<'_>) -> DResult<Self> {
+                    Ok(AsOption(Some(<As<$($param),*> as FromSql<$sql_type, B>>::from_sql(bytes)?)))
+                }
+
+                fn from_nullable_sql(bytes: Option<B::RawValue<'_>>) -> DResult<Self> {
+                    match bytes {
+                        Some(bytes) => <Self as FromSql<Nullable<$sql_type>, B>>::from_sql(bytes),
+                        None => Ok(AsOption(None)),
+                    }
+                }
+            }
+
+            impl<B, $($param),*> ToSql<Nullable<$sql_type>, B> for AsOption<$($param),*>
+            where
+                B: Backend,
+                As<$($param),*>: ToSql<$sql_type, B>,
+                $target: std::fmt::Debug,
+                $($bounds)*
+            {
+                fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, B>) -> SResult {
+                    match &self.0 {
+                        Some(value) => ToSql::<$sql_type, B>::to_sql(value, out),
+                        None => Ok(IsNull::Yes),
+                    }
+                }
+            }
+
+            impl<$($param),*> diesel::expression::AsExpression<Nullable<$sql_type>> for AsOption<$($param),*>
+            where
+                $($bounds)*
+            {
+                type Expression = $crate::BoundValue<Nullable<$sql_type>, Self>;
+
+                fn as_expression(self) -> Self::Expression {
+                    $crate::BoundValue::new(self)
+                }
+            }
+
+            impl<'a, $($param),*> diesel::expression::AsExpression<Nullable<$sql_type>> for &'a AsOption<$($param),*>
+            where
+                $($bounds)*
+            {
+                type Expression = $crate::BoundValue<Nullable<$sql_type>, Self>;
+
+                fn as_expression(self) -> Self::Expression {
+                    $crate::BoundValue::new(self)
+                }
+            }
+        }
+
+        $crate::wrap! { @aliases [$($mod_attrs)*] [] $($aliases)* }
+    };
+
+    // `impl;` implements the traits on the target itself, the bodies see it under the name `As` as usual.
+    // The impls are wrapped in an anonymous const, so the imports don't leak into and clash in the invoking module.
+    (@impls $target:ty; $sql_type:ty; $($impls:tt)*) => {
+        const _: () = {
+            use diesel::sql_types::*;
+            use diesel::{
+                backend::Backend, deserialize::{
+                    FromSql, Result as DResult
+                }, serialize::{
+                    ToSql, Result as SResult, Output, IsNull
+                }
+            };
+
+            type As = $target;
+
+            $($impls)*
+
+            // the impls `#[derive(FromSqlRow, AsExpression)]` would generate
+            impl<__DB, __ST> diesel::deserialize::Queryable<__ST, __DB> for As
+            where
+                __DB: Backend,
+                __ST: diesel::sql_types::SingleValue,
+                Self: FromSql<__ST, __DB>,
+            {
+                type Row = Self;
+
+                fn build(row: Self) -> DResult<Self> {
+                    Ok(row)
+                }
+            }
+
+            impl diesel::expression::AsExpression<$sql_type> for As {
+                type Expression = $crate::BoundValue<$sql_type, Self>;
+
+                fn as_expression(self) -> Self::Expression {
+                    $crate::BoundValue::new(self)
+                }
+            }
+
+            impl<'a> diesel::expression::AsExpression<$sql_type> for &'a As {
+                type Expression = $crate::BoundValue<$sql_type, Self>;
+
+                fn as_expression(self) -> Self::Expression {
+                    $crate::BoundValue::new(self)
+                }
+            }
+
+            impl diesel::expression::AsExpression<Nullable<$sql_type>> for As {
+                type Expression = $crate::BoundValue<Nullable<$sql_type>, Self>;
+
+                fn as_expression(self) -> Self::Expression {
+                    $crate::BoundValue::new(self)
+                }
+            }
+
+            impl<'a> diesel::expression::AsExpression<Nullable<$sql_type>> for &'a As {
+                type Expression = $crate::BoundValue<Nullable<$sql_type>, Self>;
+
+                fn as_expression(self) -> Self::Expression {
+                    $crate::BoundValue::new(self)
+                }
+            }
+
+            impl<B> ToSql<Nullable<$sql_type>, B> for As
+            where
+                B: Backend,
+                As: ToSql<$sql_type, B>,
+            {
+                fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, B>) -> SResult {
+                    ToSql::<$sql_type, B>::to_sql(self, out)
+                }
+            }
+        };
+    };
+
+    // The aliases are emitted next to the module, so they need its `cfg` attributes but none of the others.
+    (@aliases [#[cfg $($condition:tt)*] $($mod_attrs:tt)*] [$($cfg:tt)*] $($aliases:item)*) => {
+        $crate::wrap! { @aliases [$($mod_attrs)*] [$($cfg)* #[cfg $($condition)*]] $($aliases)* }
+    };
+
+    (@aliases [#[$($attr:tt)*] $($mod_attrs:tt)*] [$($cfg:tt)*] $($aliases:item)*) => {
+        $crate::wrap! { @aliases [$($mod_attrs)*] [$($cfg)*] $($aliases)* }
+    };
+
+    (@aliases [] [$($cfg:tt)*]) => {};
+
+    (@aliases [] [$($cfg:tt)*] $alias:item $($aliases:item)*) => {
+        $($cfg)*
+        $alias
+        $crate::wrap! { @aliases [] [$($cfg)*] $($aliases)* }
+    };
+
+    (@wrappers $target:ty; {$($as_vis:tt)*} {$($option_vis:tt)*} [$($as_sql:tt)*] [$($option_sql:tt)*] [$($lenient_sql:tt)*] [$($as_attrs:tt)*] [$($option_attrs:tt)*] [$($as_delegate:ident)*] [$($option_delegate:ident)*] [$($debug:ident)?]) => {
+        $crate::wrap! {
+            @struct {$($as_vis)*}
+            [
+                ///Wrapper that can be used for #[diesel(serialize_as())] and #[diesel(deserialize_as())].
+                $($as_sql)*
+                #[repr(transparent)]
+                $($as_attrs)*
+            ]
+            struct As(pub $target);
+        }
+
+        impl As {
+            ///Borrows the target as wrapper without moving or cloning it.
+            pub fn from_ref(target: &$target) -> &Self {
+                // SAFETY: As is repr(transparent) over the target.
+                unsafe { &*(target as *const $target as *const Self) }
+            }
+
+            ///Borrows a slice of targets as slice of wrappers without copying it.
+            pub fn from_target_slice(targets: &[$target]) -> &[Self] {
+                // SAFETY: As is repr(transparent) over the target, so the slices have the same layout.
+                unsafe { std::slice::from_raw_parts(targets.as_ptr() as *const Self, targets.len()) }
+            }
+
+            ///Borrows a slice of wrappers as slice of targets without copying it.
+            pub fn as_target_slice(wrappers: &[Self]) -> &[$target] {
+                // SAFETY: As is repr(transparent) over the target, so the slices have the same layout.
+                unsafe { std::slice::from_raw_parts(wrappers.as_ptr() as *const $target, wrappers.len()) }
+            }
+
+            ///Converts a Vec of targets into a Vec of wrappers reusing the allocation.
+            pub fn from_targets(targets: Vec<$target>) -> Vec<Self> {
+                let mut targets = std::mem::ManuallyDrop::new(targets);
+                // SAFETY: As is repr(transparent) over the target, so size and alignment are the same
+                // and the allocation can be taken over.
+                unsafe { Vec::from_raw_parts(targets.as_mut_ptr() as *mut Self, targets.len(), targets.capacity()) }
+            }
+
+            ///Converts a Vec of wrappers into a Vec of targets reusing the allocation.
+            pub fn into_targets(wrappers: Vec<Self>) -> Vec<$target> {
+                let mut wrappers = std::mem::ManuallyDrop::new(wrappers);
+                // SAFETY: As is repr(transparent) over the target, so size and alignment are the same
+                // and the allocation can be taken over.
+                unsafe { Vec::from_raw_parts(wrappers.as_mut_ptr() as *mut $target, wrappers.len(), wrappers.capacity()) }
+            }
+        }
+
+        $crate::wrap! { @debug [$($debug)?] }
+
+        $($crate::wrap! { @delegate $as_delegate As $target })*
+
+        impl From<As> for $target {
+            fn from(s: As) -> Self {
+                s.0
+            }
+        }
+
+        impl From<$target> for As {
+            fn from(s: $target) -> Self {
+                As(s)
+            }
+        }
+
+        $crate::wrap! {
+            @struct {$($option_vis)*}
+            [
+                ///Wrapper that can be used for #[diesel(serialize_as())] and #[diesel(deserialize_as())] for an optional database entry.
+                #[derive(Debug)]
+                $($option_sql)*
+                $($option_attrs)*
+            ]
+            struct AsOption(pub Option<As>);
+        }
+
+        $($crate::wrap! { @delegate $option_delegate AsOption Option<$target> })*
+
+        impl From<AsOption> for Option<$target> {
+            fn from(s: AsOption) -> Self {
+                s.0.map(|w| w.0)
+            }
+        }
+
+        impl From<Option<$target>> for AsOption {
+            fn from(s: Option<$target>) -> Self {
+                AsOption(s.map(|u| As(u)))
+            }
+        }
+
+        ///Same as As, names the default error policy: values that can't be read fail the whole query.
+        pub type AsStrict = As;
+
+        ///Wrapper that reads values that can't be deserialized (and `NULL`) as `None` instead of failing the query.
+        $($lenient_sql)*
+        pub struct AsLenient(pub Option<$target>);
+
+        impl std::fmt::Debug for AsLenient {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_tuple("AsLenient").field(&self.0.as_ref().map(As::from_ref)).finish()
+            }
+        }
+
+        impl From<AsLenient> for Option<$target> {
+            fn from(s: AsLenient) -> Self {
+                s.0
+            }
+        }
+
+        impl From<Option<$target>> for AsLenient {
+            fn from(s: Option<$target>) -> Self {
+                AsLenient(s)
+            }
+        }
+
+        ///Wrapper that can be used for #[diesel(serialize_as())] on fields of type `&target`.
+        ///It writes the borrowed target like As, so inserts don't need to move or clone large values.
+        #[derive(Clone, Copy)]
+        pub struct AsRef<'a>(pub &'a $target);
+
+        impl std::fmt::Debug for AsRef<'_> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_tuple("AsRef").field(As::from_ref(self.0)).finish()
+            }
+        }
+
+        impl<'a> From<&'a $target> for AsRef<'a> {
+            fn from(s: &'a $target) -> Self {
+                AsRef(s)
+            }
+        }
+
+        impl<'a, ST, B> ToSql<ST, B> for AsRef<'a>
+        where
+            B: Backend,
+            As: ToSql<ST, B>,
+        {
+            fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, B>) -> SResult {
+                ToSql::<ST, B>::to_sql(As::from_ref(self.0), out)
+            }
+        }
+    };
+
+    (@bound_expression $wrapper:ident) => {
+        impl<ST> diesel::expression::AsExpression<ST> for $wrapper
+        where
+            ST: diesel::sql_types::SqlType + diesel::expression::TypedExpressionType,
+        {
+            type Expression = $crate::BoundValue<ST, Self>;
+
+            fn as_expression(self) -> Self::Expression {
+                $crate::BoundValue::new(self)
+            }
+        }
+
+        impl<'a, ST> diesel::expression::AsExpression<ST> for &'a $wrapper
+        where
+            ST: diesel::sql_types::SqlType + diesel::expression::TypedExpressionType,
+        {
+            type Expression = $crate::BoundValue<ST, Self>;
+
+            fn as_expression(self) -> Self::Expression {
+                $crate::BoundValue::new(self)
+            }
+        }
+    };
+
+    (@backends [$($to:tt)*] [$($from:tt)*]) => {};
+
+    (@backends [$($to:tt)*] [$($from:tt)*] (pg $sql_type:ty) $($rest:tt)*) => {
+        $crate::__if_postgres! { $crate::wrap! { @backend_impls diesel::pg::Pg; $sql_type; [$($to)*] [$($from)*] } }
+        $crate::wrap! { @backends [$($to)*] [$($from)*] $($rest)* }
+    };
+
+    (@backends [$($to:tt)*] [$($from:tt)*] (mysql $sql_type:ty) $($rest:tt)*) => {
+        $crate::__if_mysql! { $crate::wrap! { @backend_impls diesel::mysql::Mysql; $sql_type; [$($to)*] [$($from)*] } }
+        $crate::wrap! { @backends [$($to)*] [$($from)*] $($rest)* }
+    };
+
+    (@backends [$($to:tt)*] [$($from:tt)*] (sqlite $sql_type:ty) $($rest:tt)*) => {
+        $crate::__if_sqlite! { $crate::wrap! { @backend_impls diesel::sqlite::Sqlite; $sql_type; [$($to)*] [$($from)*] } }
+        $crate::wrap! { @backends [$($to)*] [$($from)*] $($rest)* }
+    };
+
+    (@backends [$($to:tt)*] [$($from:tt)*] ($backend:ident $sql_type:ty) $($rest:tt)*) => {
+        compile_error!(concat!("unknown backend `", stringify!($backend), "` in `sql_type(...)`, expected `pg`, `mysql` or `sqlite`"));
+    };
+
+    (@concrete_impls $backend:ty; $sql_type:ty; [$($to:tt)*] [$($from:tt)*]) => {
+        // The bodies are compiled generic over the sql type (like over the backend without `backend = ...;`),
+        // so the bounds on the intermediate types guide the type inference inside of them.
+        impl FromSql<$sql_type, $backend> for As {
+            fn from_sql(bytes: <$backend as Backend>::RawValue<'_>) -> DResult<Self> {
+                trait FromSqlBody<ST>: Sized {
+                    fn from_sql_body(bytes: <$backend as Backend>::RawValue<'_>) -> DResult<Self>;
+                }
+
+                $crate::wrap! { @from_impl {FromSqlBody<ST>} from_sql_body; <ST> ST, $backend; $($from)* }
+
+                <As as FromSqlBody<$sql_type>>::from_sql_body(bytes)
+            }
+        }
+
+        impl ToSql<$sql_type, $backend> for As {
+            fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, $backend>) -> SResult {
+                trait ToSqlBody<ST> {
+                    fn to_sql_body<'b>(&'b self, out: &mut Output<'b, '_, $backend>) -> SResult;
+                }
+
+                $crate::wrap! { @to_impl {ToSqlBody<ST>} to_sql_body; <ST> ST, $backend; $($to)* }
+
+                <As as ToSqlBody<$sql_type>>::to_sql_body(self, out)
+            }
+        }
+    };
+
+    (@backend_impls $backend:ty; $sql_type:ty; [$($to:tt)*] [$($from:tt)*]) => {
+        $crate::wrap! { @concrete_impls $backend; $sql_type; [$($to)*] [$($from)*] }
+
+        impl ToSql<Nullable<$sql_type>, $backend> for As {
+            fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, $backend>) -> SResult {
+                ToSql::<$sql_type, $backend>::to_sql(self, out)
+            }
+        }
+
+        impl FromSql<Nullable<$sql_type>, $backend> for AsOption {
+            fn from_sql(bytes: <$backend as Backend>::RawValue<'_>) -> DResult<Self> {
+                Ok(AsOption(Some(<As as FromSql<$sql_type, $backend>>::from_sql(bytes)?)))
+            }
+
+            fn from_nullable_sql(bytes: Option<<$backend as Backend>::RawValue<'_>>) -> DResult<Self> {
+                match bytes {
+                    Some(bytes) => <Self as FromSql<Nullable<$sql_type>, $backend>>::from_sql(bytes),
+                    None => Ok(AsOption(None)),
+                }
+            }
+        }
+
+        impl ToSql<Nullable<$sql_type>, $backend> for AsOption {
+            fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, $backend>) -> SResult {
+                match &self.0 {
+                    Some(value) => ToSql::<$sql_type, $backend>::to_sql(value, out),
+                    None => Ok(IsNull::Yes),
+                }
+            }
+        }
+
+        impl FromSql<$sql_type, $backend> for AsLenient {
+            fn from_sql(bytes: <$backend as Backend>::RawValue<'_>) -> DResult<Self> {
+                Ok(AsLenient(<As as FromSql<$sql_type, $backend>>::from_sql(bytes).ok().map(|w| w.0)))
+            }
+
+            fn from_nullable_sql(bytes: Option<<$backend as Backend>::RawValue<'_>>) -> DResult<Self> {
+                match bytes {
+                    Some(bytes) => <Self as FromSql<$sql_type, $backend>>::from_sql(bytes),
+                    None => Ok(AsLenient(None)),
+                }
+            }
+        }
+
+        impl FromSql<Nullable<$sql_type>, $backend> for AsLenient {
+            fn from_sql(bytes: <$backend as Backend>::RawValue<'_>) -> DResult<Self> {
+                <Self as FromSql<$sql_type, $backend>>::from_sql(bytes)
+            }
+
+            fn from_nullable_sql(bytes: Option<<$backend as Backend>::RawValue<'_>>) -> DResult<Self> {
+                <Self as FromSql<$sql_type, $backend>>::from_nullable_sql(bytes)
+            }
+        }
+
+        impl ToSql<$sql_type, $backend> for AsLenient {
+            fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, $backend>) -> SResult {
+                match &self.0 {
+                    Some(target) => ToSql::<$sql_type, $backend>::to_sql(As::from_ref(target), out),
+                    None => Ok(IsNull::Yes),
+                }
+            }
+        }
+
+        impl ToSql<Nullable<$sql_type>, $backend> for AsLenient {
+            fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, $backend>) -> SResult {
+                ToSql::<$sql_type, $backend>::to_sql(self, out)
+            }
+        }
+    };
+
+    ($($input:tt)*) => {
+        $crate::wrap! { @parse [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] $($input)* }
+    };
+}
+
+///
+/// This macro creates wrappers for a smart pointer (like `Box`, `Rc` or `Arc`) to a type that already has a wrapper.\
+/// The (de)serialization is delegated to the existing wrapper, so large types can live behind pointers
+/// in Queryable/Insertable structs without writing the conversion again.\
+/// Like [`wrap!`] it creates a module containing an As and an AsOption type.\
+/// The pointer needs to implement `From<inner type>` and `Deref<Target = inner type>`.
+/// The syntax for using this macro is the following:\
+/// ```text
+/// wrap_pointer! {
+///     target = $pointer_type$;
 ///     sql_type = $type_represented_as_sql$;
+///     wrapper = $existing_wrapper$;
 ///     $module_visability$ mod $name_of_wrappers_module$;
-///     fn to_sql<$parsing_type$[where 'a,'b,...]>(self, out){
+///     [vis(As) = [$visability$];]
+///     [vis(AsOption) = [$visability$];]
+///     [attrs(As) = [#[$attribute$], ...];]
+///     [attrs(AsOption) = [#[$attribute$], ...];]
+///     [alias(As) = $alias_visability$ $alias_name$;]
+///     [alias(AsOption) = $alias_visability$ $alias_name$;]
+///     [delegate(As) = [$trait$, ...];]
+///     [delegate(AsOption) = [$trait$, ...];]
+/// }
+/// ```
+///
+/// - pointer_type is the pointer to wrap, for example `Box<uuid::Uuid>` (Needs to be fully qualified).
+/// - type_represented_as_sql is the sql type of the existing wrapper.
+/// - existing_wrapper is the As type created by [`wrap!`] for the inner type.
+///   It is resolved from inside the created module, so sibling modules need to be prefixed with `super::`.
+/// - vis, attrs, alias and delegate work like in [`wrap!`].
+///
+/// Like in [`wrap!`] the sections can be given in any order.
+///
+/// # Example:
+/// ```
+/// # use diesel::prelude::*;
+/// # use diesel_as_wrap::{wrap, wrap_pointer};
+/// # use std::sync::Arc;
+/// # use uuid::Uuid;
+/// # mod schema {
+/// #     diesel::table! {
+/// #         foo (id) {
+/// #             id -> Binary,
+/// #             parent_id -> Nullable<Binary>,
+/// #         }
+/// #     }
+/// # }
+/// # wrap! {
+/// #     target = uuid::Uuid;
+/// #     sql_type = Binary;
+/// #     pub mod uuid_wrap;
+/// #     fn to_sql<[u8]>(self, out){
+/// #         let bytes: &[u8] = self.0.as_bytes();
+/// #         bytes.to_sql(out)
+/// #     }
+/// #     fn from_sql<Vec<u8>>(bytes){
+/// #         let value = <Vec<u8>>::from_sql(bytes)?;
+/// #         uuid::Uuid::from_slice(&value)
+/// #             .map(As)
+/// #             .map_err(|e| e.into())
+/// #     }
+/// # }
+/// wrap_pointer! {
+///     target = Box<uuid::Uuid>;
+///     sql_type = Binary;
+///     wrapper = super::uuid_wrap::As;
+///     pub mod uuid_box;
+/// }
+///
+/// wrap_pointer! {
+///     target = std::sync::Arc<uuid::Uuid>;
+///     sql_type = Binary;
+///     wrapper = super::uuid_wrap::As;
+///     pub mod uuid_arc;
+/// }
+///
+/// #[derive(Debug, Queryable, Selectable, Insertable)]
+/// #[diesel(table_name = schema::foo)]
+/// pub struct Foo {
+///     #[diesel(deserialize_as = uuid_box::As)]
+///     #[diesel(serialize_as = uuid_box::As)]
+///     id: Box<Uuid>,
+///     #[diesel(deserialize_as = uuid_arc::AsOption)]
+///     #[diesel(serialize_as = uuid_arc::AsOption)]
+///     parent_id: Option<Arc<Uuid>>,
+/// }
+/// # fn main() {}
+/// ```
+///
+#[macro_export]
+macro_rules! wrap_pointer {
+    ($($input:tt)*) => {
+        $crate::wrap! { @parse [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] $($input)* }
+    };
+}
+
+///
+/// This macro creates one module containing the wrappers of several related targets (for example a family of uuid backed ids),
+/// instead of one module per target.\
+/// Every member is a braced list of [`wrap!`] sections (at least `target` and `mod`),
+/// the sections after the member list are shared by all members.
+/// Each member becomes a submodule of the family module,
+/// `types`, `alias(As)` and `alias(AsOption)` place its wrapper types directly inside the family module.\
+/// The members don't have to be related, so it also serves as batch mode for many unrelated newtypes:
+/// every member brings its own `sql_type`, `fn to_sql` and `fn from_sql` and the shared list stays empty.
+/// The syntax for using this macro is the following:\
+/// ```text
+/// wrap_family! {
+///     $module_visability$ mod $name_of_family_module$;
+///     members = [
+///         { target = $type_to_wrap$; $module_visability$ mod $name_of_wrappers_module$; [more sections of wrap!] },
 ///         ...
-///         let foo: &$parsing_type$ = ...;
-///         foo.to_sql(out)
+///     ];
+///     [shared sections of wrap!]
+/// }
+/// ```
+///
+/// Types are resolved from inside the member modules, two levels below the invocation,
+/// so they should be fully qualified (or prefixed with `super::super::`).
+///
+/// # Example:
+#[cfg_attr(feature = "sqlite", doc = "```")]
+#[cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+/// # use diesel::prelude::*;
+/// # use diesel::sql_types::Binary;
+/// # use diesel_as_wrap::{wrap, wrap_family};
+/// # wrap! {
+/// #     target = uuid::Uuid;
+/// #     sql_type = Binary;
+/// #     pub mod uuid_wrap;
+/// #     fn to_sql<[u8]>(self, out){
+/// #         let bytes: &[u8] = self.0.as_bytes();
+/// #         bytes.to_sql(out)
+/// #     }
+/// #     fn from_sql<Vec<u8>>(bytes){
+/// #         let value = <Vec<u8>>::from_sql(bytes)?;
+/// #         uuid::Uuid::from_slice(&value)
+/// #             .map(As)
+/// #             .map_err(|e| e.into())
+/// #     }
+/// # }
+/// #[derive(Debug, PartialEq)]
+/// pub struct UserId(uuid::Uuid);
+///
+/// impl std::ops::Deref for UserId {
+///     type Target = uuid::Uuid;
+///     fn deref(&self) -> &uuid::Uuid {
+///         &self.0
 ///     }
-///     fn from_sql<$parsing_type$[where 'a,'b,...]>(bytes){
-///         let value = <$parsing_type$>::from_sql(bytes)?;
-///         ...
+/// }
+///
+/// impl From<uuid::Uuid> for UserId {
+///     fn from(id: uuid::Uuid) -> Self {
+///         UserId(id)
+///     }
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// pub struct OrderId(uuid::Uuid);
+///
+/// impl std::ops::Deref for OrderId {
+///     type Target = uuid::Uuid;
+///     fn deref(&self) -> &uuid::Uuid {
+///         &self.0
 ///     }
 /// }
+///
+/// impl From<uuid::Uuid> for OrderId {
+///     fn from(id: uuid::Uuid) -> Self {
+///         OrderId(id)
+///     }
+/// }
+///
+/// wrap_family! {
+///     pub mod ids;
+///     members = [
+///         { target = crate::UserId; pub mod user_id; alias(As) = pub UserId; alias(AsOption) = pub OptionUserId; },
+///         { target = crate::OrderId; pub mod order_id; alias(As) = pub OrderId; alias(AsOption) = pub OptionOrderId; },
+///     ];
+///     sql_type = Binary;
+///     wrapper = crate::uuid_wrap::As;
+/// }
+///
+/// # fn main() {
+/// let mut conn = SqliteConnection::establish(":memory:").unwrap();
+/// let id = UserId(uuid::Uuid::from_u128(1));
+/// let loaded: ids::UserId = diesel::select(ids::UserId::from_ref(&id).into_sql::<Binary>()).get_result(&mut conn).unwrap();
+/// assert_eq!(loaded.0, id);
+/// let loaded: ids::OrderId = diesel::select(uuid_wrap::As(uuid::Uuid::from_u128(2)).into_sql::<Binary>()).get_result(&mut conn).unwrap();
+/// assert_eq!(loaded.0, OrderId(uuid::Uuid::from_u128(2)));
+/// # }
 /// ```
-/// 
-/// - type_to_wrap is the type you like to wrap (Needs to be fully qualified).
-/// - type_represented_as_sql is the sql type your type should be represented as.
-/// - name_of_wrappers_module is the module created by this macro containing the wrappers.
-/// - [where 'a,'b,...] is an optional list of lifetime specifiers for higher rank trait bounds for the parsing type.
-/// - parsing_type is the type that can already be parsed by diesel and is closest to your type. 
-/// It is used to deserialise the raw bytes from diesel and is used to return the bytes when serializing.
-/// 
+///
+/// Wrapping unrelated newtypes in one batch:
+#[cfg_attr(feature = "sqlite", doc = "```")]
+#[cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+/// # use diesel::prelude::*;
+/// # use diesel::sql_types::{Integer, Text};
+/// # use diesel_as_wrap::wrap_family;
+/// #[derive(Debug, PartialEq)]
+/// pub struct Sku(String);
+///
+/// #[derive(Debug, PartialEq)]
+/// pub struct Quantity(u16);
+///
+/// wrap_family! {
+///     pub mod domain;
+///     members = [
+///         {
+///             target = crate::Sku;
+///             sql_type = Text;
+///             mod sku;
+///             types = Sku, OptionSku;
+///             fn to_sql<str>(self, out) {
+///                 let sku: &str = &self.0.0;
+///                 sku.to_sql(out)
+///             }
+///             fn from_sql<String>(bytes) {
+///                 String::from_sql(bytes).map(|sku| As(crate::Sku(sku)))
+///             }
+///         },
+///         {
+///             target = crate::Quantity;
+///             sql_type = Integer;
+///             mod quantity;
+///             types = Quantity, OptionQuantity;
+///             fn into_sql<i32>(self) {
+///                 i32::from(self.0.0)
+///             }
+///             fn from_sql<i32>(bytes) {
+///                 let value = i32::from_sql(bytes)?;
+///                 u16::try_from(value).map(|value| As(crate::Quantity(value))).map_err(Into::into)
+///             }
+///         },
+///     ];
+/// }
+///
+/// # fn main() {
+/// let mut conn = SqliteConnection::establish(":memory:").unwrap();
+/// let sku: domain::Sku = diesel::select(domain::Sku(Sku("A-1".to_string())).into_sql::<Text>()).get_result(&mut conn).unwrap();
+/// assert_eq!(sku.0, Sku("A-1".to_string()));
+/// let quantity: domain::Quantity = diesel::select(12.into_sql::<Integer>()).get_result(&mut conn).unwrap();
+/// assert_eq!(quantity.0, Quantity(12));
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! wrap_family {
+    ($visablity:vis mod $name:ident; members = [$($members:tt)*]; $($shared:tt)*) => {
+        $visablity mod $name {
+            $crate::wrap_family! { @members [$($shared)*] $($members)* }
+        }
+    };
+
+    (@members [$($shared:tt)*] $(,)?) => {};
+
+    (@members [$($shared:tt)*] {$($member:tt)*} $($rest:tt)*) => {
+        $crate::wrap! { $($member)* $($shared)* }
+        $crate::wrap_family! { @members [$($shared)*] $($rest)* }
+    };
+
+    (@members [$($shared:tt)*] , $($rest:tt)*) => {
+        $crate::wrap_family! { @members [$($shared)*] $($rest)* }
+    };
+}
+
+///
+/// This macro fails compilation if the wrappers of a module created by [`wrap!`] or [`wrap_pointer!`]
+/// can't be read or written on one of the listed backends.\
+/// The generated impls are generic over the backend, so an intermediate type that is only supported by some backends
+/// otherwise only shows up once a query using the wrapper is compiled for the other ones.
+/// The syntax for using this macro is the following:\
+/// ```text
+/// assert_wrapper_backends!($path_to_module$, [$backend$, ...]);
+/// ```
+///
+/// It checks that As implements `FromSql` and `ToSql` and AsOption implements `FromSql` for the nullable sql type.
+///
+/// # Example:
+/// ```
+/// # use diesel_as_wrap::{assert_wrapper_backends, wrap};
+/// wrap! {
+///     target = std::num::Wrapping<i16>;
+///     sql_type = SmallInt;
+///     pub mod wrapping_wrap;
+///     fn to_sql<i16>(self, out) {
+///         let value: &i16 = &self.0.0;
+///         value.to_sql(out)
+///     }
+///     fn from_sql<i16>(bytes) {
+///         i16::from_sql(bytes).map(|value| As(std::num::Wrapping(value)))
+///     }
+/// }
+///
+/// assert_wrapper_backends!(wrapping_wrap, [diesel::sqlite::Sqlite]);
+/// ```
+/// `Unsigned<Integer>` only exists on MySQL:
+/// ```compile_fail
+/// # use diesel_as_wrap::{assert_wrapper_backends, wrap};
+/// wrap! {
+///     target = std::num::NonZeroU32;
+///     sql_type = Unsigned<Integer>;
+///     pub mod non_zero_wrap;
+///     fn into_sql<u32>(self) {
+///         self.0.get()
+///     }
+///     fn from_sql<u32>(bytes) {
+///         let value = u32::from_sql(bytes)?;
+///         std::num::NonZeroU32::new(value).map(As).ok_or_else(|| "zero".into())
+///     }
+/// }
+///
+/// assert_wrapper_backends!(non_zero_wrap, [diesel::sqlite::Sqlite]);
+/// ```
+///
+#[macro_export]
+macro_rules! assert_wrapper_backends {
+    ($($module:ident)::+, [$($backend:ty),* $(,)?] $(,)?) => {
+        const _: () = {
+            use $($module)::+ as wrapper;
+
+            fn supports<ST, B, T, O>()
+            where
+                B: diesel::backend::Backend,
+                T: diesel::deserialize::FromSql<ST, B> + diesel::serialize::ToSql<ST, B>,
+                O: diesel::deserialize::FromSql<diesel::sql_types::Nullable<ST>, B>,
+            {
+            }
+
+            $(
+                let _ = supports::<wrapper::SqlType, $backend, wrapper::As, wrapper::AsOption>;
+            )*
+        };
+    };
+}
+
+///
+/// This macro writes a value with the As wrapper of a module created by [`wrap!`] or [`wrap_pointer!`],
+/// reads it back on the given connection (`SELECT <value>`) and asserts that the result equals the value.\
+/// It catches asymmetric `to_sql`/`from_sql` pairs during development.
+/// The `round_trip_check;` section of [`wrap!`] checks every written value instead, without a connection but only on postgres and mysql.
+/// Like [`debug_assert!`] it only runs in builds with debug assertions, so release builds don't pay for the query.
+/// The target needs to implement `PartialEq` and `Debug`.
+/// The syntax for using this macro is the following:\
+/// ```text
+/// debug_assert_round_trip!($path_to_module$, $connection$, $value$);
+/// ```
+///
 /// # Example:
 /// ```
+/// # use diesel::prelude::*;
+/// # use diesel_as_wrap::{debug_assert_round_trip, wrap};
+/// wrap! {
+///     target = std::num::Wrapping<i16>;
+///     sql_type = SmallInt;
+///     pub mod wrapping_wrap;
+///     fn to_sql<i16>(self, out) {
+///         let value: &i16 = &self.0.0;
+///         value.to_sql(out)
+///     }
+///     fn from_sql<i16>(bytes) {
+///         i16::from_sql(bytes).map(|value| As(std::num::Wrapping(value)))
+///     }
+/// }
+///
+/// # fn main() {
+/// let mut conn = SqliteConnection::establish(":memory:").unwrap();
+/// debug_assert_round_trip!(wrapping_wrap, &mut conn, std::num::Wrapping(-7));
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! debug_assert_round_trip {
+    ($($module:ident)::+, $conn:expr, $value:expr $(,)?) => {
+        if cfg!(debug_assertions) {
+            use $($module)::+ as wrapper;
+
+            let value: &wrapper::Target = &$value;
+            let query = diesel::select(diesel::IntoSql::into_sql::<wrapper::SqlType>(wrapper::As::from_ref(value)));
+            let loaded: wrapper::As = diesel::RunQueryDsl::get_result(query, $conn)
+                .unwrap_or_else(|e| panic!("round trip of {:?} through {} failed: {}", value, wrapper::INFO.module, e));
+            assert_eq!(&loaded.0, value, "round trip through {} changed the value", wrapper::INFO.module);
+        }
+    };
+}
+
+///
+/// This macro creates an insertable struct for a table together with a builder for it.\
+/// Fields that need a wrapper name it after `as`, the struct then carries the matching `#[diesel(serialize_as = ...)]`
+/// and the builder takes the target type, so insert call sites never mention wrapper types.\
+/// Fields can have a default (`= $expression$`) used when the builder method wasn't called,
+/// `build` returns [`MissingField`] for fields without default that weren't set.
+/// The syntax for using this macro is the following:\
+/// ```text
+/// insert_builder! {
+///     table = $path_to_table$;
+///     [#[$attribute$] ...]
+///     $visability$ struct $name_of_struct$, $name_of_builder$ {
+///         $field$: $type$ [as $wrapper$] [= $default$],
+///         ...
+///     }
+/// }
+/// ```
+///
+/// # Example:
+#[cfg_attr(feature = "sqlite", doc = "```")]
+#[cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+/// # use diesel::prelude::*;
+/// # use diesel_as_wrap::{insert_builder, wrap, MissingField};
+/// # mod schema {
+/// #     diesel::table! {
+/// #         users (id) {
+/// #             id -> Binary,
+/// #             email -> Text,
+/// #             nickname -> Nullable<Binary>,
+/// #         }
+/// #     }
+/// # }
+/// use schema::users;
+///
 /// wrap! {
 ///     target = uuid::Uuid;
 ///     sql_type = Binary;
 ///     pub mod uuid_wrap;
-///     fn to_sql<[for<'a,'b,...>] [u8]>(self, out){
+///     fn to_sql<[u8]>(self, out){
 ///         let bytes: &[u8] = self.0.as_bytes();
 ///         bytes.to_sql(out)
 ///     }
-///     fn from_sql<[for<'a,'b,...>] Vec<u8>>(bytes){
+///     fn from_sql<Vec<u8>>(bytes){
 ///         let value = <Vec<u8>>::from_sql(bytes)?;
 ///         uuid::Uuid::from_slice(&value)
 ///             .map(As)
 ///             .map_err(|e| e.into())
 ///     }
 /// }
-/// 
-/// 
-/// #[derive(Debug, Queryable, Selectable, Insertable)]
-/// #[diesel(table_name = crate::schema::foo)]
-/// pub struct Foo {
-///     #[diesel(deserialize_as = uuid_wrap::As)]
-///     #[diesel(serialize_as = uuid_wrap::As)]
-///     id: Uuid,
-///     #[diesel(deserialize_as = uuid_wrap::AsOption)]
-///     #[diesel(serialize_as = uuid_wrap::AsOption)]
-///     opt_id: Option<Uuid>,
+///
+/// insert_builder! {
+///     table = users;
+///     pub struct NewUser, NewUserBuilder {
+///         id: uuid::Uuid as uuid_wrap::As,
+///         email: String,
+///         nickname: Option<uuid::Uuid> as uuid_wrap::AsOption = None,
+///     }
 /// }
+///
+/// # fn main() {
+/// let mut conn = SqliteConnection::establish(":memory:").unwrap();
+/// # diesel::sql_query("CREATE TABLE users (id BLOB PRIMARY KEY, email TEXT NOT NULL, nickname BLOB)")
+/// #     .execute(&mut conn)
+/// #     .unwrap();
+/// let id = uuid::Uuid::from_u128(7);
+/// let user = NewUserBuilder::new().id(id).email("foo@example.com".to_string()).build().unwrap();
+/// diesel::insert_into(users::table).values(user).execute(&mut conn).unwrap();
+///
+/// let missing = NewUserBuilder::new().id(id).build();
+/// assert_eq!(missing.err(), Some(MissingField("email")));
+/// # }
 /// ```
-/// 
+///
 #[macro_export]
-macro_rules! wrap {
-    (target = $target:ty; sql_type = $sql_type:ty; $visablity:vis mod $name:ident; fn to_sql< $to_intermediate:ty$(where $($to_lifetimes:lifetime),+)?>($self:ident, $out:ident)$to:block fn from_sql<$from_intermediate:ty$(where $($from_lifetimes:lifetime),+)?>($bytes:ident)$from:block) => {
-
-        $visablity mod $name {
-
-            use std::option::Option;
-            use diesel::sql_types::*;
-            use diesel::{
-                backend::Backend, deserialize::{
-                    FromSql, Result as DResult
-                }, serialize::{
-                    ToSql, Result as SResult, Output, IsNull
-                },
-                FromSqlRow, AsExpression
-            };
+macro_rules! insert_builder {
+    (
+        table = $($table:ident)::+;
+        $(#[$attr:meta])*
+        $visablity:vis struct $name:ident, $builder:ident {
+            $($field:ident: $field_type:ty $(as $wrapper:ty)? $(= $default:expr)?),* $(,)?
+        }
+    ) => {
+        $(#[$attr])*
+        #[derive(diesel::Insertable)]
+        #[diesel(table_name = $($table)::+)]
+        $visablity struct $name {
+            $(
+                $(#[diesel(serialize_as = $wrapper)])?
+                $visablity $field: $field_type,
+            )*
+        }
 
-            ///Wrapper that can be used for #[diesel(serialize_as())] and #[diesel(deserialize_as())].
-            #[derive(Debug, FromSqlRow, AsExpression)]
-            #[diesel(sql_type = $sql_type)]
-            pub struct As(pub $target);
+        ///Builder for
+        #[doc = concat!("[`", stringify!($name), "`].")]
+        #[derive(Default)]
+        $visablity struct $builder {
+            $($field: Option<$field_type>,)*
+        }
 
-            impl From<As> for $target {
-                fn from(s: As) -> Self {
-                    s.0
-                }
+        impl $builder {
+            ///Creates a builder with no field set.
+            pub fn new() -> Self {
+                Self::default()
             }
 
-            impl From<$target> for As {
-                fn from(s: $target) -> Self {
-                    As(s)
+            $(
+                ///Sets the field of the same name.
+                pub fn $field(mut self, value: $field_type) -> Self {
+                    self.$field = Some(value);
+                    self
                 }
-            }
-
-            impl<B> FromSql<$sql_type, B> for As
-            where
-                B: Backend,
-                $(for<$($from_lifetimes),+>)? $from_intermediate: FromSql<$sql_type, B>,
-            {
-                fn from_sql($bytes: B::RawValue<'_>) -> DResult<Self> $from
-            }
+            )*
 
-            impl<B> ToSql<$sql_type, B> for As
-            where
-                B: Backend,
-                $(for<$($to_lifetimes),+>)? $to_intermediate: ToSql<$sql_type, B>,
-            {
-                fn to_sql<'b>(&'b $self, $out: &mut Output<'b, '_, B>) -> SResult $to
+            ///Builds the insertable struct, failing on the first field without default that wasn't set.
+            pub fn build(self) -> Result<$name, $crate::MissingField> {
+                Ok($name {
+                    $($field: $crate::insert_builder!(@field self.$field, stringify!($field) $(, $default)?),)*
+                })
             }
+        }
+    };
 
-            ///Wrapper that can be used for #[diesel(serialize_as())] and #[diesel(deserialize_as())] for an optional database entry.
-            #[derive(Debug, FromSqlRow, AsExpression)]
-            #[diesel(sql_type = $sql_type)]
-            pub struct AsOption(pub Option<As>);
+    (@field $value:expr, $field:expr) => {
+        $value.ok_or($crate::MissingField($field))?
+    };
 
-            impl From<AsOption> for Option<$target> {
-                fn from(s: AsOption) -> Self {
-                    s.0.map(|w| w.0)
-                }
-            }
+    (@field $value:expr, $field:expr, $default:expr) => {
+        $value.unwrap_or_else(|| $default)
+    };
+}
 
-            impl From<Option<$target>> for AsOption {
-                fn from(s: Option<$target>) -> Self {
-                    AsOption(s.map(|u| As(u)))
-                }
-            }
+///
+/// This macro creates a struct mirroring a domain struct with wrapper types as fields,
+/// together with `From` conversions in both directions.\
+/// It is an alternative to `serialize_as`/`deserialize_as` for domain structs that live in a crate that can't depend on diesel:
+/// the diesel derives go on the mirror struct and the domain struct stays untouched.\
+/// Every field is converted with `Into`, so wrapper fields use the `From` impls created by [`wrap!`]
+/// and all other fields are moved unchanged. The domain struct needs fields of the same names.
+/// The syntax for using this macro is the following:\
+/// ```text
+/// wrap_row! {
+///     [#[$attribute$] ...]
+///     $visability$ struct $name_of_row$ for $path_to_domain_struct$ {
+///         [#[$attribute$] ...]
+///         $field$: $wrapper_or_type$,
+///         ...
+///     }
+/// }
+/// ```
+///
+/// # Example:
+#[cfg_attr(feature = "sqlite", doc = "```")]
+#[cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+/// # use diesel::prelude::*;
+/// # use diesel_as_wrap::{wrap, wrap_row};
+/// # mod schema {
+/// #     diesel::table! {
+/// #         users (id) {
+/// #             id -> Binary,
+/// #             email -> Text,
+/// #             referrer -> Nullable<Binary>,
+/// #         }
+/// #     }
+/// # }
+/// use schema::users;
+///
+/// mod domain {
+///     pub struct User {
+///         pub id: uuid::Uuid,
+///         pub email: String,
+///         pub referrer: Option<uuid::Uuid>,
+///     }
+/// }
+///
+/// wrap! {
+///     target = uuid::Uuid;
+///     sql_type = Binary;
+///     pub mod uuid_wrap;
+///     fn to_sql<[u8]>(self, out){
+///         let bytes: &[u8] = self.0.as_bytes();
+///         bytes.to_sql(out)
+///     }
+///     fn from_sql<Vec<u8>>(bytes){
+///         let value = <Vec<u8>>::from_sql(bytes)?;
+///         uuid::Uuid::from_slice(&value)
+///             .map(As)
+///             .map_err(|e| e.into())
+///     }
+/// }
+///
+/// wrap_row! {
+///     #[derive(Queryable, Selectable, Insertable)]
+///     #[diesel(table_name = users)]
+///     pub struct UserRow for domain::User {
+///         id: uuid_wrap::As,
+///         email: String,
+///         referrer: uuid_wrap::AsOption,
+///     }
+/// }
+///
+/// # fn main() {
+/// let mut conn = SqliteConnection::establish(":memory:").unwrap();
+/// # diesel::sql_query("CREATE TABLE users (id BLOB PRIMARY KEY, email TEXT NOT NULL, referrer BLOB)")
+/// #     .execute(&mut conn)
+/// #     .unwrap();
+/// let user = domain::User { id: uuid::Uuid::from_u128(1), email: "foo@example.com".to_string(), referrer: Some(uuid::Uuid::from_u128(2)) };
+/// diesel::insert_into(users::table).values(UserRow::from(user)).execute(&mut conn).unwrap();
+///
+/// let user: domain::User = users::table.select(UserRow::as_select()).first(&mut conn).map(Into::into).unwrap();
+/// assert_eq!(user.id, uuid::Uuid::from_u128(1));
+/// assert_eq!(user.referrer, Some(uuid::Uuid::from_u128(2)));
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! wrap_row {
+    (
+        $(#[$attr:meta])*
+        $visablity:vis struct $name:ident for $domain:path {
+            $($(#[$field_attr:meta])* $field:ident: $field_type:ty),* $(,)?
+        }
+    ) => {
+        $(#[$attr])*
+        $visablity struct $name {
+            $(
+                $(#[$field_attr])*
+                $visablity $field: $field_type,
+            )*
+        }
 
-            impl<B> FromSql<Nullable<$sql_type>, B> for AsOption
-            where
-                B: Backend,
-                As: FromSql<$sql_type, B>,
-            {
-                fn from_sql(bytes: B::RawValue<'_>) -> DResult<Self> {
-                    Ok(AsOption(<Option<As>>::from_sql(bytes)?))
+        impl From<$name> for $domain {
+            fn from(row: $name) -> Self {
+                Self {
+                    $($field: row.$field.into(),)*
                 }
             }
+        }
 
-            impl<B> ToSql<$sql_type, B> for AsOption
-            where
-                B: Backend,
-                As: ToSql<$sql_type, B>,
-            {
-                fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, B>) -> SResult {
-                    if let Some(uuid) = &self.0 {
-                        uuid.to_sql(out)
-                    } else {Ok(IsNull::Yes)}
+        impl From<$domain> for $name {
+            fn from(domain: $domain) -> Self {
+                Self {
+                    $($field: domain.$field.into(),)*
                 }
             }
         }
     };
-
-
 }
-
-
-// wrap! {
-//     target = u32;
-//     sql_type = Int4;
-//     pub mod u32;
-//     fn to_sql<u32 where 'a, 'b>(self, out) {
-//         self.0.to_sql(out)
-//     }
-
-//     fn from_sql<i32>(bytes) {
-//         let integer = i32::from_sql(bytes)?;
-//         u32::try_from(integer)
-//             .map(As)
-//             .map_err(Into::into)
-//     }
-
-// }
\ No newline at end of file
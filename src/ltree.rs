@@ -0,0 +1,248 @@
+//! Wrapper for the Postgres [`ltree`](https://www.postgresql.org/docs/current/ltree.html) extension type.
+//!
+//! [`Ltree`] is the sql type of `ltree` columns, use it inside `table!` (`path -> diesel_as_wrap::ltree::Ltree`).\
+//! [`label_path`] maps an `ltree` column to a [`LabelPath`], a dot separated list of labels that is validated on read and on construction.
+//! Labels consist of 1 to 1000 ascii letters, digits, `_` and `-` (the hyphen is accepted by Postgres 16 and later).
+//!
+//! # Example:
+#![cfg_attr(feature = "postgres", doc = "```")]
+#![cfg_attr(not(feature = "postgres"), doc = "```ignore")]
+//! # use diesel::prelude::*;
+//! # mod schema {
+//! #     diesel::table! {
+//! #         use diesel::sql_types::*;
+//! #         use diesel_as_wrap::ltree::Ltree;
+//! #
+//! #         categories (id) {
+//! #             id -> Integer,
+//! #             path -> Ltree,
+//! #             moved_from -> Nullable<Ltree>,
+//! #         }
+//! #     }
+//! # }
+//! use diesel_as_wrap::ltree::{label_path, LabelPath};
+//!
+//! #[derive(Debug, Queryable, Selectable, Insertable)]
+//! #[diesel(table_name = schema::categories)]
+//! #[diesel(check_for_backend(diesel::pg::Pg))]
+//! pub struct Category {
+//!     id: i32,
+//!     #[diesel(deserialize_as = label_path::As)]
+//!     #[diesel(serialize_as = label_path::As)]
+//!     path: LabelPath,
+//!     #[diesel(deserialize_as = label_path::AsOption)]
+//!     #[diesel(serialize_as = label_path::AsOption)]
+//!     moved_from: Option<LabelPath>,
+//! }
+//!
+//! let path: LabelPath = "Top.Science.Astronomy".parse().unwrap();
+//! assert_eq!(path.labels().collect::<Vec<_>>(), ["Top", "Science", "Astronomy"]);
+//! assert_eq!(path.parent().unwrap().as_str(), "Top.Science");
+//! assert!(path.parent().unwrap().is_ancestor_of(&path));
+//! assert!("Top..Science".parse::<LabelPath>().is_err());
+//! ```
+//!
+//! The binary format is a version byte (`1`) followed by the text of the path:
+#![cfg_attr(feature = "postgres", doc = "```")]
+#![cfg_attr(not(feature = "postgres"), doc = "```ignore")]
+//! # use diesel::deserialize::FromSql;
+//! # use diesel::pg::{Pg, PgMetadataLookup, PgTypeMetadata, PgValue};
+//! # use diesel::query_builder::bind_collector::RawBytesBindCollector;
+//! # use diesel::query_builder::BindCollector;
+//! # use diesel::serialize::ToSql;
+//! # use diesel::sql_types::HasSqlType;
+//! # struct Types;
+//! # impl PgMetadataLookup for Types {
+//! #     fn lookup_type(&mut self, _: &str, _: Option<&str>) -> PgTypeMetadata {
+//! #         PgTypeMetadata::new(100_000, 100_001)
+//! #     }
+//! # }
+//! # fn encode<ST, T: ToSql<ST, Pg>>(value: &T) -> Option<Vec<u8>> where Pg: HasSqlType<ST> {
+//! #     let mut collector = RawBytesBindCollector::<Pg>::new();
+//! #     collector.push_bound_value::<ST, T>(value, &mut Types).unwrap();
+//! #     collector.binds.pop().unwrap()
+//! # }
+//! # fn decode<ST, T: FromSql<ST, Pg>>(bytes: Option<&[u8]>) -> diesel::deserialize::Result<T> where Pg: HasSqlType<ST> {
+//! #     let oid = std::num::NonZeroU32::new(<Pg as HasSqlType<ST>>::metadata(&mut Types).oid().unwrap()).unwrap();
+//! #     T::from_nullable_sql(bytes.map(|bytes| PgValue::new(bytes, &oid)))
+//! # }
+//! use diesel::sql_types::Nullable;
+//! use diesel_as_wrap::ltree::{label_path, LabelPath, Ltree};
+//!
+//! let path: LabelPath = "Top.Science".parse().unwrap();
+//! let bytes = encode::<Ltree, _>(&label_path::As(path.clone())).unwrap();
+//! assert_eq!(bytes, b"\x01Top.Science");
+//! assert_eq!(decode::<Ltree, label_path::As>(Some(&bytes)).unwrap().0, path);
+//!
+//! let bytes = encode::<Ltree, _>(&label_path::As(LabelPath::default())).unwrap();
+//! assert_eq!(bytes, [1]);
+//! assert!(decode::<Ltree, label_path::As>(Some(&bytes)).unwrap().0.is_empty());
+//! assert!(decode::<Nullable<Ltree>, label_path::AsOption>(None).unwrap().0.is_none());
+//!
+//! let error = decode::<Ltree, label_path::As>(Some(b"\x02Top.Science")).unwrap_err();
+//! assert_eq!(error.to_string(), "unsupported ltree binary format");
+//! assert!(decode::<Ltree, label_path::As>(Some(b"")).is_err());
+//! let error = decode::<Ltree, label_path::As>(Some(b"\x01Top..Science")).unwrap_err();
+//! assert!(error.to_string().contains("must be between 1 and 1000 characters long"));
+//! ```
+
+use std::fmt;
+use std::io::Write;
+use std::str::FromStr;
+
+use diesel::deserialize::{self, FromSql};
+use diesel::pg::{Pg, PgValue};
+use diesel::serialize::{self, IsNull, Output, ToSql};
+
+const MAX_LABEL_LENGTH: usize = 1000;
+const MAX_LABELS: usize = 65535;
+const BINARY_VERSION: u8 = 1;
+
+///
+/// The sql type of the Postgres `ltree` extension.
+///
+#[derive(Debug, Clone, Copy, Default, diesel::sql_types::SqlType, diesel::query_builder::QueryId)]
+#[diesel(postgres_type(name = "ltree"))]
+pub struct Ltree;
+
+impl ToSql<Ltree, Pg> for str {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        out.write_all(&[BINARY_VERSION])?;
+        out.write_all(self.as_bytes())?;
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Ltree, Pg> for String {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        match bytes.as_bytes().split_first() {
+            Some((&BINARY_VERSION, text)) => Ok(String::from_utf8(text.to_vec())?),
+            _ => Err("unsupported ltree binary format".into()),
+        }
+    }
+}
+
+///
+/// A validated `ltree` value: labels separated by `.`, the empty path has no labels.
+///
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LabelPath(String);
+
+impl LabelPath {
+    /// The path as written to the database.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The labels of the path from the root to the leaf.
+    pub fn labels(&self) -> impl Iterator<Item = &str> {
+        self.0.split('.').filter(|label| !label.is_empty())
+    }
+
+    /// The number of labels.
+    pub fn len(&self) -> usize {
+        self.labels().count()
+    }
+
+    /// Returns true for the empty path.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The path without its last label, `None` for the empty path.
+    pub fn parent(&self) -> Option<LabelPath> {
+        if self.is_empty() {
+            return None;
+        }
+        let parent = self.0.rsplit_once('.').map_or("", |(parent, _)| parent);
+        Some(LabelPath(parent.to_owned()))
+    }
+
+    /// Appends a label to the path.
+    pub fn child(&self, label: &str) -> Result<LabelPath, InvalidLabelPath> {
+        validate_label(label)?;
+        if self.len() == MAX_LABELS {
+            return Err(InvalidLabelPath(format!("a path can't have more than {MAX_LABELS} labels")));
+        }
+        if self.is_empty() {
+            Ok(LabelPath(label.to_owned()))
+        } else {
+            Ok(LabelPath(format!("{}.{label}", self.0)))
+        }
+    }
+
+    /// Returns true if this path is an ancestor of or equal to the other path (like `@>` in sql).
+    pub fn is_ancestor_of(&self, other: &LabelPath) -> bool {
+        let mut labels = other.labels();
+        self.labels().all(|label| labels.next() == Some(label))
+    }
+}
+
+fn validate_label(label: &str) -> Result<(), InvalidLabelPath> {
+    if label.is_empty() || label.len() > MAX_LABEL_LENGTH {
+        return Err(InvalidLabelPath(format!("label {label:?} must be between 1 and {MAX_LABEL_LENGTH} characters long")));
+    }
+    if let Some(invalid) = label.chars().find(|c| !(c.is_ascii_alphanumeric() || *c == '_' || *c == '-')) {
+        return Err(InvalidLabelPath(format!("label {label:?} contains the invalid character {invalid:?}")));
+    }
+    Ok(())
+}
+
+impl FromStr for LabelPath {
+    type Err = InvalidLabelPath;
+
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        if path.is_empty() {
+            return Ok(LabelPath::default());
+        }
+        let mut labels = 0;
+        for label in path.split('.') {
+            validate_label(label)?;
+            labels += 1;
+        }
+        if labels > MAX_LABELS {
+            return Err(InvalidLabelPath(format!("a path can't have more than {MAX_LABELS} labels")));
+        }
+        Ok(LabelPath(path.to_owned()))
+    }
+}
+
+impl fmt::Display for LabelPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<LabelPath> for String {
+    fn from(path: LabelPath) -> Self {
+        path.0
+    }
+}
+
+///
+/// The error returned for paths that aren't valid `ltree` values.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidLabelPath(String);
+
+impl fmt::Display for InvalidLabelPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for InvalidLabelPath {}
+
+crate::wrap! {
+    target = crate::ltree::LabelPath;
+    sql_type = crate::ltree::Ltree;
+    pub mod label_path;
+    fn to_sql<str>(self, out) {
+        let path: &str = self.0.as_str();
+        path.to_sql(out)
+    }
+    fn from_sql<String>(bytes) {
+        let path = String::from_sql(bytes)?;
+        path.parse().map(As).map_err(Into::into)
+    }
+}
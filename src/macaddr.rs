@@ -0,0 +1,82 @@
+//! Wrappers for [`macaddr::MacAddr6`](::macaddr::MacAddr6).
+//!
+//! - [`binary`] stores a MAC address as its 6 octets inside a `Binary` column.
+//!   Binary values that aren't 6 bytes long produce a deserialization error.
+//! - [`text`] stores it inside a `Text` column in the form Postgres prints `macaddr` values in (`08:00:2b:01:02:03`).
+//!   Reading also accepts upper case digits and the other formats understood by `MacAddr6::from_str`
+//!   (`08-00-2B-01-02-03` and `0800.2b01.0203`), text that isn't a MAC address produces a deserialization error.
+//! - [`native`] maps it to the native Postgres `macaddr` type (requires the `postgres` feature).
+//!
+//! # Example:
+#![cfg_attr(feature = "sqlite", doc = "```")]
+#![cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+//! use diesel::prelude::*;
+//! use diesel::sql_types::{Binary, Text};
+//! use diesel_as_wrap::macaddr::{binary, text};
+//! use macaddr::MacAddr6;
+//!
+//! let mut conn = SqliteConnection::establish(":memory:").unwrap();
+//!
+//! let address = MacAddr6::new(0x08, 0x00, 0x2b, 0x01, 0x02, 0x03);
+//! let stored: String = diesel::select(text::As(address).into_sql::<Text>()).get_result(&mut conn).unwrap();
+//! assert_eq!(stored, "08:00:2b:01:02:03");
+//! let loaded: text::As = diesel::select("08-00-2B-01-02-03".into_sql::<Text>()).get_result(&mut conn).unwrap();
+//! assert_eq!(loaded.0, address);
+//!
+//! let stored: Vec<u8> = diesel::select(binary::As(address).into_sql::<Binary>()).get_result(&mut conn).unwrap();
+//! assert_eq!(stored, [0x08, 0x00, 0x2b, 0x01, 0x02, 0x03]);
+//! let loaded: binary::As = diesel::select(stored.into_sql::<Binary>()).get_result(&mut conn).unwrap();
+//! assert_eq!(loaded.0, address);
+//!
+//! let invalid = diesel::select("08:00:2b".into_sql::<Text>()).get_result::<text::As>(&mut conn);
+//! assert!(invalid.unwrap_err().to_string().contains(r#""08:00:2b" is not a MAC address"#));
+//! ```
+
+crate::wrap! {
+    target = ::macaddr::MacAddr6;
+    sql_type = Binary;
+    pub mod binary;
+    fn to_sql<[u8]>(self, out) {
+        let bytes: &[u8] = self.0.as_bytes();
+        bytes.to_sql(out)
+    }
+    fn from_sql<&[u8]>(bytes) {
+        <[u8; 6]>::try_from(bytes)
+            .map(|octets| As(::macaddr::MacAddr6::from(octets)))
+            .map_err(|_| format!("expected 6 bytes for a MAC address, got {}", bytes.len()).into())
+    }
+}
+
+crate::wrap! {
+    target = ::macaddr::MacAddr6;
+    sql_type = Text;
+    pub mod text;
+    fn buffered_sql<String>(self, buffer) {
+        use std::fmt::Write;
+        for (index, octet) in self.0.as_bytes().iter().enumerate() {
+            if index > 0 {
+                buffer.push(':');
+            }
+            write!(buffer, "{octet:02x}")?;
+        }
+        Ok(())
+    }
+    fn from_sql<&str>(bytes) {
+        bytes.parse()
+            .map(As)
+            .map_err(|e| format!("{bytes:?} is not a MAC address: {e}").into())
+    }
+}
+
+#[cfg(feature = "postgres")]
+crate::wrap! {
+    target = ::macaddr::MacAddr6;
+    sql_type = MacAddr;
+    pub mod native;
+    fn into_sql<[u8; 6]>(self) {
+        self.0.into_array()
+    }
+    fn from_sql<[u8; 6]>(bytes) {
+        <[u8; 6]>::from_sql(bytes).map(|octets| As(::macaddr::MacAddr6::from(octets)))
+    }
+}
@@ -0,0 +1,41 @@
+//! Wrappers for [`mime::Mime`](::mime::Mime).
+//!
+//! [`text`] stores a media type inside a `Text` column in its string form (`Mime::as_ref`), including its parameters
+//! (`text/plain; charset=utf-8`).
+//! Reading parses the text again, text that isn't a media type produces a deserialization error.
+//! Parsing lowercases the type, subtype and parameter names,
+//! text that wasn't written through the wrapper can therefore be read back in a different form.
+//!
+//! # Example:
+#![cfg_attr(feature = "sqlite", doc = "```")]
+#![cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+//! use diesel::prelude::*;
+//! use diesel::sql_types::Text;
+//! use diesel_as_wrap::mime::text;
+//!
+//! let mut conn = SqliteConnection::establish(":memory:").unwrap();
+//!
+//! let stored: String = diesel::select(text::As(mime::TEXT_PLAIN_UTF_8).into_sql::<Text>()).get_result(&mut conn).unwrap();
+//! assert_eq!(stored, "text/plain; charset=utf-8");
+//!
+//! let loaded: text::As = diesel::select("Image/SVG+XML".into_sql::<Text>()).get_result(&mut conn).unwrap();
+//! assert_eq!(loaded.0, mime::IMAGE_SVG);
+//!
+//! let invalid = diesel::select("image".into_sql::<Text>()).get_result::<text::As>(&mut conn);
+//! assert!(invalid.unwrap_err().to_string().contains(r#""image" is not a media type"#));
+//! ```
+
+crate::wrap! {
+    target = ::mime::Mime;
+    sql_type = Text;
+    pub mod text;
+    fn to_sql<str>(self, out) {
+        let text: &str = self.0.as_ref();
+        text.to_sql(out)
+    }
+    fn from_sql<&str>(bytes) {
+        bytes.parse()
+            .map(As)
+            .map_err(|e| format!("{bytes:?} is not a media type: {e}").into())
+    }
+}
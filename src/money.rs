@@ -0,0 +1,157 @@
+/// The error returned by the conversions of [`MinorUnits`].
+pub type MinorUnitsError = Box<dyn std::error::Error + Send + Sync>;
+
+///
+/// A decimal amount that can be stored as integer count of its minor units (cents for a scale of 2).\
+/// It is used by [`wrap_minor_units!`](crate::wrap_minor_units) and implemented for `bigdecimal::BigDecimal`
+/// and `rust_decimal::Decimal` with the `bigdecimal` and `rust_decimal` features.
+/// Implement it for an own money type to store it the same way.
+///
+pub trait MinorUnits: Sized {
+    /// Converts the amount into minor units, `12.34` is `1234` for a scale of 2.
+    /// Amounts with more decimal places than `scale` or that don't fit into an `i64` are an error instead of being rounded.
+    fn to_minor_units(&self, scale: u32) -> Result<i64, MinorUnitsError>;
+
+    /// Converts minor units back into the amount, `1234` is `12.34` for a scale of 2.
+    fn from_minor_units(units: i64, scale: u32) -> Result<Self, MinorUnitsError>;
+}
+
+#[cfg(feature = "bigdecimal")]
+impl MinorUnits for ::bigdecimal::BigDecimal {
+    fn to_minor_units(&self, scale: u32) -> Result<i64, MinorUnitsError> {
+        let rescaled = self.with_scale(i64::from(scale));
+        if rescaled != *self {
+            return Err(format!("{self} has more than {scale} decimal places").into());
+        }
+        let (units, _) = rescaled.into_bigint_and_exponent();
+        i64::try_from(&units).map_err(|_| format!("{self} is out of range for {scale} decimal places").into())
+    }
+
+    fn from_minor_units(units: i64, scale: u32) -> Result<Self, MinorUnitsError> {
+        Ok(::bigdecimal::BigDecimal::new(units.into(), i64::from(scale)))
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl MinorUnits for ::rust_decimal::Decimal {
+    fn to_minor_units(&self, scale: u32) -> Result<i64, MinorUnitsError> {
+        let mut rescaled = *self;
+        rescaled.rescale(scale);
+        if rescaled != *self {
+            return Err(format!("{self} has more than {scale} decimal places").into());
+        }
+        i64::try_from(rescaled.mantissa()).map_err(|_| format!("{self} is out of range for {scale} decimal places").into())
+    }
+
+    fn from_minor_units(units: i64, scale: u32) -> Result<Self, MinorUnitsError> {
+        ::rust_decimal::Decimal::try_new(units, scale).map_err(Into::into)
+    }
+}
+
+///
+/// This macro creates the wrappers of [`wrap!`](crate::wrap) for a decimal amount stored as integer count of its minor units
+/// inside a `BigInt` column, the `sql_type`, `to_sql` and `from_sql` sections are generated.\
+/// The target has to implement [`MinorUnits`](crate::MinorUnits), `scale` is the number of decimal places of the minor unit
+/// (2 for cents, 3 for mills) and checked at compile time to be at most 18.
+/// Writing an amount with more decimal places than the scale is a serialization error, amounts are never rounded.
+/// Reading returns amounts with exactly `scale` decimal places.\
+/// The `target` and `scale` sections have to come first, all other sections of [`wrap!`](crate::wrap) can follow in any order.
+/// The syntax for using this macro is the following:\
+/// ```text
+/// wrap_minor_units! {
+///     target = $decimal_type$;
+///     scale = $decimal_places$;
+///     $module_visability$ mod $name_of_wrappers_module$;
+///     [more sections of wrap!]
+/// }
+/// ```
+///
+/// # Example:
+#[cfg_attr(all(feature = "sqlite", feature = "rust_decimal"), doc = "```")]
+#[cfg_attr(not(all(feature = "sqlite", feature = "rust_decimal")), doc = "```ignore")]
+/// # use diesel::prelude::*;
+/// # mod schema {
+/// #     diesel::table! {
+/// #         orders (id) {
+/// #             id -> Integer,
+/// #             total -> BigInt,
+/// #             refunded -> Nullable<BigInt>,
+/// #         }
+/// #     }
+/// # }
+/// use std::str::FromStr;
+/// use diesel_as_wrap::wrap_minor_units;
+/// use rust_decimal::Decimal;
+///
+/// wrap_minor_units! {
+///     target = ::rust_decimal::Decimal;
+///     scale = 2;
+///     pub mod cents;
+/// }
+///
+/// #[derive(Debug, Clone, PartialEq, Queryable, Selectable, Insertable)]
+/// #[diesel(table_name = schema::orders)]
+/// pub struct Order {
+///     id: i32,
+///     #[diesel(deserialize_as = cents::As)]
+///     #[diesel(serialize_as = cents::As)]
+///     total: Decimal,
+///     #[diesel(deserialize_as = cents::AsOption)]
+///     #[diesel(serialize_as = cents::AsOption)]
+///     refunded: Option<Decimal>,
+/// }
+///
+/// # fn main() {
+/// let mut conn = SqliteConnection::establish(":memory:").unwrap();
+/// diesel::sql_query("CREATE TABLE orders (id INTEGER PRIMARY KEY, total BIGINT NOT NULL, refunded BIGINT)")
+///     .execute(&mut conn)
+///     .unwrap();
+///
+/// let orders = vec![
+///     Order { id: 1, total: Decimal::from_str("19.90").unwrap(), refunded: Some(Decimal::from_str("5.00").unwrap()) },
+///     Order { id: 2, total: Decimal::from_str("-0.01").unwrap(), refunded: None },
+/// ];
+/// diesel::insert_into(schema::orders::table).values(orders.clone()).execute(&mut conn).unwrap();
+///
+/// let stored: Vec<i64> = schema::orders::table.select(schema::orders::total).load(&mut conn).unwrap();
+/// assert_eq!(stored, [1990, -1]);
+/// let loaded: Vec<Order> = schema::orders::table.select(Order::as_select()).load(&mut conn).unwrap();
+/// assert_eq!(loaded, orders);
+///
+/// let fraction = Order { id: 3, total: Decimal::from_str("0.001").unwrap(), refunded: None };
+/// let error = diesel::insert_into(schema::orders::table).values(fraction).execute(&mut conn).unwrap_err();
+/// assert!(error.to_string().ends_with("0.001 has more than 2 decimal places"));
+/// # }
+/// ```
+///
+/// Scales beyond the digits of an `i64` are rejected:
+#[cfg_attr(feature = "rust_decimal", doc = "```compile_fail")]
+#[cfg_attr(not(feature = "rust_decimal"), doc = "```ignore")]
+/// # use diesel_as_wrap::wrap_minor_units;
+/// wrap_minor_units! {
+///     target = ::rust_decimal::Decimal;
+///     scale = 19;
+///     pub mod attos;
+/// }
+/// # fn main() {}
+/// ```
+///
+#[macro_export]
+macro_rules! wrap_minor_units {
+    (target = $target:ty; scale = $scale:literal; $($rest:tt)*) => {
+        const _: () = assert!($scale <= 18, "`scale` of `wrap_minor_units!` has to be at most 18");
+
+        $crate::wrap! {
+            target = $target;
+            sql_type = BigInt;
+            $($rest)*
+            fn into_sql<i64>(self) {
+                $crate::MinorUnits::to_minor_units(&self.0, $scale)?
+            }
+            fn from_sql<i64>(bytes) {
+                let units = i64::from_sql(bytes)?;
+                <Target as $crate::MinorUnits>::from_minor_units(units, $scale).map(As)
+            }
+        }
+    };
+}
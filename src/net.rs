@@ -0,0 +1,425 @@
+//! Wrappers for the [`std::net`] address types storing them numerically or as network addresses.
+//!
+//! Storing addresses as numbers allows range queries (for example to match subnets) on backends without native network types.
+//! - [`ipv4_bigint`] stores an `Ipv4Addr` as its numeric value (`1.2.3.4` is stored as `0x01020304`) inside a `BigInt` column.
+//!   The numeric order is the same as the address order.
+//! - [`ipv4_integer`] stores the same value inside a 32 bit `Integer` column by reinterpreting it as signed (two's complement).
+//!   Addresses from `128.0.0.0` onwards are negative, so range queries crossing that boundary have to be split.
+//! - [`ipv6_binary`] stores an `Ipv6Addr` as its 16 octets in network byte order (big endian) inside a `Binary` column.
+//!   Comparing the bytes lexicographically is the same as comparing the addresses.
+//!
+//! Values that are out of range (or binary values that aren't 16 bytes long) produce a deserialization error.
+//!
+//! [`PortableInet`] is a network address sql type for every backend, use it inside `table!` (`address -> diesel_as_wrap::net::PortableInet`).
+//! Postgres stores it as its native `inet`, MySQL and SQLite as the address text (`192.168.0.1`, `2001:db8::1`) in a `TEXT` column.
+#![cfg_attr(feature = "ipnetwork", doc = "//! [`PortableCidr`] is the same for the Postgres `cidr` type.")]
+#![cfg_attr(not(feature = "ipnetwork"), doc = "//! `PortableCidr` (requires the `ipnetwork` feature) is the same for the Postgres `cidr` type.")]
+//! - [`ip_inet`] maps an `IpAddr` to `PortableInet`.
+//!   Reading an `inet` with a netmask that doesn't cover the whole address (`10.0.0.1/24`) produces a deserialization error.
+#![cfg_attr(feature = "ipnetwork", doc = "//! - [`ip_network_inet`] and [`ip_network_cidr`] map an [`ipnetwork::IpNetwork`](::ipnetwork::IpNetwork) to `PortableInet` and `PortableCidr`,")]
+#![cfg_attr(not(feature = "ipnetwork"), doc = "//! - `ip_network_inet` and `ip_network_cidr` (requires the `ipnetwork` feature) map an `ipnetwork::IpNetwork` to `PortableInet` and `PortableCidr`,")]
+//!   the text form includes the prefix (`10.0.0.0/24`). Postgres rejects `cidr` values with bits set to the right of the netmask.
+//!
+//! # Example:
+#![cfg_attr(feature = "sqlite", doc = "```")]
+#![cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+//! use diesel::prelude::*;
+//! use diesel::sql_types::{BigInt, Binary, Integer};
+//! use diesel_as_wrap::net::{ipv4_bigint, ipv4_integer, ipv6_binary};
+//! use std::net::{Ipv4Addr, Ipv6Addr};
+//!
+//! let mut conn = SqliteConnection::establish(":memory:").unwrap();
+//!
+//! let address = Ipv4Addr::new(1, 2, 3, 4);
+//! let stored: i64 = diesel::select(ipv4_bigint::As(address).into_sql::<BigInt>()).get_result(&mut conn).unwrap();
+//! assert_eq!(stored, 0x01020304);
+//!
+//! let address = Ipv4Addr::new(255, 255, 255, 254);
+//! let stored: i32 = diesel::select(ipv4_integer::As(address).into_sql::<Integer>()).get_result(&mut conn).unwrap();
+//! assert_eq!(stored, -2);
+//! let loaded: ipv4_integer::As = diesel::select(stored.into_sql::<Integer>()).get_result(&mut conn).unwrap();
+//! assert_eq!(loaded.0, address);
+//!
+//! let address: Ipv6Addr = "2001:db8::1".parse().unwrap();
+//! let stored: Vec<u8> = diesel::select(ipv6_binary::As(address).into_sql::<Binary>()).get_result(&mut conn).unwrap();
+//! assert_eq!(&stored[..4], &[0x20, 0x01, 0x0d, 0xb8]);
+//! assert_eq!(stored[15], 1);
+//! let loaded: ipv6_binary::As = diesel::select(stored.into_sql::<Binary>()).get_result(&mut conn).unwrap();
+//! assert_eq!(loaded.0, address);
+//! ```
+//!
+//! The network address types on SQLite:
+#![cfg_attr(feature = "sqlite", doc = "```")]
+#![cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+//! # use diesel::prelude::*;
+//! # mod schema {
+//! #     diesel::table! {
+//! #         use diesel::sql_types::*;
+//! #         use diesel_as_wrap::net::PortableInet;
+//! #
+//! #         hosts (id) {
+//! #             id -> Integer,
+//! #             address -> PortableInet,
+//! #             gateway -> Nullable<PortableInet>,
+//! #         }
+//! #     }
+//! # }
+//! use std::net::IpAddr;
+//! use diesel_as_wrap::net::ip_inet;
+//!
+//! #[derive(Debug, Clone, PartialEq, Queryable, Selectable, Insertable)]
+//! #[diesel(table_name = schema::hosts)]
+//! pub struct Host {
+//!     id: i32,
+//!     #[diesel(deserialize_as = ip_inet::As)]
+//!     #[diesel(serialize_as = ip_inet::As)]
+//!     address: IpAddr,
+//!     #[diesel(deserialize_as = ip_inet::AsOption)]
+//!     #[diesel(serialize_as = ip_inet::AsOption)]
+//!     gateway: Option<IpAddr>,
+//! }
+//!
+//! # fn main() {
+//! let mut conn = SqliteConnection::establish(":memory:").unwrap();
+//! diesel::sql_query("CREATE TABLE hosts (id INTEGER PRIMARY KEY, address TEXT NOT NULL, gateway TEXT)")
+//!     .execute(&mut conn)
+//!     .unwrap();
+//!
+//! let hosts = vec![
+//!     Host { id: 1, address: "192.168.0.10".parse().unwrap(), gateway: Some("192.168.0.1".parse().unwrap()) },
+//!     Host { id: 2, address: "2001:db8::1".parse().unwrap(), gateway: None },
+//! ];
+//! diesel::insert_into(schema::hosts::table).values(hosts.clone()).execute(&mut conn).unwrap();
+//!
+//! let stored: String = schema::hosts::table
+//!     .filter(schema::hosts::id.eq(2))
+//!     .select(diesel::dsl::sql::<diesel::sql_types::Text>("address"))
+//!     .first(&mut conn)
+//!     .unwrap();
+//! assert_eq!(stored, "2001:db8::1");
+//! let loaded: Vec<Host> = schema::hosts::table.select(Host::as_select()).load(&mut conn).unwrap();
+//! assert_eq!(loaded, hosts);
+//! # }
+//! ```
+//!
+//! The binary format of Postgres `inet` and `cidr` (family, netmask bits, is cidr, address length and the address):
+#![cfg_attr(all(feature = "postgres", feature = "ipnetwork"), doc = "```")]
+#![cfg_attr(not(all(feature = "postgres", feature = "ipnetwork")), doc = "```ignore")]
+//! # use diesel::deserialize::FromSql;
+//! # use diesel::pg::{Pg, PgMetadataLookup, PgTypeMetadata, PgValue};
+//! # use diesel::query_builder::bind_collector::RawBytesBindCollector;
+//! # use diesel::query_builder::BindCollector;
+//! # use diesel::serialize::ToSql;
+//! # use diesel::sql_types::HasSqlType;
+//! # struct Types;
+//! # impl PgMetadataLookup for Types {
+//! #     fn lookup_type(&mut self, _: &str, _: Option<&str>) -> PgTypeMetadata {
+//! #         PgTypeMetadata::new(100_000, 100_001)
+//! #     }
+//! # }
+//! # fn encode<ST, T: ToSql<ST, Pg>>(value: &T) -> Option<Vec<u8>> where Pg: HasSqlType<ST> {
+//! #     let mut collector = RawBytesBindCollector::<Pg>::new();
+//! #     collector.push_bound_value::<ST, T>(value, &mut Types).unwrap();
+//! #     collector.binds.pop().unwrap()
+//! # }
+//! # fn decode<ST, T: FromSql<ST, Pg>>(bytes: Option<&[u8]>) -> diesel::deserialize::Result<T> where Pg: HasSqlType<ST> {
+//! #     let oid = std::num::NonZeroU32::new(<Pg as HasSqlType<ST>>::metadata(&mut Types).oid().unwrap()).unwrap();
+//! #     T::from_nullable_sql(bytes.map(|bytes| PgValue::new(bytes, &oid)))
+//! # }
+//! use std::net::{IpAddr, Ipv6Addr};
+//! use diesel_as_wrap::net::{ip_inet, ip_network_cidr, ip_network_inet, PortableCidr, PortableInet};
+//! use ipnetwork::IpNetwork;
+//!
+//! let address: IpAddr = "192.168.0.10".parse().unwrap();
+//! let bytes = encode::<PortableInet, _>(&ip_inet::As(address)).unwrap();
+//! assert_eq!(bytes, [2, 32, 0, 4, 192, 168, 0, 10]);
+//! assert_eq!(decode::<PortableInet, ip_inet::As>(Some(&bytes)).unwrap().0, address);
+//!
+//! let address: Ipv6Addr = "2001:db8::1".parse().unwrap();
+//! let bytes = encode::<PortableInet, _>(&ip_inet::As(IpAddr::V6(address))).unwrap();
+//! assert_eq!(bytes[..4], [3, 128, 0, 16]);
+//! assert_eq!(bytes[4..], address.octets());
+//! assert_eq!(decode::<PortableInet, ip_inet::As>(Some(&bytes)).unwrap().0, IpAddr::V6(address));
+//!
+//! // inet '10.0.0.1/24' is a network, not a single address
+//! let host_in_network = [2, 24, 0, 4, 10, 0, 0, 1];
+//! let error = decode::<PortableInet, ip_inet::As>(Some(&host_in_network)).unwrap_err();
+//! assert_eq!(error.to_string(), "10.0.0.1/24 is a network, not a single address");
+//! let network = decode::<PortableInet, ip_network_inet::As>(Some(&host_in_network)).unwrap();
+//! assert_eq!(network.0, "10.0.0.1/24".parse::<IpNetwork>().unwrap());
+//!
+//! let network: IpNetwork = "10.0.0.0/24".parse().unwrap();
+//! let bytes = encode::<PortableCidr, _>(&ip_network_cidr::As(network)).unwrap();
+//! assert_eq!(bytes, [2, 24, 1, 4, 10, 0, 0, 0]);
+//! assert_eq!(decode::<PortableCidr, ip_network_cidr::As>(Some(&bytes)).unwrap().0, network);
+//! let network: IpNetwork = "2001:db8::/32".parse().unwrap();
+//! let bytes = encode::<PortableCidr, _>(&ip_network_cidr::As(network)).unwrap();
+//! assert_eq!(bytes[..8], [3, 32, 1, 16, 0x20, 0x01, 0x0d, 0xb8]);
+//! assert_eq!(decode::<PortableCidr, ip_network_cidr::As>(Some(&bytes)).unwrap().0, network);
+//!
+//! let error = decode::<PortableInet, ip_inet::As>(Some(&bytes[..7])).unwrap_err();
+//! assert_eq!(error.to_string(), "invalid network address of 7 bytes");
+//! ```
+
+#[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+use diesel::deserialize::{self, FromSql};
+#[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+use diesel::serialize::{self, Output, ToSql};
+
+#[cfg(any(feature = "mysql", feature = "sqlite"))]
+use crate::ToSqlOwned;
+
+crate::wrap! {
+    target = ::std::net::Ipv4Addr;
+    sql_type = BigInt;
+    pub mod ipv4_bigint;
+    fn into_sql<i64>(self) {
+        i64::from(u32::from(self.0))
+    }
+    fn from_sql<i64>(bytes) {
+        let value = i64::from_sql(bytes)?;
+        u32::try_from(value)
+            .map(|value| As(::std::net::Ipv4Addr::from(value)))
+            .map_err(|_| format!("{value} is not a valid ipv4 address").into())
+    }
+}
+
+crate::wrap! {
+    target = ::std::net::Ipv4Addr;
+    sql_type = Integer;
+    pub mod ipv4_integer;
+    fn into_sql<i32>(self) {
+        i32::from_be_bytes(self.0.octets())
+    }
+    fn from_sql<i32>(bytes) {
+        let value = i32::from_sql(bytes)?;
+        Ok(As(::std::net::Ipv4Addr::from(value.to_be_bytes())))
+    }
+}
+
+crate::wrap! {
+    target = ::std::net::Ipv6Addr;
+    sql_type = Binary;
+    pub mod ipv6_binary;
+    fn into_sql<Vec<u8>>(self) {
+        self.0.octets().to_vec()
+    }
+    fn from_sql<&[u8]>(bytes) {
+        <[u8; 16]>::try_from(bytes)
+            .map(|octets| As(::std::net::Ipv6Addr::from(octets)))
+            .map_err(|_| format!("expected 16 bytes for an ipv6 address, got {}", bytes.len()).into())
+    }
+}
+
+///
+/// A network address sql type that is `inet` on Postgres and `TEXT` on MySQL and SQLite.
+///
+#[derive(Debug, Clone, Copy, Default, diesel::sql_types::SqlType, diesel::query_builder::QueryId)]
+pub struct PortableInet;
+
+///
+/// A network sql type that is `cidr` on Postgres and `TEXT` on MySQL and SQLite.
+///
+#[cfg(feature = "ipnetwork")]
+#[derive(Debug, Clone, Copy, Default, diesel::sql_types::SqlType, diesel::query_builder::QueryId)]
+pub struct PortableCidr;
+
+#[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+macro_rules! has_sql_type {
+    ($portable:ty => $($backend:ty: $native:ty),+) => {
+        $(
+            impl diesel::sql_types::HasSqlType<$portable> for $backend {
+                fn metadata(lookup: &mut Self::MetadataLookup) -> Self::TypeMetadata {
+                    <$backend as diesel::sql_types::HasSqlType<$native>>::metadata(lookup)
+                }
+            }
+        )+
+    };
+}
+
+#[cfg(feature = "postgres")]
+has_sql_type!(PortableInet => diesel::pg::Pg: diesel::sql_types::Inet);
+#[cfg(feature = "mysql")]
+has_sql_type!(PortableInet => diesel::mysql::Mysql: diesel::sql_types::Text);
+#[cfg(feature = "sqlite")]
+has_sql_type!(PortableInet => diesel::sqlite::Sqlite: diesel::sql_types::Text);
+#[cfg(all(feature = "ipnetwork", feature = "postgres"))]
+has_sql_type!(PortableCidr => diesel::pg::Pg: diesel::sql_types::Cidr);
+#[cfg(all(feature = "ipnetwork", feature = "mysql"))]
+has_sql_type!(PortableCidr => diesel::mysql::Mysql: diesel::sql_types::Text);
+#[cfg(all(feature = "ipnetwork", feature = "sqlite"))]
+has_sql_type!(PortableCidr => diesel::sqlite::Sqlite: diesel::sql_types::Text);
+
+// the binary format of Postgres `inet` and `cidr`: family, netmask bits, is cidr, address length and the address
+#[cfg(feature = "postgres")]
+mod pg_inet {
+    use std::io::Write;
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+    use diesel::serialize::{self, IsNull, Output};
+
+    const FAMILY_V4: u8 = 2;
+    const FAMILY_V6: u8 = 3;
+
+    pub(super) fn write(out: &mut Output<'_, '_, diesel::pg::Pg>, address: IpAddr, bits: u8, is_cidr: bool) -> serialize::Result {
+        match address {
+            IpAddr::V4(address) => {
+                out.write_all(&[FAMILY_V4, bits, u8::from(is_cidr), 4])?;
+                out.write_all(&address.octets())?;
+            }
+            IpAddr::V6(address) => {
+                out.write_all(&[FAMILY_V6, bits, u8::from(is_cidr), 16])?;
+                out.write_all(&address.octets())?;
+            }
+        }
+        Ok(IsNull::No)
+    }
+
+    pub(super) fn full_bits(address: IpAddr) -> u8 {
+        if address.is_ipv4() { 32 } else { 128 }
+    }
+
+    pub(super) fn read(bytes: &[u8]) -> Result<(IpAddr, u8), String> {
+        match bytes {
+            [FAMILY_V4, bits, _, 4, octets @ ..] => <[u8; 4]>::try_from(octets).ok().map(|octets| (IpAddr::V4(Ipv4Addr::from(octets)), *bits)),
+            [FAMILY_V6, bits, _, 16, octets @ ..] => <[u8; 16]>::try_from(octets).ok().map(|octets| (IpAddr::V6(Ipv6Addr::from(octets)), *bits)),
+            _ => None,
+        }
+        .ok_or_else(|| format!("invalid network address of {} bytes", bytes.len()))
+    }
+}
+
+#[cfg(any(feature = "mysql", feature = "sqlite"))]
+fn parse_text<T>(text: &str, what: &str) -> deserialize::Result<T>
+where
+    T: ::std::str::FromStr,
+    T::Err: ::std::fmt::Display,
+{
+    text.parse().map_err(|e| format!("{text:?} is not {what}: {e}").into())
+}
+
+#[cfg(feature = "postgres")]
+impl ToSql<PortableInet, diesel::pg::Pg> for ::std::net::IpAddr {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, diesel::pg::Pg>) -> serialize::Result {
+        pg_inet::write(out, *self, pg_inet::full_bits(*self), false)
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl FromSql<PortableInet, diesel::pg::Pg> for ::std::net::IpAddr {
+    fn from_sql(bytes: diesel::pg::PgValue<'_>) -> deserialize::Result<Self> {
+        let (address, bits) = pg_inet::read(bytes.as_bytes())?;
+        if bits != pg_inet::full_bits(address) {
+            return Err(format!("{address}/{bits} is a network, not a single address").into());
+        }
+        Ok(address)
+    }
+}
+
+#[cfg(all(feature = "ipnetwork", feature = "postgres"))]
+macro_rules! pg_ip_network {
+    ($($sql_type:ty: $is_cidr:literal),+) => {
+        $(
+            impl ToSql<$sql_type, diesel::pg::Pg> for ::ipnetwork::IpNetwork {
+                fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, diesel::pg::Pg>) -> serialize::Result {
+                    pg_inet::write(out, self.ip(), self.prefix(), $is_cidr)
+                }
+            }
+
+            impl FromSql<$sql_type, diesel::pg::Pg> for ::ipnetwork::IpNetwork {
+                fn from_sql(bytes: diesel::pg::PgValue<'_>) -> deserialize::Result<Self> {
+                    let (address, bits) = pg_inet::read(bytes.as_bytes())?;
+                    ::ipnetwork::IpNetwork::new(address, bits).map_err(Into::into)
+                }
+            }
+        )+
+    };
+}
+
+#[cfg(all(feature = "ipnetwork", feature = "postgres"))]
+pg_ip_network!(PortableInet: false, PortableCidr: true);
+
+// MySQL and SQLite store the text form
+#[cfg(any(feature = "mysql", feature = "sqlite"))]
+macro_rules! text_network {
+    ($($ty:ty: $sql_type:ty, $what:literal => $($backend:ty),+;)+) => {
+        $($(
+            impl ToSql<$sql_type, $backend> for $ty {
+                fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, $backend>) -> serialize::Result {
+                    ToSqlOwned::<diesel::sql_types::Text, $backend>::to_sql_owned(self.to_string(), out)
+                }
+            }
+
+            impl FromSql<$sql_type, $backend> for $ty {
+                fn from_sql(bytes: <$backend as diesel::backend::Backend>::RawValue<'_>) -> deserialize::Result<Self> {
+                    let text = <*const str as FromSql<diesel::sql_types::Text, $backend>>::from_sql(bytes)?;
+                    // SAFETY: diesel guarantees the pointer to be valid for the duration of `from_sql`.
+                    parse_text(unsafe { &*text }, $what)
+                }
+            }
+        )+)+
+    };
+}
+
+#[cfg(feature = "mysql")]
+text_network! {
+    ::std::net::IpAddr: PortableInet, "an ip address" => diesel::mysql::Mysql;
+}
+
+#[cfg(feature = "sqlite")]
+text_network! {
+    ::std::net::IpAddr: PortableInet, "an ip address" => diesel::sqlite::Sqlite;
+}
+
+#[cfg(all(feature = "ipnetwork", feature = "mysql"))]
+text_network! {
+    ::ipnetwork::IpNetwork: PortableInet, "an ip network" => diesel::mysql::Mysql;
+    ::ipnetwork::IpNetwork: PortableCidr, "an ip network" => diesel::mysql::Mysql;
+}
+
+#[cfg(all(feature = "ipnetwork", feature = "sqlite"))]
+text_network! {
+    ::ipnetwork::IpNetwork: PortableInet, "an ip network" => diesel::sqlite::Sqlite;
+    ::ipnetwork::IpNetwork: PortableCidr, "an ip network" => diesel::sqlite::Sqlite;
+}
+
+crate::wrap! {
+    target = ::std::net::IpAddr;
+    sql_type = crate::net::PortableInet;
+    pub mod ip_inet;
+    fn to_sql<::std::net::IpAddr>(self, out) {
+        let value: &::std::net::IpAddr = &self.0;
+        value.to_sql(out)
+    }
+    fn from_sql<::std::net::IpAddr>(bytes) {
+        <::std::net::IpAddr>::from_sql(bytes).map(As)
+    }
+}
+
+#[cfg(feature = "ipnetwork")]
+crate::wrap! {
+    target = ::ipnetwork::IpNetwork;
+    sql_type = crate::net::PortableInet;
+    pub mod ip_network_inet;
+    fn to_sql<::ipnetwork::IpNetwork>(self, out) {
+        let value: &::ipnetwork::IpNetwork = &self.0;
+        value.to_sql(out)
+    }
+    fn from_sql<::ipnetwork::IpNetwork>(bytes) {
+        <::ipnetwork::IpNetwork>::from_sql(bytes).map(As)
+    }
+}
+
+#[cfg(feature = "ipnetwork")]
+crate::wrap! {
+    target = ::ipnetwork::IpNetwork;
+    sql_type = crate::net::PortableCidr;
+    pub mod ip_network_cidr;
+    fn to_sql<::ipnetwork::IpNetwork>(self, out) {
+        let value: &::ipnetwork::IpNetwork = &self.0;
+        value.to_sql(out)
+    }
+    fn from_sql<::ipnetwork::IpNetwork>(bytes) {
+        <::ipnetwork::IpNetwork>::from_sql(bytes).map(As)
+    }
+}
@@ -0,0 +1,133 @@
+use diesel::backend::Backend;
+use diesel::serialize::{Output, Result as SResult};
+
+///
+/// Serializes a value that is created inside of `to_sql` and therefore can't be borrowed for the lifetime of the output.\
+/// Diesel only allows reborrowing the output on byte based backends (Postgres and MySQL),
+/// Sqlite instead takes ownership of the bound value.
+/// This trait hides that difference so wrappers can compute their intermediate value on the fly.\
+/// It is used by the `into_sql` form of [`wrap!`](crate::wrap) and implemented for the backends enabled by the
+/// `postgres`, `mysql` and `sqlite` features.
+///
+pub trait ToSqlOwned<ST, DB: Backend> {
+    /// Writes the owned value to the output.
+    fn to_sql_owned(self, out: &mut Output<'_, '_, DB>) -> SResult;
+}
+
+#[cfg(feature = "postgres")]
+impl<ST, T> ToSqlOwned<ST, diesel::pg::Pg> for T
+where
+    T: diesel::serialize::ToSql<ST, diesel::pg::Pg>,
+{
+    fn to_sql_owned(self, out: &mut Output<'_, '_, diesel::pg::Pg>) -> SResult {
+        self.to_sql(&mut out.reborrow())
+    }
+}
+
+#[cfg(feature = "mysql")]
+impl<ST, T> ToSqlOwned<ST, diesel::mysql::Mysql> for T
+where
+    T: diesel::serialize::ToSql<ST, diesel::mysql::Mysql>,
+{
+    fn to_sql_owned(self, out: &mut Output<'_, '_, diesel::mysql::Mysql>) -> SResult {
+        self.to_sql(&mut out.reborrow())
+    }
+}
+
+#[cfg(feature = "sqlite")]
+macro_rules! sqlite_owned {
+    ($($ty:ty => $bind:ty),+ $(,)?) => {
+        $(
+            impl<ST> ToSqlOwned<ST, diesel::sqlite::Sqlite> for $ty
+            where
+                $ty: diesel::serialize::ToSql<ST, diesel::sqlite::Sqlite>,
+            {
+                fn to_sql_owned(self, out: &mut Output<'_, '_, diesel::sqlite::Sqlite>) -> SResult {
+                    out.set_value(<$bind>::from(self));
+                    Ok(diesel::serialize::IsNull::No)
+                }
+            }
+        )+
+    };
+}
+
+#[cfg(feature = "sqlite")]
+sqlite_owned! {
+    bool => i32,
+    i16 => i32,
+    i32 => i32,
+    i64 => i64,
+    f32 => f64,
+    f64 => f64,
+    String => String,
+    Vec<u8> => Vec<u8>,
+}
+
+// the formats diesel writes the chrono types with on sqlite
+#[cfg(all(feature = "sqlite", feature = "chrono"))]
+macro_rules! sqlite_owned_formatted {
+    ($($ty:ty => $format:literal),+ $(,)?) => {
+        $(
+            impl<ST> ToSqlOwned<ST, diesel::sqlite::Sqlite> for $ty
+            where
+                $ty: diesel::serialize::ToSql<ST, diesel::sqlite::Sqlite>,
+            {
+                fn to_sql_owned(self, out: &mut Output<'_, '_, diesel::sqlite::Sqlite>) -> SResult {
+                    out.set_value(self.format($format).to_string());
+                    Ok(diesel::serialize::IsNull::No)
+                }
+            }
+        )+
+    };
+}
+
+#[cfg(all(feature = "sqlite", feature = "chrono"))]
+sqlite_owned_formatted! {
+    ::chrono::NaiveDate => "%F",
+    ::chrono::NaiveTime => "%T%.f",
+    ::chrono::NaiveDateTime => "%F %T%.f",
+}
+
+// the text diesel writes the time types as on sqlite, without requiring the formatting features of `time`
+#[cfg(all(feature = "sqlite", feature = "time"))]
+mod sqlite_time {
+    use std::fmt::Write;
+
+    pub(super) fn date(date: ::time::Date) -> String {
+        format!("{:04}-{:02}-{:02}", date.year(), u8::from(date.month()), date.day())
+    }
+
+    pub(super) fn time(time: ::time::Time) -> String {
+        let mut text = format!("{:02}:{:02}:{:02}", time.hour(), time.minute(), time.second());
+        if time.nanosecond() != 0 {
+            let nanos = format!("{:09}", time.nanosecond());
+            let _ = write!(text, ".{}", nanos.trim_end_matches('0'));
+        }
+        text
+    }
+}
+
+#[cfg(all(feature = "sqlite", feature = "time"))]
+macro_rules! sqlite_owned_time {
+    ($($ty:ty => |$value:ident| $text:expr),+ $(,)?) => {
+        $(
+            impl<ST> ToSqlOwned<ST, diesel::sqlite::Sqlite> for $ty
+            where
+                $ty: diesel::serialize::ToSql<ST, diesel::sqlite::Sqlite>,
+            {
+                fn to_sql_owned(self, out: &mut Output<'_, '_, diesel::sqlite::Sqlite>) -> SResult {
+                    let $value = self;
+                    out.set_value($text);
+                    Ok(diesel::serialize::IsNull::No)
+                }
+            }
+        )+
+    };
+}
+
+#[cfg(all(feature = "sqlite", feature = "time"))]
+sqlite_owned_time! {
+    ::time::Date => |value| sqlite_time::date(value),
+    ::time::Time => |value| sqlite_time::time(value),
+    ::time::PrimitiveDateTime => |value| format!("{} {}", sqlite_time::date(value.date()), sqlite_time::time(value.time())),
+}
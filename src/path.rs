@@ -0,0 +1,134 @@
+//! Wrappers for file system paths inside `Text` columns, on every backend.
+//!
+//! A [`PathBuf`](std::path::PathBuf) can contain bytes that aren't valid UTF-8 on Unix (and unpaired surrogates on Windows),
+//! which can't be stored in a `Text` column unchanged. The handling of such paths is selected by picking the module:
+//! - [`utf8`] produces a serialization error for paths that aren't valid UTF-8, so a stored path always reads back unchanged.
+//! - [`lossy`] replaces the invalid parts with `U+FFFD REPLACEMENT CHARACTER` (`Path::to_string_lossy`),
+//!   the stored path can therefore differ from the written one and no longer point to the same file.
+//!
+//! [`camino`] stores a [`camino::Utf8PathBuf`](::camino::Utf8PathBuf) (requires the `camino` feature),
+//! which is valid UTF-8 by construction, so writing never fails.
+//!
+//! Reading is the same for all modules, the text is converted into the path without any validation or normalization.
+//!
+//! # Example:
+#![cfg_attr(feature = "sqlite", doc = "```")]
+#![cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+//! # use diesel::prelude::*;
+//! # mod schema {
+//! #     diesel::table! {
+//! #         documents (id) {
+//! #             id -> Integer,
+//! #             path -> Text,
+//! #             thumbnail -> Nullable<Text>,
+//! #         }
+//! #     }
+//! # }
+//! use std::path::PathBuf;
+//! use diesel_as_wrap::path::utf8;
+//!
+//! #[derive(Debug, Clone, PartialEq, Queryable, Selectable, Insertable)]
+//! #[diesel(table_name = schema::documents)]
+//! pub struct Document {
+//!     id: i32,
+//!     #[diesel(deserialize_as = utf8::As)]
+//!     #[diesel(serialize_as = utf8::As)]
+//!     path: PathBuf,
+//!     #[diesel(deserialize_as = utf8::AsOption)]
+//!     #[diesel(serialize_as = utf8::AsOption)]
+//!     thumbnail: Option<PathBuf>,
+//! }
+//!
+//! # fn main() {
+//! let mut conn = SqliteConnection::establish(":memory:").unwrap();
+//! diesel::sql_query("CREATE TABLE documents (id INTEGER PRIMARY KEY, path TEXT NOT NULL, thumbnail TEXT)")
+//!     .execute(&mut conn)
+//!     .unwrap();
+//!
+//! let documents = vec![
+//!     Document { id: 1, path: PathBuf::from("/srv/files/Übersicht.pdf"), thumbnail: Some(PathBuf::from("thumbs/1.png")) },
+//!     Document { id: 2, path: PathBuf::from("/srv/files/notes.txt"), thumbnail: None },
+//! ];
+//! diesel::insert_into(schema::documents::table).values(documents.clone()).execute(&mut conn).unwrap();
+//! let loaded: Vec<Document> = schema::documents::table.select(Document::as_select()).load(&mut conn).unwrap();
+//! assert_eq!(loaded, documents);
+//! # }
+//! ```
+//!
+//! Paths that aren't valid UTF-8:
+#![cfg_attr(all(feature = "sqlite", unix), doc = "```")]
+#![cfg_attr(not(all(feature = "sqlite", unix)), doc = "```ignore")]
+//! use std::ffi::OsStr;
+//! use std::os::unix::ffi::OsStrExt;
+//! use std::path::PathBuf;
+//! use diesel::prelude::*;
+//! use diesel::sql_types::Text;
+//! use diesel_as_wrap::path::{lossy, utf8};
+//!
+//! let mut conn = SqliteConnection::establish(":memory:").unwrap();
+//!
+//! let path = PathBuf::from(OsStr::from_bytes(b"/tmp/caf\xe9.txt"));
+//! let error = diesel::select(utf8::As(path.clone()).into_sql::<Text>()).get_result::<String>(&mut conn);
+//! assert!(error.unwrap_err().to_string().ends_with(r#""/tmp/caf\xE9.txt" is not valid UTF-8"#));
+//!
+//! let stored: String = diesel::select(lossy::As(path).into_sql::<Text>()).get_result(&mut conn).unwrap();
+//! assert_eq!(stored, "/tmp/caf\u{FFFD}.txt");
+//! ```
+//!
+//! A `camino` path:
+#![cfg_attr(all(feature = "sqlite", feature = "camino"), doc = "```")]
+#![cfg_attr(not(all(feature = "sqlite", feature = "camino")), doc = "```ignore")]
+//! use ::camino::Utf8PathBuf;
+//! use diesel::prelude::*;
+//! use diesel::sql_types::Text;
+//! use diesel_as_wrap::path::camino;
+//!
+//! let mut conn = SqliteConnection::establish(":memory:").unwrap();
+//!
+//! let path = Utf8PathBuf::from("assets/logo.svg");
+//! let loaded: camino::As = diesel::select(camino::As(path.clone()).into_sql::<Text>()).get_result(&mut conn).unwrap();
+//! assert_eq!(loaded.0, path);
+//! ```
+
+crate::wrap! {
+    target = ::std::path::PathBuf;
+    sql_type = Text;
+    pub mod utf8;
+    fn to_sql<str>(self, out) {
+        let text = self.0.to_str().ok_or_else(|| format!("{:?} is not valid UTF-8", self.0))?;
+        text.to_sql(out)
+    }
+    fn from_sql<String>(bytes) {
+        let text = String::from_sql(bytes)?;
+        Ok(As(text.into()))
+    }
+}
+
+crate::wrap! {
+    target = ::std::path::PathBuf;
+    sql_type = Text;
+    pub mod lossy;
+    fn buffered_sql<String>(self, buffer) {
+        buffer.push_str(&self.0.to_string_lossy());
+        Ok(())
+    }
+    fn from_sql<String>(bytes) {
+        let text = String::from_sql(bytes)?;
+        Ok(As(text.into()))
+    }
+}
+
+#[cfg(feature = "camino")]
+crate::wrap! {
+    target = ::camino::Utf8PathBuf;
+    sql_type = Text;
+    pub mod camino;
+    fn to_sql<str>(self, out) {
+        let text: &str = self.0.as_str();
+        text.to_sql(out)
+    }
+    fn from_sql<String>(bytes) {
+        let text = String::from_sql(bytes)?;
+        Ok(As(text.into()))
+    }
+}
@@ -0,0 +1,86 @@
+use std::fmt::{self, Write};
+use std::str::FromStr;
+
+use diesel::deserialize;
+
+use crate::{AsBinary, AsText, FillError};
+
+///
+/// A value that is turned into bytes before it is stored, by wrappers transforming the bytes themselves
+/// (like `AsEncrypted` of the `encrypted` feature and `AsCompressed` of the `zstd` and `deflate` features).\
+/// It is implemented for `String`, `Vec<u8>`, [`AsText`], [`AsBinary`], `AsCompressed` and, with the `json` feature,
+/// `AsJson`, so the existing adapters choose how a value becomes bytes.
+///
+pub trait Payload: Sized {
+    /// Appends the bytes of the value to the buffer.
+    fn write_payload(&self, buffer: &mut Vec<u8>) -> Result<(), FillError>;
+
+    /// Reads the value from the bytes written by [`write_payload`](Payload::write_payload).
+    fn read_payload(bytes: Vec<u8>) -> deserialize::Result<Self>;
+}
+
+impl Payload for String {
+    fn write_payload(&self, buffer: &mut Vec<u8>) -> Result<(), FillError> {
+        buffer.extend_from_slice(self.as_bytes());
+        Ok(())
+    }
+
+    fn read_payload(bytes: Vec<u8>) -> deserialize::Result<Self> {
+        Ok(String::from_utf8(bytes)?)
+    }
+}
+
+impl Payload for Vec<u8> {
+    fn write_payload(&self, buffer: &mut Vec<u8>) -> Result<(), FillError> {
+        buffer.extend_from_slice(self);
+        Ok(())
+    }
+
+    fn read_payload(bytes: Vec<u8>) -> deserialize::Result<Self> {
+        Ok(bytes)
+    }
+}
+
+impl<T> Payload for AsText<T>
+where
+    T: fmt::Display + FromStr,
+    T::Err: fmt::Display,
+{
+    fn write_payload(&self, buffer: &mut Vec<u8>) -> Result<(), FillError> {
+        struct Bytes<'a>(&'a mut Vec<u8>);
+
+        impl Write for Bytes<'_> {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                self.0.extend_from_slice(s.as_bytes());
+                Ok(())
+            }
+        }
+
+        write!(Bytes(buffer), "{}", self.0)?;
+        Ok(())
+    }
+
+    fn read_payload(bytes: Vec<u8>) -> deserialize::Result<Self> {
+        let text = String::from_utf8(bytes)?;
+        text.parse()
+            .map(AsText)
+            .map_err(|e| format!("{text:?} is not a valid `{}`: {e}", std::any::type_name::<T>()).into())
+    }
+}
+
+impl<T> Payload for AsBinary<T>
+where
+    T: AsRef<[u8]> + TryFrom<Vec<u8>>,
+{
+    fn write_payload(&self, buffer: &mut Vec<u8>) -> Result<(), FillError> {
+        buffer.extend_from_slice(self.0.as_ref());
+        Ok(())
+    }
+
+    fn read_payload(bytes: Vec<u8>) -> deserialize::Result<Self> {
+        let len = bytes.len();
+        T::try_from(bytes)
+            .map(AsBinary)
+            .map_err(|_| format!("{len} bytes are not a valid `{}`", std::any::type_name::<T>()).into())
+    }
+}
@@ -0,0 +1,81 @@
+//! Wrappers for [`phonenumber::PhoneNumber`](::phonenumber::PhoneNumber).
+//!
+//! [`e164`] stores a phone number inside a `Text` column in the E.164 format (`+41446681800`),
+//! so every number of the column has the same normalized form and can be compared and indexed as text.
+//! Extensions aren't part of E.164 and are dropped when writing, as is the carrier code.
+//! Reading parses the text without a default region, which is always possible for E.164 text,
+//! and validates the number against the metadata of its region.
+//! Text that isn't a phone number or a number that isn't valid produces a deserialization error.
+//! Numbers are read back with the country code source `Plus`, a number parsed from national text with a default region
+//! (source `Default`) therefore doesn't compare equal to the read one, even though both are the same number.
+//!
+//! # Example:
+#![cfg_attr(feature = "sqlite", doc = "```")]
+#![cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+//! # use diesel::prelude::*;
+//! # mod schema {
+//! #     diesel::table! {
+//! #         contacts (id) {
+//! #             id -> Integer,
+//! #             phone -> Text,
+//! #             fax -> Nullable<Text>,
+//! #         }
+//! #     }
+//! # }
+//! use phonenumber::{country, PhoneNumber};
+//! use diesel_as_wrap::phonenumber::e164;
+//!
+//! #[derive(Debug, Clone, PartialEq, Queryable, Selectable, Insertable)]
+//! #[diesel(table_name = schema::contacts)]
+//! pub struct Contact {
+//!     id: i32,
+//!     #[diesel(deserialize_as = e164::As)]
+//!     #[diesel(serialize_as = e164::As)]
+//!     phone: PhoneNumber,
+//!     #[diesel(deserialize_as = e164::AsOption)]
+//!     #[diesel(serialize_as = e164::AsOption)]
+//!     fax: Option<PhoneNumber>,
+//! }
+//!
+//! # fn main() {
+//! let mut conn = SqliteConnection::establish(":memory:").unwrap();
+//! diesel::sql_query("CREATE TABLE contacts (id INTEGER PRIMARY KEY, phone TEXT NOT NULL, fax TEXT)")
+//!     .execute(&mut conn)
+//!     .unwrap();
+//!
+//! let contact = Contact {
+//!     id: 1,
+//!     phone: phonenumber::parse(Some(country::CH), "+41 44 668 18 00").unwrap(),
+//!     fax: None,
+//! };
+//! diesel::insert_into(schema::contacts::table).values(contact.clone()).execute(&mut conn).unwrap();
+//!
+//! let stored: String = schema::contacts::table.select(schema::contacts::phone).first(&mut conn).unwrap();
+//! assert_eq!(stored, "+41446681800");
+//! let loaded: Contact = schema::contacts::table.select(Contact::as_select()).first(&mut conn).unwrap();
+//! assert_eq!(loaded, contact);
+//!
+//! diesel::sql_query("UPDATE contacts SET phone = '+4144668' WHERE id = 1").execute(&mut conn).unwrap();
+//! let error = schema::contacts::table.select(Contact::as_select()).first(&mut conn).unwrap_err();
+//! assert!(error.to_string().ends_with(r#""+4144668" is not a valid phone number"#));
+//! # }
+//! ```
+
+crate::wrap! {
+    target = ::phonenumber::PhoneNumber;
+    sql_type = Text;
+    pub mod e164;
+    fn buffered_sql<String>(self, buffer) {
+        use std::fmt::Write;
+        write!(buffer, "{}", self.0.format().mode(::phonenumber::Mode::E164))?;
+        Ok(())
+    }
+    fn from_sql<&str>(bytes) {
+        let number = ::phonenumber::parse(None, bytes)
+            .map_err(|e| format!("{bytes:?} is not a phone number: {e}"))?;
+        if !number.is_valid() {
+            return Err(format!("{bytes:?} is not a valid phone number").into());
+        }
+        Ok(As(number))
+    }
+}
@@ -0,0 +1,491 @@
+//! Wrappers for Postgres composite types with tuples and structs as targets.
+//!
+//! [`wrap_record!`](crate::wrap_record) wraps a struct target, each listed field becomes an attribute of the composite value.
+//!
+//! [`wrap_tuple!`](crate::wrap_tuple) wraps a tuple target like `(i32, String)` without defining a named struct,
+//! for composite function results and ad-hoc pairs.
+//! The `fields` section lists the sql types of the tuple elements, the `sql_type` is either
+//! - `Record<fields>` for reading anonymous records (Postgres doesn't accept anonymous records as bind parameters) or
+//! - a composite type declared with `#[diesel(postgres_type(name = "..."))]` and implementing [`IsComposite<fields>`](IsComposite)
+//!   for reading and writing.
+//!
+//! Diesel can read tuples from records but only writes them as row constructors inside queries,
+//! [`RecordTuple`] adds the missing `ToSql` using [`WriteTuple`].
+//!
+//! Like every module created by [`wrap!`](crate::wrap) the modules contain the array wrappers `AsVec`, `AsOptionVec`,
+//! `AsVecOption` and `AsOptionVecOption`, so arrays of composite values (`line_item[]`) map to `Vec<LineItem>`
+//! (see [`wrap_record!`](crate::wrap_record)).
+//!
+//! # Example:
+#![cfg_attr(feature = "postgres", doc = "```")]
+#![cfg_attr(not(feature = "postgres"), doc = "```ignore")]
+//! # use diesel::prelude::*;
+//! # mod schema {
+//! #     diesel::table! {
+//! #         use diesel::sql_types::*;
+//! #         use crate::IntRange;
+//! #
+//! #         measurements (id) {
+//! #             id -> Integer,
+//! #             range -> IntRange,
+//! #             previous -> Nullable<IntRange>,
+//! #         }
+//! #     }
+//! # }
+//! use diesel::sql_types::{Integer, Record, Text};
+//! use diesel_as_wrap::record::IsComposite;
+//! use diesel_as_wrap::wrap_tuple;
+//!
+//! // CREATE TYPE int_range AS (low int, high int)
+//! #[derive(diesel::sql_types::SqlType, diesel::query_builder::QueryId)]
+//! #[diesel(postgres_type(name = "int_range"))]
+//! pub struct IntRange;
+//!
+//! impl IsComposite<(Integer, Integer)> for IntRange {}
+//!
+//! wrap_tuple! {
+//!     target = (i32, i32);
+//!     fields = (Integer, Integer);
+//!     sql_type = crate::IntRange;
+//!     pub mod range_wrap;
+//! }
+//!
+//! // the result of a function returning an anonymous record, only for reading
+//! wrap_tuple! {
+//!     target = (String, i32);
+//!     fields = (Text, Integer);
+//!     sql_type = Record<(Text, Integer)>;
+//!     pub mod label_record;
+//! }
+//!
+//! #[derive(Debug, Queryable, Selectable, Insertable)]
+//! #[diesel(table_name = schema::measurements)]
+//! #[diesel(check_for_backend(diesel::pg::Pg))]
+//! pub struct Measurement {
+//!     id: i32,
+//!     #[diesel(deserialize_as = range_wrap::As)]
+//!     #[diesel(serialize_as = range_wrap::As)]
+//!     range: (i32, i32),
+//!     #[diesel(deserialize_as = range_wrap::AsOption)]
+//!     #[diesel(serialize_as = range_wrap::AsOption)]
+//!     previous: Option<(i32, i32)>,
+//! }
+//! # fn main() {}
+//! ```
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use diesel::deserialize::{self, FromSql};
+use diesel::pg::{Pg, PgValue};
+use diesel::serialize::{self, Output, ToSql, WriteTuple};
+use diesel::sql_types::{Record, SqlType};
+
+///
+/// Marks a sql type as Postgres composite type whose attributes have the sql types `ST` (a tuple).\
+/// [`RecordTuple`] is only read and written as sql types implementing it, so a tuple can't be bound as `Text` or another
+/// scalar type by accident. It is implemented for anonymous records (`Record<ST>`),
+/// composite types declared with `#[diesel(postgres_type(name = "..."))]` implement it with the sql types of their attributes:
+/// ```
+/// # use diesel::sql_types::{Integer, Nullable, Text};
+/// # use diesel_as_wrap::record::IsComposite;
+/// // CREATE TYPE address AS (street text, zip_code int, country text)
+/// #[derive(diesel::sql_types::SqlType, diesel::query_builder::QueryId)]
+/// #[diesel(postgres_type(name = "address"))]
+/// pub struct Address;
+///
+/// impl IsComposite<(Text, Integer, Nullable<Text>)> for Address {}
+/// ```
+///
+pub trait IsComposite<ST>: SqlType {}
+
+impl<ST: 'static> IsComposite<ST> for Record<ST> {}
+
+///
+/// A tuple with the sql types of its elements (`ST`) that is read and written as Postgres composite value,
+/// as every sql type implementing [`IsComposite<ST>`](IsComposite).\
+/// It is `repr(transparent)`, so a tuple can be borrowed as RecordTuple with [`RecordTuple::from_ref`].
+///
+/// Other sql types are rejected:
+/// ```compile_fail
+/// # use diesel::serialize::ToSql;
+/// # use diesel::sql_types::{Integer, Text};
+/// # use diesel_as_wrap::record::RecordTuple;
+/// fn bind<T: ToSql<Text, diesel::pg::Pg>>() {}
+/// bind::<RecordTuple<(i32,), (Integer,)>>();
+/// // error: the trait bound `IsComposite<(Integer,)>` is not satisfied for `Text`
+/// ```
+///
+#[repr(transparent)]
+pub struct RecordTuple<T, ST>(pub T, PhantomData<ST>);
+
+impl<T, ST> RecordTuple<T, ST> {
+    /// Wraps the tuple.
+    pub fn new(tuple: T) -> Self {
+        RecordTuple(tuple, PhantomData)
+    }
+
+    /// Borrows the tuple as RecordTuple without moving or cloning it.
+    pub fn from_ref(tuple: &T) -> &Self {
+        // SAFETY: RecordTuple is repr(transparent) over the tuple.
+        unsafe { &*(tuple as *const T as *const Self) }
+    }
+}
+
+impl<T: fmt::Debug, ST> fmt::Debug for RecordTuple<T, ST> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RecordTuple").field(&self.0).finish()
+    }
+}
+
+impl<T, ST, CT> ToSql<CT, Pg> for RecordTuple<T, ST>
+where
+    CT: IsComposite<ST>,
+    ST: 'static,
+    T: WriteTuple<ST> + fmt::Debug,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        self.0.write_tuple(out)
+    }
+}
+
+impl<T, ST, CT> FromSql<CT, Pg> for RecordTuple<T, ST>
+where
+    CT: IsComposite<ST>,
+    ST: 'static,
+    T: FromSql<Record<ST>, Pg>,
+{
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        T::from_sql(bytes).map(RecordTuple::new)
+    }
+}
+
+///
+/// This macro creates the wrappers of [`wrap!`](crate::wrap) for a tuple target mapped to a Postgres composite type,
+/// the `to_sql` and `from_sql` sections are generated.\
+/// The `target` and `fields` sections have to come first, all other sections of [`wrap!`](crate::wrap) can follow in any order.
+/// Requires the `postgres` feature, see the [module documentation](crate::record) for an example.
+/// The syntax for using this macro is the following:\
+/// ```text
+/// wrap_tuple! {
+///     target = ($type$, ...);
+///     fields = ($sql_type_of_element$, ...);
+///     sql_type = $composite_sql_type$;
+///     $module_visability$ mod $name_of_wrappers_module$;
+///     [more sections of wrap!]
+/// }
+/// ```
+///
+/// The tuple is written in the binary record format of Postgres, `None` elements as `NULL` attributes:
+#[cfg_attr(feature = "postgres", doc = "```")]
+#[cfg_attr(not(feature = "postgres"), doc = "```ignore")]
+/// # use diesel::deserialize::FromSql;
+/// # use diesel::pg::{Pg, PgMetadataLookup, PgTypeMetadata, PgValue};
+/// # use diesel::query_builder::bind_collector::RawBytesBindCollector;
+/// # use diesel::query_builder::BindCollector;
+/// # use diesel::serialize::ToSql;
+/// # use diesel::sql_types::HasSqlType;
+/// # struct Types;
+/// # impl PgMetadataLookup for Types {
+/// #     fn lookup_type(&mut self, _: &str, _: Option<&str>) -> PgTypeMetadata {
+/// #         PgTypeMetadata::new(100_000, 100_001)
+/// #     }
+/// # }
+/// # fn encode<ST, T: ToSql<ST, Pg>>(value: &T) -> Option<Vec<u8>> where Pg: HasSqlType<ST> {
+/// #     let mut collector = RawBytesBindCollector::<Pg>::new();
+/// #     collector.push_bound_value::<ST, T>(value, &mut Types).unwrap();
+/// #     collector.binds.pop().unwrap()
+/// # }
+/// # fn decode<ST, T: FromSql<ST, Pg>>(bytes: Option<&[u8]>) -> diesel::deserialize::Result<T> where Pg: HasSqlType<ST> {
+/// #     let oid = std::num::NonZeroU32::new(<Pg as HasSqlType<ST>>::metadata(&mut Types).oid().unwrap()).unwrap();
+/// #     T::from_nullable_sql(bytes.map(|bytes| PgValue::new(bytes, &oid)))
+/// # }
+/// use diesel::sql_types::{Integer, Nullable, Text};
+/// use diesel_as_wrap::record::IsComposite;
+/// use diesel_as_wrap::wrap_tuple;
+///
+/// // CREATE TYPE labeled AS (label text, value int)
+/// #[derive(diesel::sql_types::SqlType, diesel::query_builder::QueryId)]
+/// #[diesel(postgres_type(name = "labeled"))]
+/// pub struct Labeled;
+///
+/// impl IsComposite<(Nullable<Text>, Integer)> for Labeled {}
+///
+/// wrap_tuple! {
+///     target = (Option<String>, i32);
+///     fields = (Nullable<Text>, Integer);
+///     sql_type = crate::Labeled;
+///     pub mod labeled_wrap;
+/// }
+///
+/// # fn main() {
+/// let labeled = labeled_wrap::As((Some("answer".to_string()), 42));
+/// let bytes = encode::<Labeled, _>(&labeled).unwrap();
+/// // 2 attributes, a text of 6 bytes and an int of 4 bytes
+/// let expected = [&[0, 0, 0, 2, 0, 0, 0, 25, 0, 0, 0, 6][..], b"answer", &[0, 0, 0, 23, 0, 0, 0, 4, 0, 0, 0, 42]].concat();
+/// assert_eq!(bytes, expected);
+/// let read: labeled_wrap::As = decode::<Labeled, _>(Some(&bytes)).unwrap();
+/// assert_eq!(read.0, labeled.0);
+///
+/// let unlabeled = labeled_wrap::As((None, -1));
+/// let bytes = encode::<Labeled, _>(&unlabeled).unwrap();
+/// assert_eq!(bytes, [0, 0, 0, 2, 0, 0, 0, 25, 255, 255, 255, 255, 0, 0, 0, 23, 0, 0, 0, 4, 255, 255, 255, 255]);
+/// let read: labeled_wrap::As = decode::<Labeled, _>(Some(&bytes)).unwrap();
+/// assert_eq!(read.0, (None, -1));
+///
+/// let read: labeled_wrap::AsOption = decode::<Nullable<Labeled>, _>(None).unwrap();
+/// assert!(read.0.is_none());
+/// // a record with a missing attribute is an error instead of a panic
+/// assert!(decode::<Labeled, labeled_wrap::As>(Some(&bytes[..12])).is_err());
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! wrap_tuple {
+    (target = $target:ty; fields = $fields:ty; $($rest:tt)*) => {
+        $crate::wrap! {
+            target = $target;
+            $($rest)*
+            fn to_sql<$crate::record::RecordTuple<$target, $fields>>(self, out) {
+                $crate::record::RecordTuple::<$target, $fields>::from_ref(&self.0).to_sql(out)
+            }
+            fn from_sql<$crate::record::RecordTuple<$target, $fields>>(bytes) {
+                <$crate::record::RecordTuple<$target, $fields>>::from_sql(bytes).map(|tuple| As(tuple.0))
+            }
+        }
+    };
+}
+
+///
+/// This macro creates the wrappers of [`wrap!`](crate::wrap) for a struct mapped to a Postgres composite type,
+/// the `to_sql` and `from_sql` sections are generated.\
+/// The `fields` section lists the fields of the struct in the order of the composite type's attributes,
+/// each with its Rust type and sql type. All fields of the struct have to be listed, as it is constructed from them when reading.
+/// Writing borrows the fields, so the struct doesn't need to implement `Clone`.
+/// The fields have to be visible to the module invoking the macro and the `sql_type` has to be a composite type
+/// declared with `#[diesel(postgres_type(name = "..."))]` implementing [`IsComposite`] with the sql types of the fields,
+/// `Record<...>` can be used for only reading anonymous records.\
+/// The `target` and `fields` sections have to come first, all other sections of [`wrap!`](crate::wrap) can follow in any order.
+/// Requires the `postgres` feature, the `backend` section is generated as well.
+/// The syntax for using this macro is the following:\
+/// ```text
+/// wrap_record! {
+///     target = $struct$;
+///     fields = { $field$: $type$ => $sql_type_of_field$, ... };
+///     sql_type = $composite_sql_type$;
+///     $module_visability$ mod $name_of_wrappers_module$;
+///     [more sections of wrap!]
+/// }
+/// ```
+///
+/// # Example:
+#[cfg_attr(feature = "postgres", doc = "```")]
+#[cfg_attr(not(feature = "postgres"), doc = "```ignore")]
+/// # use diesel::prelude::*;
+/// # mod schema {
+/// #     diesel::table! {
+/// #         use diesel::sql_types::*;
+/// #         use crate::Address;
+/// #
+/// #         customers (id) {
+/// #             id -> Integer,
+/// #             address -> Address,
+/// #             billing_address -> Nullable<Address>,
+/// #         }
+/// #     }
+/// # }
+/// use diesel::sql_types::{Integer, Nullable, Text};
+/// use diesel_as_wrap::record::IsComposite;
+/// use diesel_as_wrap::wrap_record;
+///
+/// // CREATE TYPE address AS (street text, zip_code int, country text)
+/// #[derive(diesel::sql_types::SqlType, diesel::query_builder::QueryId)]
+/// #[diesel(postgres_type(name = "address"))]
+/// pub struct Address;
+///
+/// impl IsComposite<(Text, Integer, Nullable<Text>)> for Address {}
+///
+/// #[derive(Debug, PartialEq)]
+/// pub struct PostalAddress {
+///     street: String,
+///     zip_code: i32,
+///     country: Option<String>,
+/// }
+///
+/// wrap_record! {
+///     target = crate::PostalAddress;
+///     fields = { street: String => Text, zip_code: i32 => Integer, country: Option<String> => Nullable<Text> };
+///     sql_type = crate::Address;
+///     pub mod address_wrap;
+/// }
+///
+/// #[derive(Debug, Queryable, Selectable, Insertable)]
+/// #[diesel(table_name = schema::customers)]
+/// #[diesel(check_for_backend(diesel::pg::Pg))]
+/// pub struct Customer {
+///     id: i32,
+///     #[diesel(deserialize_as = address_wrap::As)]
+///     #[diesel(serialize_as = address_wrap::As)]
+///     address: PostalAddress,
+///     #[diesel(deserialize_as = address_wrap::AsOption)]
+///     #[diesel(serialize_as = address_wrap::AsOption)]
+///     billing_address: Option<PostalAddress>,
+/// }
+/// # fn main() {}
+/// ```
+///
+/// The struct is written in the binary record format of Postgres, `None` fields as `NULL` attributes,
+/// and so are its arrays:
+#[cfg_attr(feature = "postgres", doc = "```")]
+#[cfg_attr(not(feature = "postgres"), doc = "```ignore")]
+/// # use diesel::deserialize::FromSql;
+/// # use diesel::pg::{Pg, PgMetadataLookup, PgTypeMetadata, PgValue};
+/// # use diesel::query_builder::bind_collector::RawBytesBindCollector;
+/// # use diesel::query_builder::BindCollector;
+/// # use diesel::serialize::ToSql;
+/// # use diesel::sql_types::{Array, HasSqlType};
+/// # struct Types;
+/// # impl PgMetadataLookup for Types {
+/// #     fn lookup_type(&mut self, _: &str, _: Option<&str>) -> PgTypeMetadata {
+/// #         PgTypeMetadata::new(100_000, 100_001)
+/// #     }
+/// # }
+/// # fn encode<ST, T: ToSql<ST, Pg>>(value: &T) -> Option<Vec<u8>> where Pg: HasSqlType<ST> {
+/// #     let mut collector = RawBytesBindCollector::<Pg>::new();
+/// #     collector.push_bound_value::<ST, T>(value, &mut Types).unwrap();
+/// #     collector.binds.pop().unwrap()
+/// # }
+/// # fn decode<ST, T: FromSql<ST, Pg>>(bytes: Option<&[u8]>) -> diesel::deserialize::Result<T> where Pg: HasSqlType<ST> {
+/// #     let oid = std::num::NonZeroU32::new(<Pg as HasSqlType<ST>>::metadata(&mut Types).oid().unwrap()).unwrap();
+/// #     T::from_nullable_sql(bytes.map(|bytes| PgValue::new(bytes, &oid)))
+/// # }
+/// use diesel::sql_types::{Integer, Nullable, Text};
+/// use diesel_as_wrap::record::IsComposite;
+/// use diesel_as_wrap::wrap_record;
+///
+/// #[derive(diesel::sql_types::SqlType, diesel::query_builder::QueryId)]
+/// #[diesel(postgres_type(name = "address"))]
+/// pub struct Address;
+///
+/// impl IsComposite<(Text, Integer, Nullable<Text>)> for Address {}
+///
+/// #[derive(Debug, Clone, PartialEq)]
+/// pub struct PostalAddress {
+///     street: String,
+///     zip_code: i32,
+///     country: Option<String>,
+/// }
+///
+/// wrap_record! {
+///     target = crate::PostalAddress;
+///     fields = { street: String => Text, zip_code: i32 => Integer, country: Option<String> => Nullable<Text> };
+///     sql_type = crate::Address;
+///     pub mod address_wrap;
+/// }
+///
+/// # fn main() {
+/// let address = PostalAddress { street: "Main St".to_string(), zip_code: 12345, country: None };
+/// let bytes = encode::<Address, _>(address_wrap::As::from_ref(&address)).unwrap();
+/// let expected = [
+///     &[0, 0, 0, 3, 0, 0, 0, 25, 0, 0, 0, 7][..],
+///     b"Main St",
+///     &[0, 0, 0, 23, 0, 0, 0, 4, 0, 0, 0x30, 0x39],
+///     &[0, 0, 0, 25, 255, 255, 255, 255],
+/// ]
+/// .concat();
+/// assert_eq!(bytes, expected);
+/// let read: address_wrap::As = decode::<Address, _>(Some(&bytes)).unwrap();
+/// assert_eq!(read.0, address);
+/// let read: address_wrap::AsOption = decode::<Nullable<Address>, _>(None).unwrap();
+/// assert!(read.0.is_none());
+///
+/// let addresses = vec![Some(address), None];
+/// let mut bytes = encode::<Array<Nullable<Address>>, _>(&address_wrap::AsVecOption::from(addresses.clone())).unwrap();
+/// assert_eq!(bytes[8..12], 100_000u32.to_be_bytes()); // the oid of `address` looked up by name
+/// assert_eq!(bytes[bytes.len() - 4..], [255, 255, 255, 255]);
+/// // Postgres sends arrays containing NULL with the has-null flag
+/// bytes[7] = 1;
+/// let read: address_wrap::AsVecOption = decode::<Array<Nullable<Address>>, _>(Some(&bytes)).unwrap();
+/// assert_eq!(Vec::<Option<PostalAddress>>::from(read), addresses);
+///
+/// // a record of other attributes is an error
+/// let pair = [0, 0, 0, 1, 0, 0, 0, 23, 0, 0, 0, 4, 0, 0, 0, 1];
+/// assert!(decode::<Address, address_wrap::As>(Some(&pair)).is_err());
+/// # }
+/// ```
+///
+/// Arrays of composite values use the array wrappers of the module, the element type needs no extra impls:
+#[cfg_attr(feature = "postgres", doc = "```")]
+#[cfg_attr(not(feature = "postgres"), doc = "```ignore")]
+/// # use diesel::prelude::*;
+/// # mod schema {
+/// #     diesel::table! {
+/// #         use diesel::sql_types::*;
+/// #         use crate::LineItemType;
+/// #
+/// #         orders (id) {
+/// #             id -> Integer,
+/// #             items -> Array<LineItemType>,
+/// #             returned -> Nullable<Array<Nullable<LineItemType>>>,
+/// #         }
+/// #     }
+/// # }
+/// use diesel::sql_types::{Integer, Nullable, Text};
+/// use diesel_as_wrap::record::IsComposite;
+/// use diesel_as_wrap::wrap_record;
+///
+/// // CREATE TYPE line_item AS (sku text, quantity int, note text)
+/// #[derive(diesel::sql_types::SqlType, diesel::query_builder::QueryId)]
+/// #[diesel(postgres_type(name = "line_item"))]
+/// pub struct LineItemType;
+///
+/// impl IsComposite<(Text, Integer, Nullable<Text>)> for LineItemType {}
+///
+/// #[derive(Debug, Clone, PartialEq)]
+/// pub struct LineItem {
+///     sku: String,
+///     quantity: i32,
+///     note: Option<String>,
+/// }
+///
+/// wrap_record! {
+///     target = crate::LineItem;
+///     fields = { sku: String => Text, quantity: i32 => Integer, note: Option<String> => Nullable<Text> };
+///     sql_type = crate::LineItemType;
+///     pub mod line_item_wrap;
+/// }
+///
+/// // CREATE TABLE orders (id int PRIMARY KEY, items line_item[] NOT NULL, returned line_item[])
+/// #[derive(Debug, Queryable, Selectable, Insertable)]
+/// #[diesel(table_name = schema::orders)]
+/// #[diesel(check_for_backend(diesel::pg::Pg))]
+/// pub struct Order {
+///     id: i32,
+///     #[diesel(deserialize_as = line_item_wrap::AsVec)]
+///     #[diesel(serialize_as = line_item_wrap::AsVec)]
+///     items: Vec<LineItem>,
+///     #[diesel(deserialize_as = line_item_wrap::AsOptionVecOption)]
+///     #[diesel(serialize_as = line_item_wrap::AsOptionVecOption)]
+///     returned: Option<Vec<Option<LineItem>>>,
+/// }
+/// # fn main() {}
+/// ```
+///
+#[macro_export]
+macro_rules! wrap_record {
+    (target = $target:ty; fields = { $($field:ident: $type:ty => $field_sql_type:ty),+ $(,)? }; $($rest:tt)*) => {
+        $crate::wrap! {
+            target = $target;
+            backend = diesel::pg::Pg;
+            $($rest)*
+            fn to_sql<$crate::record::RecordTuple<($($type,)+), ($($field_sql_type,)+)>>(self, out) {
+                let fields = ($(&self.0.$field,)+);
+                diesel::serialize::WriteTuple::<($($field_sql_type,)+)>::write_tuple(&fields, out)
+            }
+            fn from_sql<$crate::record::RecordTuple<($($type,)+), ($($field_sql_type,)+)>>(bytes) {
+                let ($($field,)+) = <$crate::record::RecordTuple<($($type,)+), ($($field_sql_type,)+)>>::from_sql(bytes)?.0;
+                Ok(As(Target { $($field),+ }))
+            }
+        }
+    };
+}
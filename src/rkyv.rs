@@ -0,0 +1,73 @@
+//! Wrappers storing [`rkyv`](::rkyv) archives in `Binary` columns.
+//!
+//! [`wrap_rkyv!`](crate::wrap_rkyv) creates the wrappers of [`wrap!`](crate::wrap) for a target implementing
+//! rkyv's `Archive`, `Serialize` and `Deserialize`.
+//! Values are archived into a reused buffer (see [`ToSqlBuffered`](crate::ToSqlBuffered)) and validated with `bytecheck` on read,
+//! so corrupted or foreign bytes produce a deserialization error instead of undefined behavior.
+//! It suits caches where the serde overhead of json dominates, the bytes are only readable as long as the archived layout stays the same.
+//!
+//! # Example:
+#![cfg_attr(feature = "sqlite", doc = "```")]
+#![cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+//! use diesel::prelude::*;
+//! use diesel::sql_types::Binary;
+//! use diesel_as_wrap::wrap_rkyv;
+//!
+//! #[derive(Debug, PartialEq, rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)]
+//! pub struct CacheEntry {
+//!     key: String,
+//!     hits: u64,
+//! }
+//!
+//! wrap_rkyv! {
+//!     target = crate::CacheEntry;
+//!     pub mod entry_rkyv;
+//! }
+//!
+//! # fn main() {
+//! let mut conn = SqliteConnection::establish(":memory:").unwrap();
+//! let entry = entry_rkyv::As(CacheEntry { key: "home".to_string(), hits: 3 });
+//! let loaded: entry_rkyv::As = diesel::select(entry.into_sql::<Binary>()).get_result(&mut conn).unwrap();
+//! assert_eq!(loaded.0, CacheEntry { key: "home".to_string(), hits: 3 });
+//!
+//! let garbage = diesel::select(vec![1u8, 2, 3].into_sql::<Binary>()).get_result::<entry_rkyv::As>(&mut conn);
+//! assert!(garbage.is_err());
+//! # }
+//! ```
+
+///
+/// This macro creates the wrappers of [`wrap!`](crate::wrap) for a target archived with rkyv in a `Binary` column,
+/// the `sql_type`, `to_sql` and `from_sql` sections are generated.\
+/// The `target` section has to come first, all other sections of [`wrap!`](crate::wrap) can follow in any order.
+/// Requires the `rkyv` feature, see the [module documentation](crate::rkyv) for an example.
+/// The syntax for using this macro is the following:\
+/// ```text
+/// wrap_rkyv! {
+///     target = $type_to_wrap$;
+///     $module_visability$ mod $name_of_wrappers_module$;
+///     [more sections of wrap!]
+/// }
+/// ```
+///
+#[macro_export]
+macro_rules! wrap_rkyv {
+    (target = $target:ty; $($rest:tt)*) => {
+        $crate::wrap! {
+            target = $target;
+            sql_type = Binary;
+            $($rest)*
+            fn buffered_sql<Vec<u8>>(self, buffer) {
+                $crate::__private::rkyv::api::high::to_bytes_in::<_, $crate::__private::rkyv::rancor::Error>(&self.0, buffer)?;
+                Ok(())
+            }
+            fn from_sql<&[u8]>(bytes) {
+                // archives are only accessible at their alignment, which bytes from the database don't guarantee
+                let mut aligned = $crate::__private::rkyv::util::AlignedVec::<16>::with_capacity(bytes.len());
+                aligned.extend_from_slice(bytes);
+                $crate::__private::rkyv::from_bytes::<$target, $crate::__private::rkyv::rancor::Error>(&aligned)
+                    .map(As)
+                    .map_err(Into::into)
+            }
+        }
+    };
+}
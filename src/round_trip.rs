@@ -0,0 +1,123 @@
+use std::fmt;
+
+use diesel::backend::Backend;
+use diesel::deserialize::{self, FromSql};
+use diesel::serialize::{self, Output};
+use diesel::sql_types::HasSqlType;
+
+///
+/// Backends the `round_trip_check;` section of [`wrap!`](crate::wrap) reads written values back on.\
+/// A backend reading them back needs a raw value built from the written bytes, diesel only allows that for
+/// postgres and mysql behind its `i-implement-a-third-party-backend-and-opt-into-breaking-changes` feature,
+/// which the `round_trip_check` feature enables.
+/// Without the feature and on sqlite (whose raw values point into the database connection) the check is skipped.
+/// Implemented for the backends enabled by the `postgres`, `mysql` and `sqlite` features.
+///
+pub trait RoundTrip: Backend {
+    /// Writes the value with `to_sql` (the one without the check) and reads the bytes back,
+    /// `None` if the backend can't read them.
+    #[doc(hidden)]
+    fn read_back<ST, T>(value: &T, to_sql: ToSqlFn<T, Self>, lookup: &mut Self::MetadataLookup) -> Option<deserialize::Result<T>>
+    where
+        Self: HasSqlType<ST>,
+        T: FromSql<ST, Self> + fmt::Debug;
+}
+
+/// The `to_sql` of a wrapper with `round_trip_check;`, without the check.
+#[doc(hidden)]
+pub type ToSqlFn<T, DB> = for<'b> fn(&'b T, &mut Output<'b, '_, DB>) -> serialize::Result;
+
+/// Binds a value with its unchecked `to_sql`, so writing it again for the check doesn't check again.
+#[cfg(all(feature = "round_trip_check", any(feature = "postgres", feature = "mysql")))]
+struct Unchecked<'a, T, DB: Backend>(&'a T, ToSqlFn<T, DB>);
+
+#[cfg(all(feature = "round_trip_check", any(feature = "postgres", feature = "mysql")))]
+impl<T: fmt::Debug, DB: Backend> fmt::Debug for Unchecked<'_, T, DB> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(all(feature = "round_trip_check", any(feature = "postgres", feature = "mysql")))]
+impl<ST, DB, T> diesel::serialize::ToSql<ST, DB> for Unchecked<'_, T, DB>
+where
+    DB: Backend,
+    T: fmt::Debug,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+        (self.1)(self.0, out)
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl RoundTrip for diesel::pg::Pg {
+    #[cfg(feature = "round_trip_check")]
+    fn read_back<ST, T>(value: &T, to_sql: ToSqlFn<T, Self>, lookup: &mut Self::MetadataLookup) -> Option<deserialize::Result<T>>
+    where
+        Self: HasSqlType<ST>,
+        T: FromSql<ST, Self> + fmt::Debug,
+    {
+        use diesel::query_builder::bind_collector::RawBytesBindCollector;
+        use diesel::query_builder::BindCollector;
+
+        let mut collector = RawBytesBindCollector::<Self>::new();
+        if let Err(e) = collector.push_bound_value::<ST, _>(&Unchecked(value, to_sql), lookup) {
+            return Some(Err(e.into()));
+        }
+        // Types looked up by name (enums, composites, ...) have no oid without a connection,
+        // their `from_sql` doesn't ask for it.
+        let oid = collector.metadata[0].oid().ok().and_then(std::num::NonZeroU32::new).unwrap_or(std::num::NonZeroU32::MAX);
+        let bytes = collector.binds[0].as_deref().map(|bytes| diesel::pg::PgValue::new(bytes, &oid));
+        Some(T::from_nullable_sql(bytes))
+    }
+
+    #[cfg(not(feature = "round_trip_check"))]
+    fn read_back<ST, T>(_value: &T, _to_sql: ToSqlFn<T, Self>, _lookup: &mut Self::MetadataLookup) -> Option<deserialize::Result<T>>
+    where
+        Self: HasSqlType<ST>,
+        T: FromSql<ST, Self> + fmt::Debug,
+    {
+        None
+    }
+}
+
+#[cfg(feature = "mysql")]
+impl RoundTrip for diesel::mysql::Mysql {
+    #[cfg(feature = "round_trip_check")]
+    fn read_back<ST, T>(value: &T, to_sql: ToSqlFn<T, Self>, lookup: &mut Self::MetadataLookup) -> Option<deserialize::Result<T>>
+    where
+        Self: HasSqlType<ST>,
+        T: FromSql<ST, Self> + fmt::Debug,
+    {
+        use diesel::query_builder::bind_collector::RawBytesBindCollector;
+        use diesel::query_builder::BindCollector;
+
+        let mut collector = RawBytesBindCollector::<Self>::new();
+        if let Err(e) = collector.push_bound_value::<ST, _>(&Unchecked(value, to_sql), lookup) {
+            return Some(Err(e.into()));
+        }
+        let sql_type = collector.metadata[0];
+        let bytes = collector.binds[0].as_deref().map(|bytes| diesel::mysql::MysqlValue::new(bytes, sql_type));
+        Some(T::from_nullable_sql(bytes))
+    }
+
+    #[cfg(not(feature = "round_trip_check"))]
+    fn read_back<ST, T>(_value: &T, _to_sql: ToSqlFn<T, Self>, _lookup: &mut Self::MetadataLookup) -> Option<deserialize::Result<T>>
+    where
+        Self: HasSqlType<ST>,
+        T: FromSql<ST, Self> + fmt::Debug,
+    {
+        None
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl RoundTrip for diesel::sqlite::Sqlite {
+    fn read_back<ST, T>(_value: &T, _to_sql: ToSqlFn<T, Self>, _lookup: &mut Self::MetadataLookup) -> Option<deserialize::Result<T>>
+    where
+        Self: HasSqlType<ST>,
+        T: FromSql<ST, Self> + fmt::Debug,
+    {
+        None
+    }
+}
@@ -0,0 +1,149 @@
+//! Wrappers for [`rust_decimal::Decimal`](::rust_decimal::Decimal).
+//!
+//! [`decimal`] maps a `Decimal` to [`PortableDecimal`](crate::bigdecimal::PortableDecimal), the decimal sql type of the `bigdecimal` feature:
+//! native `numeric` on Postgres and MySQL and the canonical string in a `TEXT` column on SQLite.
+//! Values are converted losslessly through `BigDecimal`, including their scale.
+//! `numeric` values that don't fit into a `Decimal` (more than 28 decimal places or a mantissa beyond 96 bits)
+//! produce a deserialization error instead of being rounded.
+//!
+//! # Example:
+#![cfg_attr(feature = "sqlite", doc = "```")]
+#![cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+//! # use diesel::prelude::*;
+//! # mod schema {
+//! #     diesel::table! {
+//! #         use diesel::sql_types::*;
+//! #         use diesel_as_wrap::bigdecimal::PortableDecimal;
+//! #
+//! #         payments (id) {
+//! #             id -> Integer,
+//! #             amount -> PortableDecimal,
+//! #             fee -> Nullable<PortableDecimal>,
+//! #         }
+//! #     }
+//! # }
+//! use std::str::FromStr;
+//! use diesel_as_wrap::rust_decimal::decimal;
+//! use rust_decimal::Decimal;
+//!
+//! #[derive(Debug, Clone, PartialEq, Queryable, Selectable, Insertable)]
+//! #[diesel(table_name = schema::payments)]
+//! pub struct Payment {
+//!     id: i32,
+//!     #[diesel(deserialize_as = decimal::As)]
+//!     #[diesel(serialize_as = decimal::As)]
+//!     amount: Decimal,
+//!     #[diesel(deserialize_as = decimal::AsOption)]
+//!     #[diesel(serialize_as = decimal::AsOption)]
+//!     fee: Option<Decimal>,
+//! }
+//!
+//! # fn main() {
+//! let mut conn = SqliteConnection::establish(":memory:").unwrap();
+//! diesel::sql_query("CREATE TABLE payments (id INTEGER PRIMARY KEY, amount TEXT NOT NULL, fee TEXT)")
+//!     .execute(&mut conn)
+//!     .unwrap();
+//!
+//! let payments = vec![
+//!     Payment { id: 1, amount: Decimal::from_str("19.90").unwrap(), fee: Some(Decimal::from_str("0.35").unwrap()) },
+//!     Payment { id: 2, amount: Decimal::MAX, fee: None },
+//! ];
+//! diesel::insert_into(schema::payments::table).values(payments.clone()).execute(&mut conn).unwrap();
+//!
+//! let loaded: Vec<Payment> = schema::payments::table.select(Payment::as_select()).load(&mut conn).unwrap();
+//! assert_eq!(loaded, payments);
+//! assert_eq!(loaded[0].amount.scale(), 2);
+//!
+//! diesel::sql_query("UPDATE payments SET amount = '0.00000000000000000000000000001' WHERE id = 2").execute(&mut conn).unwrap();
+//! let error = schema::payments::table.select(Payment::as_select()).load(&mut conn).unwrap_err();
+//! assert!(error.to_string().ends_with("1E-29 doesn't fit into a `Decimal`"));
+//! # }
+//! ```
+
+#[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+use ::bigdecimal::BigDecimal;
+#[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+use ::rust_decimal::Decimal;
+#[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+use diesel::deserialize::{self, FromSql};
+#[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+use diesel::serialize::{self, Output, ToSql};
+
+#[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+use crate::bigdecimal::PortableDecimal;
+
+#[cfg(any(feature = "postgres", feature = "mysql"))]
+fn to_big_decimal(decimal: &Decimal) -> BigDecimal {
+    BigDecimal::new(decimal.mantissa().into(), i64::from(decimal.scale()))
+}
+
+#[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+fn from_big_decimal(decimal: BigDecimal) -> deserialize::Result<Decimal> {
+    let out_of_range = || format!("{decimal} doesn't fit into a `Decimal`").into();
+    let (mantissa, exponent) = decimal.as_bigint_and_exponent();
+    // a negative exponent (`1E+20`) has to be moved into the mantissa, the scale of a `Decimal` can't be negative
+    let (mantissa, scale) = match u32::try_from(exponent) {
+        Ok(scale) => (mantissa, scale),
+        // the mantissa of a `Decimal` has at most 29 digits
+        Err(_) if exponent >= -29 => (mantissa * ::bigdecimal::num_bigint::BigInt::from(10).pow(exponent.unsigned_abs() as u32), 0),
+        Err(_) => return Err(out_of_range()),
+    };
+    i128::try_from(&mantissa)
+        .ok()
+        .and_then(|mantissa| Decimal::try_from_i128_with_scale(mantissa, scale).ok())
+        .ok_or_else(out_of_range)
+}
+
+#[cfg(any(feature = "postgres", feature = "mysql"))]
+macro_rules! native_decimal {
+    ($($backend:ty),+) => {
+        $(
+            impl ToSql<PortableDecimal, $backend> for Decimal {
+                fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, $backend>) -> serialize::Result {
+                    ToSql::<PortableDecimal, $backend>::to_sql(&to_big_decimal(self), &mut out.reborrow())
+                }
+            }
+
+            impl FromSql<PortableDecimal, $backend> for Decimal {
+                fn from_sql(bytes: <$backend as diesel::backend::Backend>::RawValue<'_>) -> deserialize::Result<Self> {
+                    from_big_decimal(<BigDecimal as FromSql<PortableDecimal, $backend>>::from_sql(bytes)?)
+                }
+            }
+        )+
+    };
+}
+
+#[cfg(feature = "postgres")]
+native_decimal!(diesel::pg::Pg);
+
+#[cfg(feature = "mysql")]
+native_decimal!(diesel::mysql::Mysql);
+
+#[cfg(feature = "sqlite")]
+impl ToSql<PortableDecimal, diesel::sqlite::Sqlite> for Decimal {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, diesel::sqlite::Sqlite>) -> serialize::Result {
+        out.set_value(self.to_string());
+        Ok(serialize::IsNull::No)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl FromSql<PortableDecimal, diesel::sqlite::Sqlite> for Decimal {
+    fn from_sql(bytes: diesel::sqlite::SqliteValue<'_, '_, '_>) -> deserialize::Result<Self> {
+        // parsed as `BigDecimal`, so the text written for a `BigDecimal` (`1E+30`) is read the same way
+        from_big_decimal(<BigDecimal as FromSql<PortableDecimal, diesel::sqlite::Sqlite>>::from_sql(bytes)?)
+    }
+}
+
+crate::wrap! {
+    target = ::rust_decimal::Decimal;
+    sql_type = crate::bigdecimal::PortableDecimal;
+    pub mod decimal;
+    fn to_sql<::rust_decimal::Decimal>(self, out) {
+        let value: &::rust_decimal::Decimal = &self.0;
+        value.to_sql(out)
+    }
+    fn from_sql<::rust_decimal::Decimal>(bytes) {
+        <::rust_decimal::Decimal>::from_sql(bytes).map(As)
+    }
+}
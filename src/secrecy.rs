@@ -0,0 +1,88 @@
+//! Wrappers for the secret types of [`secrecy`](::secrecy).
+//!
+//! [`text`] stores a [`SecretString`](::secrecy::SecretString) inside a `Text` column
+//! and [`binary`] a [`SecretSlice<u8>`](::secrecy::SecretSlice) (the former `SecretVec<u8>`) inside a `Binary` column.
+//! Both use `debug = redacted`, so bound values show up as `As(<redacted>)` in [`debug_query`](diesel::debug_query)
+//! and query logs instead of exposing the secret through the `Debug` implementation of the wrapper.
+//!
+//! Reading copies the value straight from the raw database value into the secret without an intermediate `String` or `Vec`.
+//! The buffers of the backends themselves are not zeroized, the secret is only protected once it reached the application.
+//!
+//! # Example:
+#![cfg_attr(feature = "sqlite", doc = "```")]
+#![cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+//! # use diesel::prelude::*;
+//! # mod schema {
+//! #     diesel::table! {
+//! #         credentials (id) {
+//! #             id -> Integer,
+//! #             api_key -> Text,
+//! #             signing_key -> Nullable<Binary>,
+//! #         }
+//! #     }
+//! # }
+//! use secrecy::{ExposeSecret, SecretSlice, SecretString};
+//! use diesel_as_wrap::secrecy::{binary, text};
+//!
+//! #[derive(Debug, Queryable, Selectable, Insertable)]
+//! #[diesel(table_name = schema::credentials)]
+//! pub struct Credential {
+//!     id: i32,
+//!     #[diesel(deserialize_as = text::As)]
+//!     #[diesel(serialize_as = text::As)]
+//!     api_key: SecretString,
+//!     #[diesel(deserialize_as = binary::AsOption)]
+//!     #[diesel(serialize_as = binary::AsOption)]
+//!     signing_key: Option<SecretSlice<u8>>,
+//! }
+//!
+//! # fn main() {
+//! let mut conn = SqliteConnection::establish(":memory:").unwrap();
+//! diesel::sql_query("CREATE TABLE credentials (id INTEGER PRIMARY KEY, api_key TEXT NOT NULL, signing_key BLOB)")
+//!     .execute(&mut conn)
+//!     .unwrap();
+//!
+//! let credential = Credential {
+//!     id: 1,
+//!     api_key: SecretString::from("sk-live-1234"),
+//!     signing_key: Some(SecretSlice::from(vec![0xde, 0xad, 0xbe, 0xef])),
+//! };
+//! let query = diesel::insert_into(schema::credentials::table).values(credential);
+//! let logged = diesel::debug_query::<diesel::sqlite::Sqlite, _>(&query).to_string();
+//! assert!(logged.contains("As(<redacted>)"));
+//! assert!(!logged.contains("sk-live-1234"));
+//! query.execute(&mut conn).unwrap();
+//!
+//! let loaded: Credential = schema::credentials::table.select(Credential::as_select()).first(&mut conn).unwrap();
+//! assert_eq!(loaded.api_key.expose_secret(), "sk-live-1234");
+//! assert_eq!(loaded.signing_key.unwrap().expose_secret(), [0xde, 0xad, 0xbe, 0xef]);
+//! # }
+//! ```
+
+crate::wrap! {
+    target = ::secrecy::SecretString;
+    sql_type = Text;
+    pub mod text;
+    debug = redacted;
+    fn to_sql<str>(self, out) {
+        let text: &str = ::secrecy::ExposeSecret::expose_secret(&self.0);
+        text.to_sql(out)
+    }
+    fn from_sql<&str>(bytes) {
+        Ok(As(::secrecy::SecretString::from(bytes)))
+    }
+}
+
+crate::wrap! {
+    target = ::secrecy::SecretSlice<u8>;
+    sql_type = Binary;
+    pub mod binary;
+    debug = redacted;
+    fn to_sql<[u8]>(self, out) {
+        let bytes: &[u8] = ::secrecy::ExposeSecret::expose_secret(&self.0);
+        bytes.to_sql(out)
+    }
+    fn from_sql<&[u8]>(bytes) {
+        Ok(As(::secrecy::SecretSlice::from(bytes.to_vec())))
+    }
+}
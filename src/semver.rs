@@ -0,0 +1,156 @@
+//! Wrappers for [`semver::Version`](::semver::Version).
+//!
+//! [`text`] stores a version inside a `Text` column in its usual form (`1.2.3-alpha.10+build.5`),
+//! text that isn't a semantic version produces a deserialization error.
+//! Sorting the column as text doesn't sort by version (`1.10.0` sorts before `1.2.0`), use the sortable encoding for that.
+//!
+//! [`sortable_text`] stores a version inside a `Text` column in an encoding that sorts like the version itself,
+//! so `ORDER BY version` and range comparisons in sql match semantic version precedence:
+//! - major, minor and patch are zero padded to 20 digits and separated by `.`.
+//! - a release is followed by `~`, a pre-release by `-` and its identifiers separated by `,`
+//!   (numeric identifiers as `0`, their two digit length and the number, alphanumeric ones as `1` and the identifier).
+//!   Numeric identifiers of 100 or more digits don't fit the length and produce a serialization error.
+//! - build metadata is appended unchanged after a `+`.
+//!
+//! For example `1.2.3-alpha.10+build.5` is stored as `00000000000000000001.00000000000000000002.00000000000000000003-1alpha,00210+build.5`.
+//! Values that aren't in this encoding produce a deserialization error.
+//!
+//! # Example:
+#![cfg_attr(feature = "sqlite", doc = "```")]
+#![cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+//! use diesel::prelude::*;
+//! use diesel::sql_types::Text;
+//! use diesel_as_wrap::semver::{sortable_text, text};
+//! use semver::Version;
+//!
+//! diesel::table! {
+//!     releases (version) {
+//!         version -> Text,
+//!     }
+//! }
+//!
+//! let mut conn = SqliteConnection::establish(":memory:").unwrap();
+//! diesel::sql_query("CREATE TABLE releases (version TEXT PRIMARY KEY)").execute(&mut conn).unwrap();
+//!
+//! let mut versions: Vec<Version> = ["1.10.0", "1.2.0", "1.2.0-rc.1", "1.2.0-beta.11", "1.2.0-beta.2", "1.2.0-beta"]
+//!     .into_iter()
+//!     .map(|version| version.parse().unwrap())
+//!     .collect();
+//! for version in &versions {
+//!     diesel::insert_into(releases::table)
+//!         .values(releases::version.eq(sortable_text::As(version.clone())))
+//!         .execute(&mut conn)
+//!         .unwrap();
+//! }
+//!
+//! let sorted: Vec<sortable_text::As> = releases::table
+//!     .select(releases::version)
+//!     .order(releases::version)
+//!     .load(&mut conn)
+//!     .unwrap();
+//! versions.sort();
+//! assert_eq!(sortable_text::As::into_targets(sorted), versions);
+//!
+//! let stored: String = diesel::select(text::As(versions[0].clone()).into_sql::<Text>()).get_result(&mut conn).unwrap();
+//! assert_eq!(stored, "1.2.0-beta");
+//! let invalid = diesel::select("1.2".into_sql::<Text>()).get_result::<text::As>(&mut conn);
+//! assert!(invalid.unwrap_err().to_string().contains(r#""1.2" is not a semantic version"#));
+//!
+//! let longest: Version = format!("1.0.0-{}", "9".repeat(99)).parse().unwrap();
+//! let loaded: sortable_text::As = diesel::select(sortable_text::As(longest.clone()).into_sql::<Text>()).get_result(&mut conn).unwrap();
+//! assert_eq!(loaded.0, longest);
+//! let too_long: Version = format!("1.0.0-{}", "9".repeat(100)).parse().unwrap();
+//! let error = diesel::select(sortable_text::As(too_long).into_sql::<Text>()).get_result::<sortable_text::As>(&mut conn).unwrap_err();
+//! assert!(error.to_string().contains("has more than 99 digits"));
+//! ```
+
+use std::fmt::Write;
+
+use crate::FillError;
+
+fn encode_sortable(version: &::semver::Version, encoded: &mut String) -> Result<(), FillError> {
+    write!(encoded, "{:020}.{:020}.{:020}", version.major, version.minor, version.patch)?;
+    if version.pre.is_empty() {
+        encoded.push('~');
+    } else {
+        encoded.push('-');
+        for (index, identifier) in version.pre.split('.').enumerate() {
+            if index > 0 {
+                encoded.push(',');
+            }
+            if identifier.bytes().all(|byte| byte.is_ascii_digit()) {
+                // Numeric identifiers have no leading zeros, so comparing the length first compares them numerically.
+                if identifier.len() > 99 {
+                    return Err(format!("the pre-release identifier {identifier} of {version} has more than 99 digits").into());
+                }
+                write!(encoded, "0{:02}{identifier}", identifier.len())?;
+            } else {
+                encoded.push('1');
+                encoded.push_str(identifier);
+            }
+        }
+    }
+    if !version.build.is_empty() {
+        encoded.push('+');
+        encoded.push_str(&version.build);
+    }
+    Ok(())
+}
+
+fn decode_sortable(encoded: &str) -> Result<::semver::Version, String> {
+    let invalid = || format!("{encoded:?} is not a sortable version");
+    let (rest, build) = match encoded.split_once('+') {
+        Some((rest, build)) => (rest, ::semver::BuildMetadata::new(build).map_err(|_| invalid())?),
+        None => (encoded, ::semver::BuildMetadata::EMPTY),
+    };
+    let (core, pre) = if let Some(core) = rest.strip_suffix('~') {
+        (core, ::semver::Prerelease::EMPTY)
+    } else {
+        let (core, identifiers) = rest.split_once('-').ok_or_else(invalid)?;
+        let identifiers = identifiers
+            .split(',')
+            .map(|identifier| match identifier.split_at_checked(1) {
+                Some(("0", numeric)) => numeric
+                    .get(2..)
+                    .filter(|number| numeric[..2].parse() == Ok(number.len()))
+                    .ok_or_else(invalid),
+                Some(("1", alphanumeric)) => Ok(alphanumeric),
+                _ => Err(invalid()),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        (core, ::semver::Prerelease::new(&identifiers.join(".")).map_err(|_| invalid())?)
+    };
+    let mut parts = core.split('.').map(|part| if part.len() == 20 { part.parse::<u64>().ok() } else { None });
+    let (Some(Some(major)), Some(Some(minor)), Some(Some(patch)), None) = (parts.next(), parts.next(), parts.next(), parts.next()) else {
+        return Err(invalid());
+    };
+    Ok(::semver::Version { major, minor, patch, pre, build })
+}
+
+crate::wrap! {
+    target = ::semver::Version;
+    sql_type = Text;
+    pub mod text;
+    fn buffered_sql<String>(self, buffer) {
+        use std::fmt::Write;
+        write!(buffer, "{}", self.0)?;
+        Ok(())
+    }
+    fn from_sql<&str>(bytes) {
+        bytes.parse()
+            .map(As)
+            .map_err(|e| format!("{bytes:?} is not a semantic version: {e}").into())
+    }
+}
+
+crate::wrap! {
+    target = ::semver::Version;
+    sql_type = Text;
+    pub mod sortable_text;
+    fn buffered_sql<String>(self, buffer) {
+        super::encode_sortable(&self.0, buffer)
+    }
+    fn from_sql<&str>(bytes) {
+        super::decode_sortable(bytes).map(As).map_err(Into::into)
+    }
+}
@@ -0,0 +1,133 @@
+//! Wrapper for snowflake style ids stored in a `BigInt` column.
+//!
+//! A [`Snowflake`] packs a millisecond timestamp relative to a custom epoch, a worker id and a sequence number into
+//! the 63 non negative bits of an `i64` (the layout introduced by Twitter):
+//! - 41 bits timestamp (about 69 years after the epoch)
+//! - 10 bits worker id
+//! - 12 bits sequence number
+//!
+//! [`bigint`] stores the id unchanged, so `ORDER BY id` orders rows by creation time.
+//! Negative values have a timestamp outside of the epoch range and produce a deserialization error.
+//!
+//! # Example:
+#![cfg_attr(feature = "sqlite", doc = "```")]
+#![cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+//! use diesel::prelude::*;
+//! use diesel::sql_types::BigInt;
+//! use diesel_as_wrap::snowflake::{bigint, Snowflake};
+//! use std::time::{Duration, UNIX_EPOCH};
+//!
+//! let mut conn = SqliteConnection::establish(":memory:").unwrap();
+//!
+//! let id = Snowflake::from_parts(1_000, 3, 7).unwrap();
+//! let stored: i64 = diesel::select(bigint::As(id).into_sql::<BigInt>()).get_result(&mut conn).unwrap();
+//! assert_eq!(stored, (1_000 << 22) | (3 << 12) | 7);
+//!
+//! let loaded: bigint::As = diesel::select(stored.into_sql::<BigInt>()).get_result(&mut conn).unwrap();
+//! assert_eq!(loaded.0.timestamp(), 1_000);
+//! assert_eq!(loaded.0.worker(), 3);
+//! assert_eq!(loaded.0.sequence(), 7);
+//! assert_eq!(loaded.0.unix_millis(Snowflake::TWITTER_EPOCH), Some(Snowflake::TWITTER_EPOCH + 1_000));
+//! let created_at = loaded.0.created_at(Snowflake::TWITTER_EPOCH).unwrap();
+//! assert_eq!(created_at, UNIX_EPOCH + Duration::from_millis(Snowflake::TWITTER_EPOCH + 1_000));
+//! assert_eq!(loaded.0.unix_millis(u64::MAX), None);
+//! assert_eq!(loaded.0.created_at(u64::MAX), None);
+//!
+//! let negative = diesel::select((-1i64).into_sql::<BigInt>()).get_result::<bigint::As>(&mut conn);
+//! assert!(negative.is_err());
+//! ```
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const WORKER_BITS: u32 = 10;
+const SEQUENCE_BITS: u32 = 12;
+const TIMESTAMP_BITS: u32 = 41;
+
+///
+/// A snowflake id, guaranteed to be non negative.\
+/// The epoch is not part of the id, so methods converting the timestamp into a point in time take it as argument.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Snowflake(i64);
+
+impl Snowflake {
+    /// The epoch used by Twitter (2010-11-04T01:42:54.657Z) in milliseconds since the unix epoch.
+    pub const TWITTER_EPOCH: u64 = 1_288_834_974_657;
+    /// The epoch used by Discord (2015-01-01T00:00:00Z) in milliseconds since the unix epoch.
+    pub const DISCORD_EPOCH: u64 = 1_420_070_400_000;
+
+    /// The largest timestamp (in milliseconds since the epoch).
+    pub const MAX_TIMESTAMP: u64 = (1 << TIMESTAMP_BITS) - 1;
+    /// The largest worker id.
+    pub const MAX_WORKER: u16 = (1 << WORKER_BITS) - 1;
+    /// The largest sequence number.
+    pub const MAX_SEQUENCE: u16 = (1 << SEQUENCE_BITS) - 1;
+
+    /// Creates a snowflake from its raw value, returns `None` for negative values.
+    pub fn new(id: i64) -> Option<Self> {
+        (id >= 0).then_some(Snowflake(id))
+    }
+
+    /// Creates a snowflake from its parts, returns `None` if a part doesn't fit into its bits.
+    pub fn from_parts(timestamp: u64, worker: u16, sequence: u16) -> Option<Self> {
+        if timestamp > Self::MAX_TIMESTAMP || worker > Self::MAX_WORKER || sequence > Self::MAX_SEQUENCE {
+            return None;
+        }
+        let id = (timestamp << (WORKER_BITS + SEQUENCE_BITS)) | (u64::from(worker) << SEQUENCE_BITS) | u64::from(sequence);
+        Some(Snowflake(id as i64))
+    }
+
+    /// The raw value of the id.
+    pub fn id(self) -> i64 {
+        self.0
+    }
+
+    /// The milliseconds since the epoch the id was created at.
+    pub fn timestamp(self) -> u64 {
+        self.0 as u64 >> (WORKER_BITS + SEQUENCE_BITS)
+    }
+
+    /// The id of the worker that created the id.
+    pub fn worker(self) -> u16 {
+        ((self.0 as u64 >> SEQUENCE_BITS) & u64::from(Self::MAX_WORKER)) as u16
+    }
+
+    /// The sequence number of the id within its millisecond and worker.
+    pub fn sequence(self) -> u16 {
+        (self.0 as u64 & u64::from(Self::MAX_SEQUENCE)) as u16
+    }
+
+    /// The milliseconds since the unix epoch the id was created at, given the epoch (in milliseconds since the unix epoch).
+    /// Returns `None` if the sum overflows a `u64`.
+    pub fn unix_millis(self, epoch: u64) -> Option<u64> {
+        epoch.checked_add(self.timestamp())
+    }
+
+    /// The point in time the id was created at, given the epoch (in milliseconds since the unix epoch).
+    /// Returns `None` if it can't be represented as a `SystemTime`.
+    pub fn created_at(self, epoch: u64) -> Option<SystemTime> {
+        UNIX_EPOCH.checked_add(Duration::from_millis(self.unix_millis(epoch)?))
+    }
+}
+
+impl From<Snowflake> for i64 {
+    fn from(id: Snowflake) -> Self {
+        id.0
+    }
+}
+
+crate::wrap! {
+    target = crate::snowflake::Snowflake;
+    sql_type = BigInt;
+    pub mod bigint;
+    fn to_sql<i64>(self, out) {
+        let id: &i64 = &self.0.0;
+        id.to_sql(out)
+    }
+    fn from_sql<i64>(bytes) {
+        let id = i64::from_sql(bytes)?;
+        crate::snowflake::Snowflake::new(id)
+            .map(As)
+            .ok_or_else(|| format!("snowflake id {id} is negative, its timestamp is out of range").into())
+    }
+}
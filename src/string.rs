@@ -0,0 +1,125 @@
+//! Wrappers for string types that can't be used by diesel directly.
+//!
+//! [`arc_str`] reads a `Text` column into an `Arc<str>` and [`rc_str`] into an `Rc<str>`.
+//! This is useful for immutable (or interned) strings that are shared widely after loading them, as cloning them only bumps a reference count.
+//! Both copy the text straight from the raw value into the shared allocation, without reading a `String` first.
+//!
+//! [`box_str`] reads a `Text` column into a `Box<str>`, which is one word smaller than a `String` for strings that never grow.
+//!
+//! [`compact_str`] and [`smol_str`] read a `Text` column into the small string types of the crates with the same name
+//! (behind the `compact_str` and `smol_str` features).
+//! Both store short strings inline, so reading short text columns doesn't allocate on the heap.
+//!
+//! # Example:
+//! ```
+//! # use diesel::prelude::*;
+//! # mod schema {
+//! #     diesel::table! {
+//! #         tenants (id) {
+//! #             id -> Integer,
+//! #             name -> Text,
+//! #             label -> Nullable<Text>,
+//! #         }
+//! #     }
+//! # }
+//! use std::sync::Arc;
+//! use diesel_as_wrap::string::arc_str;
+//!
+//! #[derive(Debug, Queryable, Selectable, Insertable)]
+//! #[diesel(table_name = schema::tenants)]
+//! pub struct Tenant {
+//!     id: i32,
+//!     #[diesel(deserialize_as = arc_str::As)]
+//!     #[diesel(serialize_as = arc_str::As)]
+//!     name: Arc<str>,
+//!     #[diesel(deserialize_as = arc_str::AsOption)]
+//!     #[diesel(serialize_as = arc_str::AsOption)]
+//!     label: Option<Arc<str>>,
+//! }
+//! ```
+//!
+//! Reading the other string types:
+#![cfg_attr(feature = "sqlite", doc = "```")]
+#![cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+//! use std::rc::Rc;
+//! use diesel::prelude::*;
+//! use diesel::sql_types::Text;
+//! use diesel_as_wrap::string::{box_str, rc_str};
+//!
+//! let mut conn = SqliteConnection::establish(":memory:").unwrap();
+//!
+//! let name: Rc<str> = Rc::from("tenant-a");
+//! let loaded: rc_str::As = diesel::select(rc_str::As(name.clone()).into_sql::<Text>()).get_result(&mut conn).unwrap();
+//! assert_eq!(loaded.0, name);
+//!
+//! let loaded: box_str::As = diesel::select("tenant-b".into_sql::<Text>()).get_result(&mut conn).unwrap();
+//! assert_eq!(&*loaded.0, "tenant-b");
+//! ```
+
+crate::wrap! {
+    target = ::std::sync::Arc<str>;
+    sql_type = Text;
+    pub mod arc_str;
+    fn to_sql<str>(self, out) {
+        let text: &str = &self.0;
+        text.to_sql(out)
+    }
+    fn from_sql<&str>(bytes) {
+        Ok(As(bytes.into()))
+    }
+}
+
+crate::wrap! {
+    target = ::std::rc::Rc<str>;
+    sql_type = Text;
+    pub mod rc_str;
+    fn to_sql<str>(self, out) {
+        let text: &str = &self.0;
+        text.to_sql(out)
+    }
+    fn from_sql<&str>(bytes) {
+        Ok(As(bytes.into()))
+    }
+}
+
+crate::wrap! {
+    target = ::std::boxed::Box<str>;
+    sql_type = Text;
+    pub mod box_str;
+    fn to_sql<str>(self, out) {
+        let text: &str = &self.0;
+        text.to_sql(out)
+    }
+    fn from_sql<String>(bytes) {
+        let text = String::from_sql(bytes)?;
+        Ok(As(text.into_boxed_str()))
+    }
+}
+
+#[cfg(feature = "compact_str")]
+crate::wrap! {
+    target = ::compact_str::CompactString;
+    sql_type = Text;
+    pub mod compact_str;
+    fn to_sql<str>(self, out) {
+        let text: &str = &self.0;
+        text.to_sql(out)
+    }
+    fn from_sql<&str>(bytes) {
+        Ok(As(::compact_str::CompactString::new(bytes)))
+    }
+}
+
+#[cfg(feature = "smol_str")]
+crate::wrap! {
+    target = ::smol_str::SmolStr;
+    sql_type = Text;
+    pub mod smol_str;
+    fn to_sql<str>(self, out) {
+        let text: &str = &self.0;
+        text.to_sql(out)
+    }
+    fn from_sql<&str>(bytes) {
+        Ok(As(::smol_str::SmolStr::new(bytes)))
+    }
+}
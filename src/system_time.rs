@@ -0,0 +1,286 @@
+//! Wrappers for [`std::time::SystemTime`] without depending on an external datetime crate.
+//!
+//! [`timestamp`] maps a `SystemTime` to the `Timestamp` sql type of every enabled backend.
+//! The value is interpreted as UTC and stored with microsecond precision (smaller parts are truncated).
+//! - On Postgres diesels native `SystemTime` support is used.
+//! - On MySQL the value is converted from and into the calendar fields of `MysqlTime`.
+//! - On Sqlite the value is stored as text in the `YYYY-MM-DD HH:MM:SS.ffffff` format used by diesel.
+//!   Reading also accepts a `T` separator, fewer fractional digits and a trailing `Z`.
+//!
+//! # Example:
+//! ```
+//! # use diesel::prelude::*;
+//! # mod schema {
+//! #     diesel::table! {
+//! #         files (id) {
+//! #             id -> Integer,
+//! #             modified_at -> Timestamp,
+//! #             accessed_at -> Nullable<Timestamp>,
+//! #         }
+//! #     }
+//! # }
+//! use std::time::SystemTime;
+//! use diesel_as_wrap::system_time::timestamp;
+//!
+//! #[derive(Debug, Queryable, Selectable, Insertable)]
+//! #[diesel(table_name = schema::files)]
+//! pub struct File {
+//!     id: i32,
+//!     #[diesel(deserialize_as = timestamp::As)]
+//!     #[diesel(serialize_as = timestamp::As)]
+//!     modified_at: SystemTime,
+//!     #[diesel(deserialize_as = timestamp::AsOption)]
+//!     #[diesel(serialize_as = timestamp::AsOption)]
+//!     accessed_at: Option<SystemTime>,
+//! }
+//! ```
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+///
+/// Intermediate type used by [`timestamp`] to read and write `Timestamp` columns on every enabled backend.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SqlTimestamp(pub SystemTime);
+
+crate::wrap! {
+    target = ::std::time::SystemTime;
+    sql_type = Timestamp;
+    pub mod timestamp;
+    fn into_sql<super::SqlTimestamp>(self) {
+        super::SqlTimestamp(self.0)
+    }
+    fn from_sql<super::SqlTimestamp>(bytes) {
+        let timestamp = <super::SqlTimestamp>::from_sql(bytes)?;
+        Ok(As(timestamp.0))
+    }
+}
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// A UTC calendar date and time with microsecond precision.
+#[cfg_attr(not(any(feature = "mysql", feature = "sqlite")), allow(dead_code))]
+struct Civil {
+    year: i64,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+    micros: u32,
+}
+
+#[cfg_attr(not(any(feature = "mysql", feature = "sqlite")), allow(dead_code))]
+impl Civil {
+    fn from_system_time(time: SystemTime) -> Self {
+        let micros = match time.duration_since(UNIX_EPOCH) {
+            Ok(after) => after.as_micros() as i128,
+            Err(before) => -(before.duration().as_nanos().div_ceil(1_000) as i128),
+        };
+        let seconds = micros.div_euclid(1_000_000) as i64;
+        let micros = micros.rem_euclid(1_000_000) as u32;
+        let days = seconds.div_euclid(SECONDS_PER_DAY);
+        let second_of_day = seconds.rem_euclid(SECONDS_PER_DAY) as u32;
+        let (year, month, day) = civil_from_days(days);
+        Civil {
+            year,
+            month,
+            day,
+            hour: second_of_day / 3600,
+            minute: second_of_day / 60 % 60,
+            second: second_of_day % 60,
+            micros,
+        }
+    }
+
+    fn to_system_time(&self) -> Option<SystemTime> {
+        if !(1..=12).contains(&self.month)
+            || self.day == 0
+            || self.day > days_in_month(self.year, self.month)
+            || self.hour > 23
+            || self.minute > 59
+            || self.second > 59
+            || self.micros > 999_999
+        {
+            return None;
+        }
+        let seconds = days_from_civil(self.year, self.month, self.day)
+            .checked_mul(SECONDS_PER_DAY)?
+            .checked_add(i64::from(self.hour * 3600 + self.minute * 60 + self.second))?;
+        let offset = Duration::from_secs(seconds.unsigned_abs());
+        let time = if seconds >= 0 {
+            UNIX_EPOCH.checked_add(offset)?
+        } else {
+            UNIX_EPOCH.checked_sub(offset)?
+        };
+        time.checked_add(Duration::from_micros(u64::from(self.micros)))
+    }
+}
+
+/// Days since 1970-01-01 for a date of the proleptic gregorian calendar.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month = i64::from(month);
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// The date of the proleptic gregorian calendar for the days since 1970-01-01.
+#[cfg_attr(not(any(feature = "mysql", feature = "sqlite")), allow(dead_code))]
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+#[cfg(feature = "postgres")]
+mod pg {
+    use super::SqlTimestamp;
+    use diesel::deserialize::{FromSql, Result as DResult};
+    use diesel::pg::{Pg, PgValue};
+    use diesel::serialize::{Output, Result as SResult, ToSql};
+    use diesel::sql_types::Timestamp;
+    use std::time::SystemTime;
+
+    impl FromSql<Timestamp, Pg> for SqlTimestamp {
+        fn from_sql(bytes: PgValue<'_>) -> DResult<Self> {
+            <SystemTime as FromSql<Timestamp, Pg>>::from_sql(bytes).map(SqlTimestamp)
+        }
+    }
+
+    impl ToSql<Timestamp, Pg> for SqlTimestamp {
+        fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> SResult {
+            <SystemTime as ToSql<Timestamp, Pg>>::to_sql(&self.0, out)
+        }
+    }
+}
+
+#[cfg(feature = "mysql")]
+mod mysql {
+    use super::{Civil, SqlTimestamp};
+    use diesel::deserialize::{FromSql, Result as DResult};
+    use diesel::mysql::data_types::{MysqlTime, MysqlTimestampType};
+    use diesel::mysql::{Mysql, MysqlValue};
+    use diesel::serialize::{Output, Result as SResult, ToSql};
+    use diesel::sql_types::Timestamp;
+
+    impl FromSql<Timestamp, Mysql> for SqlTimestamp {
+        fn from_sql(bytes: MysqlValue<'_>) -> DResult<Self> {
+            let time = <MysqlTime as FromSql<Timestamp, Mysql>>::from_sql(bytes)?;
+            Civil {
+                year: i64::from(time.year),
+                month: time.month,
+                day: time.day,
+                hour: time.hour,
+                minute: time.minute,
+                second: time.second,
+                micros: u32::try_from(time.second_part)?,
+            }
+            .to_system_time()
+            .map(SqlTimestamp)
+            .ok_or_else(|| format!("invalid timestamp {time:?}").into())
+        }
+    }
+
+    impl ToSql<Timestamp, Mysql> for SqlTimestamp {
+        fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Mysql>) -> SResult {
+            let civil = Civil::from_system_time(self.0);
+            let time = MysqlTime::new(
+                u32::try_from(civil.year).map_err(|_| format!("year {} is out of range", civil.year))?,
+                civil.month,
+                civil.day,
+                civil.hour,
+                civil.minute,
+                civil.second,
+                civil.micros.into(),
+                false,
+                MysqlTimestampType::MYSQL_TIMESTAMP_DATETIME,
+                0,
+            );
+            <MysqlTime as ToSql<Timestamp, Mysql>>::to_sql(&time, &mut out.reborrow())
+        }
+    }
+}
+
+#[cfg(feature = "sqlite")]
+mod sqlite {
+    use super::{Civil, SqlTimestamp};
+    use crate::ToSqlOwned;
+    use diesel::deserialize::{FromSql, Result as DResult};
+    use diesel::serialize::{IsNull, Output, Result as SResult, ToSql};
+    use diesel::sql_types::Timestamp;
+    use diesel::sqlite::{Sqlite, SqliteValue};
+
+    impl SqlTimestamp {
+        fn to_text(self) -> String {
+            let civil = Civil::from_system_time(self.0);
+            format!(
+                "{:04}-{:02}-{:02} {:02}:{:02}:{:02}.{:06}",
+                civil.year, civil.month, civil.day, civil.hour, civil.minute, civil.second, civil.micros
+            )
+        }
+
+        fn parse(text: &str) -> Option<Self> {
+            let text = text.strip_suffix('Z').unwrap_or(text);
+            let (date, time) = text.split_once([' ', 'T'])?;
+            let mut date = date.splitn(3, '-');
+            let (time, fraction) = time.split_once('.').unwrap_or((time, ""));
+            let mut time = time.splitn(3, ':');
+            if fraction.len() > 9 || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            let micros = format!("{:0<6}", fraction.get(..6).unwrap_or(fraction));
+            Civil {
+                year: date.next()?.parse().ok()?,
+                month: date.next()?.parse().ok()?,
+                day: date.next()?.parse().ok()?,
+                hour: time.next()?.parse().ok()?,
+                minute: time.next()?.parse().ok()?,
+                second: time.next()?.parse().ok()?,
+                micros: micros.parse().ok()?,
+            }
+            .to_system_time()
+            .map(SqlTimestamp)
+        }
+    }
+
+    impl FromSql<Timestamp, Sqlite> for SqlTimestamp {
+        fn from_sql(bytes: SqliteValue<'_, '_, '_>) -> DResult<Self> {
+            let text = <String as FromSql<Timestamp, Sqlite>>::from_sql(bytes)?;
+            SqlTimestamp::parse(&text).ok_or_else(|| format!("invalid timestamp {text}").into())
+        }
+    }
+
+    impl ToSql<Timestamp, Sqlite> for SqlTimestamp {
+        fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Sqlite>) -> SResult {
+            out.set_value(self.to_text());
+            Ok(IsNull::No)
+        }
+    }
+
+    impl ToSqlOwned<Timestamp, Sqlite> for SqlTimestamp {
+        fn to_sql_owned(self, out: &mut Output<'_, '_, Sqlite>) -> SResult {
+            out.set_value(self.to_text());
+            Ok(IsNull::No)
+        }
+    }
+}
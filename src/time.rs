@@ -0,0 +1,212 @@
+//! Wrappers for [`time`](::time) types.
+//!
+//! The `unix_*` modules store an `OffsetDateTime` as a unix timestamp inside a `BigInt` column.
+//! The precision is selected by picking the module:
+//! - [`unix_seconds`] stores whole seconds.
+//! - [`unix_millis`] stores milliseconds.
+//! - [`unix_micros`] stores microseconds.
+//!
+//! Smaller parts are rounded down (floored) the same way `chrono` does it, so `1969-12-31T23:59:59.5Z` is stored as `-1` second.
+//! The offset is not stored, values are always read back in UTC.
+//! Timestamps that are out of range produce a (de)serialization error.
+//!
+//! [`primitive_unix_micros`] stores a `PrimitiveDateTime` the same way as `unix_micros`, as if its wall clock time was UTC.
+//!
+//! [`utc_timestamp`] stores an `OffsetDateTime` in a `Timestamp` column as UTC wall clock time, on every backend.
+//! [`timestamptz`] stores it in a Postgres `Timestamptz` column (requires the `postgres` feature).
+//! Both read the value back in UTC as well.
+//!
+//! [`unix_days`] stores a `Date` as days since 1970-01-01 inside an `Integer` column and
+//! [`micros_of_day`] stores a `Time` as microseconds since midnight inside a `BigInt` column (sub microsecond parts are truncated).
+//! Values that are out of range produce a deserialization error.
+//!
+//! # Example:
+//! ```
+//! # use diesel::prelude::*;
+//! # mod schema {
+//! #     diesel::table! {
+//! #         events (id) {
+//! #             id -> Integer,
+//! #             created_at -> BigInt,
+//! #             deleted_at -> Nullable<BigInt>,
+//! #         }
+//! #     }
+//! # }
+//! use time::OffsetDateTime;
+//! use diesel_as_wrap::time::unix_seconds;
+//!
+//! #[derive(Debug, Queryable, Selectable, Insertable)]
+//! #[diesel(table_name = schema::events)]
+//! pub struct Event {
+//!     id: i32,
+//!     #[diesel(deserialize_as = unix_seconds::As)]
+//!     #[diesel(serialize_as = unix_seconds::As)]
+//!     created_at: OffsetDateTime,
+//!     #[diesel(deserialize_as = unix_seconds::AsOption)]
+//!     #[diesel(serialize_as = unix_seconds::AsOption)]
+//!     deleted_at: Option<OffsetDateTime>,
+//! }
+//! ```
+//!
+//! The other encodings:
+#![cfg_attr(feature = "sqlite", doc = "```")]
+#![cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+//! use diesel::prelude::*;
+//! use diesel::sql_types::{BigInt, Integer, Timestamp};
+//! use diesel_as_wrap::time::{micros_of_day, primitive_unix_micros, unix_days, unix_micros, unix_seconds, utc_timestamp};
+//! use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time};
+//!
+//! let mut conn = SqliteConnection::establish(":memory:").unwrap();
+//!
+//! let before_epoch = OffsetDateTime::from_unix_timestamp_nanos(-500_000_500).unwrap();
+//! let seconds: i64 = diesel::select(unix_seconds::As(before_epoch).into_sql::<BigInt>()).get_result(&mut conn).unwrap();
+//! assert_eq!(seconds, -1);
+//! let micros: i64 = diesel::select(unix_micros::As(before_epoch).into_sql::<BigInt>()).get_result(&mut conn).unwrap();
+//! assert_eq!(micros, -500_001);
+//!
+//! let launch = OffsetDateTime::from_unix_timestamp_nanos(1_700_000_000_250_000_000).unwrap();
+//! let loaded: utc_timestamp::As = diesel::select(utc_timestamp::As(launch).into_sql::<Timestamp>()).get_result(&mut conn).unwrap();
+//! assert_eq!(loaded.0, launch);
+//!
+//! let date = Date::from_calendar_date(1969, Month::December, 31).unwrap();
+//! let days: i32 = diesel::select(unix_days::As(date).into_sql::<Integer>()).get_result(&mut conn).unwrap();
+//! assert_eq!(days, -1);
+//! let loaded: unix_days::As = diesel::select(days.into_sql::<Integer>()).get_result(&mut conn).unwrap();
+//! assert_eq!(loaded.0, date);
+//!
+//! let time = Time::from_hms_micro(23, 59, 59, 999_999).unwrap();
+//! let micros: i64 = diesel::select(micros_of_day::As(time).into_sql::<BigInt>()).get_result(&mut conn).unwrap();
+//! assert_eq!(micros, 86_399_999_999);
+//! let loaded: micros_of_day::As = diesel::select(micros.into_sql::<BigInt>()).get_result(&mut conn).unwrap();
+//! assert_eq!(loaded.0, time);
+//!
+//! let midnight = PrimitiveDateTime::new(Date::from_calendar_date(1970, Month::January, 2).unwrap(), Time::MIDNIGHT);
+//! let micros: i64 = diesel::select(primitive_unix_micros::As(midnight).into_sql::<BigInt>()).get_result(&mut conn).unwrap();
+//! assert_eq!(micros, 86_400_000_000);
+//!
+//! let next_day = diesel::select(86_400_000_000_i64.into_sql::<BigInt>()).get_result::<micros_of_day::As>(&mut conn);
+//! assert!(next_day.unwrap_err().to_string().ends_with("86400000000µs since midnight are out of range"));
+//! ```
+
+crate::wrap! {
+    target = ::time::OffsetDateTime;
+    sql_type = BigInt;
+    pub mod unix_seconds;
+    fn into_sql<i64>(self) {
+        self.0.unix_timestamp()
+    }
+    fn from_sql<i64>(bytes) {
+        let seconds = i64::from_sql(bytes)?;
+        ::time::OffsetDateTime::from_unix_timestamp(seconds)
+            .map(As)
+            .map_err(Into::into)
+    }
+}
+
+crate::wrap! {
+    target = ::time::OffsetDateTime;
+    sql_type = BigInt;
+    pub mod unix_millis;
+    fn into_sql<i64>(self) {
+        i64::try_from(self.0.unix_timestamp_nanos().div_euclid(1_000_000))?
+    }
+    fn from_sql<i64>(bytes) {
+        let millis = i64::from_sql(bytes)?;
+        ::time::OffsetDateTime::from_unix_timestamp_nanos(i128::from(millis) * 1_000_000)
+            .map(As)
+            .map_err(Into::into)
+    }
+}
+
+crate::wrap! {
+    target = ::time::OffsetDateTime;
+    sql_type = BigInt;
+    pub mod unix_micros;
+    fn into_sql<i64>(self) {
+        i64::try_from(self.0.unix_timestamp_nanos().div_euclid(1_000))?
+    }
+    fn from_sql<i64>(bytes) {
+        let micros = i64::from_sql(bytes)?;
+        ::time::OffsetDateTime::from_unix_timestamp_nanos(i128::from(micros) * 1_000)
+            .map(As)
+            .map_err(Into::into)
+    }
+}
+
+crate::wrap! {
+    target = ::time::PrimitiveDateTime;
+    sql_type = BigInt;
+    pub mod primitive_unix_micros;
+    fn into_sql<i64>(self) {
+        i64::try_from(self.0.assume_utc().unix_timestamp_nanos().div_euclid(1_000))?
+    }
+    fn from_sql<i64>(bytes) {
+        let micros = i64::from_sql(bytes)?;
+        ::time::OffsetDateTime::from_unix_timestamp_nanos(i128::from(micros) * 1_000)
+            .map(|utc| As(::time::PrimitiveDateTime::new(utc.date(), utc.time())))
+            .map_err(Into::into)
+    }
+}
+
+crate::wrap! {
+    target = ::time::OffsetDateTime;
+    sql_type = Timestamp;
+    pub mod utc_timestamp;
+    fn into_sql<::time::PrimitiveDateTime>(self) {
+        let utc = self.0.to_offset(::time::UtcOffset::UTC);
+        ::time::PrimitiveDateTime::new(utc.date(), utc.time())
+    }
+    fn from_sql<::time::PrimitiveDateTime>(bytes) {
+        <::time::PrimitiveDateTime>::from_sql(bytes).map(|naive| As(naive.assume_utc()))
+    }
+}
+
+#[cfg(feature = "postgres")]
+crate::wrap! {
+    target = ::time::OffsetDateTime;
+    sql_type = Timestamptz;
+    pub mod timestamptz;
+    fn to_sql<::time::OffsetDateTime>(self, out) {
+        let value: &::time::OffsetDateTime = &self.0;
+        value.to_sql(out)
+    }
+    fn from_sql<::time::OffsetDateTime>(bytes) {
+        <::time::OffsetDateTime>::from_sql(bytes).map(|value| As(value.to_offset(::time::UtcOffset::UTC)))
+    }
+}
+
+/// The julian day number of 1970-01-01.
+const UNIX_EPOCH_JULIAN_DAY: i32 = 2_440_588;
+
+crate::wrap! {
+    target = ::time::Date;
+    sql_type = Integer;
+    pub mod unix_days;
+    fn into_sql<i32>(self) {
+        self.0.to_julian_day() - super::UNIX_EPOCH_JULIAN_DAY
+    }
+    fn from_sql<i32>(bytes) {
+        let days = i32::from_sql(bytes)?;
+        days.checked_add(super::UNIX_EPOCH_JULIAN_DAY)
+            .and_then(|julian_day| ::time::Date::from_julian_day(julian_day).ok())
+            .map(As)
+            .ok_or_else(|| format!("{days} days since the unix epoch are out of range").into())
+    }
+}
+
+crate::wrap! {
+    target = ::time::Time;
+    sql_type = BigInt;
+    pub mod micros_of_day;
+    fn into_sql<i64>(self) {
+        let (hour, minute, second, micros) = self.0.as_hms_micro();
+        ((i64::from(hour) * 60 + i64::from(minute)) * 60 + i64::from(second)) * 1_000_000 + i64::from(micros)
+    }
+    fn from_sql<i64>(bytes) {
+        let micros = i64::from_sql(bytes)?;
+        (0..86_400_000_000)
+            .contains(&micros)
+            .then(|| As(::time::Time::MIDNIGHT + ::time::Duration::microseconds(micros)))
+            .ok_or_else(|| format!("{micros}µs since midnight are out of range").into())
+    }
+}
@@ -0,0 +1,76 @@
+//! Wrappers for [`ulid::Ulid`](::ulid::Ulid).
+//!
+//! The storage mode is selected per column by picking the module:
+//! - [`text`] stores a `Ulid` inside a `Text` column as its 26 characters of Crockford base32 (`01ARZ3NDEKTSV4RRFFQ69G5FAV`).
+//!   Reading is case insensitive, text that isn't a ulid produces a deserialization error.
+//! - [`binary`] stores it as its 16 bytes in big endian order inside a `Binary` column (`BLOB` on SQLite, `BINARY(16)` on MySQL),
+//!   reading anything but 16 bytes produces a deserialization error.
+//!
+//! Both encodings keep the order of the ulid, so ordering by the column orders by creation time.
+//!
+//! # Example:
+#![cfg_attr(feature = "sqlite", doc = "```")]
+#![cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+//! use diesel::prelude::*;
+//! use diesel::sql_types::{Binary, Text};
+//! use diesel_as_wrap::ulid::{binary, text};
+//! use ulid::Ulid;
+//!
+//! let mut conn = SqliteConnection::establish(":memory:").unwrap();
+//!
+//! let id = Ulid::from_string("01ARZ3NDEKTSV4RRFFQ69G5FAV").unwrap();
+//! let stored: String = diesel::select(text::As(id).into_sql::<Text>()).get_result(&mut conn).unwrap();
+//! assert_eq!(stored, "01ARZ3NDEKTSV4RRFFQ69G5FAV");
+//! let loaded: text::As = diesel::select("01arz3ndektsv4rrffq69g5fav".into_sql::<Text>()).get_result(&mut conn).unwrap();
+//! assert_eq!(loaded.0, id);
+//!
+//! let bytes: Vec<u8> = diesel::select(binary::As(id).into_sql::<Binary>()).get_result(&mut conn).unwrap();
+//! assert_eq!(bytes, id.to_bytes());
+//! let loaded: binary::As = diesel::select(bytes.into_sql::<Binary>()).get_result(&mut conn).unwrap();
+//! assert_eq!(loaded.0, id);
+//!
+//! let invalid = diesel::select("01ARZ3NDEKTSV4RRFFQ69G5FAU!".into_sql::<Text>()).get_result::<text::As>(&mut conn);
+//! assert!(invalid.unwrap_err().to_string().contains(r#""01ARZ3NDEKTSV4RRFFQ69G5FAU!" is not a ulid"#));
+//! let truncated = diesel::select(vec![0x01_u8, 0x56].into_sql::<Binary>()).get_result::<binary::As>(&mut conn);
+//! assert!(truncated.unwrap_err().to_string().ends_with("2 bytes are not a ulid, expected 16"));
+//! ```
+
+crate::wrap! {
+    target = ::ulid::Ulid;
+    sql_type = Text;
+    pub mod text;
+    fn buffered_sql<String>(self, buffer) {
+        use std::fmt::Write;
+        write!(buffer, "{}", self.0)?;
+        Ok(())
+    }
+    fn from_sql<&str>(bytes) {
+        ::ulid::Ulid::from_string(bytes)
+            .map(As)
+            .map_err(|e| format!("{bytes:?} is not a ulid: {e}").into())
+    }
+}
+
+crate::wrap! {
+    target = ::ulid::Ulid;
+    sql_type = Binary;
+    pub mod binary;
+    fn buffered_sql<Vec<u8>>(self, buffer) {
+        buffer.extend_from_slice(&self.0.to_bytes());
+        Ok(())
+    }
+    fn from_sql<&[u8]>(bytes) {
+        <[u8; 16]>::try_from(bytes)
+            .map(|bytes| As(::ulid::Ulid::from_bytes(bytes)))
+            .map_err(|_| format!("{} bytes are not a ulid, expected 16", bytes.len()).into())
+    }
+}
+
+#[cfg(feature = "postgres")]
+crate::assert_wrapper_backends!(binary, [diesel::pg::Pg]);
+
+#[cfg(feature = "mysql")]
+crate::assert_wrapper_backends!(binary, [diesel::mysql::Mysql]);
+
+#[cfg(feature = "sqlite")]
+crate::assert_wrapper_backends!(binary, [diesel::sqlite::Sqlite]);
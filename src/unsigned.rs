@@ -0,0 +1,141 @@
+//! Wrappers for the unsigned integer types, which diesel only supports on Mysql.
+//!
+//! The modules are named after the unsigned type and the column type they are stored in, on every backend:
+//! - [`u8_smallint`], [`u16_integer`] and [`u32_bigint`] store the value in the next wider signed column,
+//!   so every value can be written.
+//! - [`u16_smallint`], [`u32_integer`] and [`u64_bigint`] store the value in the signed column of the same width.
+//!   Values above the maximum of the column produce a serialization error instead of wrapping around.
+//!
+//! Reading checks the stored integer with `TryFrom`, negative or too large values produce a deserialization error.
+//! Use [`AsI16`](crate::AsI16), [`AsI32`](crate::AsI32) and [`AsI64`](crate::AsI64) for other types converting with `TryFrom`.
+//!
+//! # Example:
+#![cfg_attr(feature = "sqlite", doc = "```")]
+#![cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+//! # use diesel::prelude::*;
+//! # mod schema {
+//! #     diesel::table! {
+//! #         files (id) {
+//! #             id -> Integer,
+//! #             mode -> Integer,
+//! #             size -> BigInt,
+//! #             links -> Nullable<SmallInt>,
+//! #         }
+//! #     }
+//! # }
+//! use diesel_as_wrap::unsigned::{u16_smallint, u32_integer, u64_bigint};
+//!
+//! #[derive(Debug, Clone, PartialEq, Queryable, Selectable, Insertable)]
+//! #[diesel(table_name = schema::files)]
+//! pub struct File {
+//!     id: i32,
+//!     #[diesel(deserialize_as = u32_integer::As)]
+//!     #[diesel(serialize_as = u32_integer::As)]
+//!     mode: u32,
+//!     #[diesel(deserialize_as = u64_bigint::As)]
+//!     #[diesel(serialize_as = u64_bigint::As)]
+//!     size: u64,
+//!     #[diesel(deserialize_as = u16_smallint::AsOption)]
+//!     #[diesel(serialize_as = u16_smallint::AsOption)]
+//!     links: Option<u16>,
+//! }
+//!
+//! # fn main() {
+//! let mut conn = SqliteConnection::establish(":memory:").unwrap();
+//! diesel::sql_query("CREATE TABLE files (id INTEGER PRIMARY KEY, mode INTEGER NOT NULL, size BIGINT NOT NULL, links SMALLINT)")
+//!     .execute(&mut conn)
+//!     .unwrap();
+//!
+//! let files = vec![
+//!     File { id: 1, mode: 0o100644, size: i64::MAX as u64, links: Some(i16::MAX as u16) },
+//!     File { id: 2, mode: 0, size: 0, links: None },
+//! ];
+//! diesel::insert_into(schema::files::table).values(files.clone()).execute(&mut conn).unwrap();
+//! let loaded: Vec<File> = schema::files::table.select(File::as_select()).load(&mut conn).unwrap();
+//! assert_eq!(loaded, files);
+//!
+//! let too_large = File { id: 3, mode: 0, size: u64::MAX, links: None };
+//! let error = diesel::insert_into(schema::files::table).values(too_large).execute(&mut conn).unwrap_err();
+//! assert!(error.to_string().ends_with("18446744073709551615 is out of range for `BigInt`"));
+//!
+//! diesel::sql_query("UPDATE files SET links = -1 WHERE id = 2").execute(&mut conn).unwrap();
+//! let error = schema::files::table.select(File::as_select()).load(&mut conn).unwrap_err();
+//! assert!(error.to_string().ends_with("-1 is out of range for `u16`"));
+//! # }
+//! ```
+//!
+//! The widening modules accept the full range of the unsigned type:
+#![cfg_attr(feature = "sqlite", doc = "```")]
+#![cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+//! use diesel::prelude::*;
+//! use diesel::sql_types::{BigInt, Integer, SmallInt};
+//! use diesel_as_wrap::unsigned::{u8_smallint, u16_integer, u32_bigint};
+//!
+//! let mut conn = SqliteConnection::establish(":memory:").unwrap();
+//!
+//! let stored: i16 = diesel::select(u8_smallint::As(u8::MAX).into_sql::<SmallInt>()).get_result(&mut conn).unwrap();
+//! assert_eq!(stored, 255);
+//! let stored: i32 = diesel::select(u16_integer::As(u16::MAX).into_sql::<Integer>()).get_result(&mut conn).unwrap();
+//! assert_eq!(stored, 65_535);
+//! let stored: i64 = diesel::select(u32_bigint::As(u32::MAX).into_sql::<BigInt>()).get_result(&mut conn).unwrap();
+//! assert_eq!(stored, 4_294_967_295);
+//! let loaded: u32_bigint::As = diesel::select(stored.into_sql::<BigInt>()).get_result(&mut conn).unwrap();
+//! assert_eq!(loaded.0, u32::MAX);
+//!
+//! let too_large = diesel::select(256_i16.into_sql::<SmallInt>()).get_result::<u8_smallint::As>(&mut conn);
+//! assert!(too_large.unwrap_err().to_string().ends_with("256 is out of range for `u8`"));
+//! ```
+
+macro_rules! unsigned {
+    (widening $($name:ident: $unsigned:ident => $sql_type:ident, $int:ident;)+) => {
+        $(
+            crate::wrap! {
+                target = $unsigned;
+                sql_type = $sql_type;
+                pub mod $name;
+                fn into_sql<$int>(self) {
+                    $int::from(self.0)
+                }
+                fn from_sql<$int>(bytes) {
+                    let value = $int::from_sql(bytes)?;
+                    $unsigned::try_from(value)
+                        .map(As)
+                        .map_err(|_| format!("{value} is out of range for `{}`", stringify!($unsigned)).into())
+                }
+            }
+        )+
+    };
+    (checked $($name:ident: $unsigned:ident => $sql_type:ident, $int:ident;)+) => {
+        $(
+            crate::wrap! {
+                target = $unsigned;
+                sql_type = $sql_type;
+                pub mod $name;
+                fn into_sql<$int>(self) {
+                    $int::try_from(self.0)
+                        .map_err(|_| format!("{} is out of range for `{}`", self.0, stringify!($sql_type)))?
+                }
+                fn from_sql<$int>(bytes) {
+                    let value = $int::from_sql(bytes)?;
+                    $unsigned::try_from(value)
+                        .map(As)
+                        .map_err(|_| format!("{value} is out of range for `{}`", stringify!($unsigned)).into())
+                }
+            }
+        )+
+    };
+}
+
+unsigned! {
+    widening
+    u8_smallint: u8 => SmallInt, i16;
+    u16_integer: u16 => Integer, i32;
+    u32_bigint: u32 => BigInt, i64;
+}
+
+unsigned! {
+    checked
+    u16_smallint: u16 => SmallInt, i16;
+    u32_integer: u32 => Integer, i32;
+    u64_bigint: u64 => BigInt, i64;
+}
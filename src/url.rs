@@ -0,0 +1,46 @@
+//! Wrappers for [`url::Url`](::url::Url).
+//!
+//! [`text`] stores a url inside a `Text` column in its serialized form (`Url::as_str`).
+//! Reading parses and validates the text again, so a malformed url written by another tool
+//! produces a deserialization error instead of reaching the application.
+//! Parsing normalizes the url, text that wasn't written through the wrapper can therefore be read back in a different form
+//! (for example `HTTP://Example.com` as `http://example.com/`).
+//!
+//! # Example:
+#![cfg_attr(feature = "sqlite", doc = "```")]
+#![cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+//! use diesel::prelude::*;
+//! use diesel::sql_types::Text;
+//! use diesel_as_wrap::url::text;
+//! use url::Url;
+//!
+//! let mut conn = SqliteConnection::establish(":memory:").unwrap();
+//!
+//! let url = Url::parse("https://example.com/search?q=diesel#results").unwrap();
+//! let stored: String = diesel::select(text::As(url.clone()).into_sql::<Text>()).get_result(&mut conn).unwrap();
+//! assert_eq!(stored, "https://example.com/search?q=diesel#results");
+//!
+//! let loaded: text::As = diesel::select("HTTP://Example.com".into_sql::<Text>()).get_result(&mut conn).unwrap();
+//! assert_eq!(loaded.0.as_str(), "http://example.com/");
+//!
+//! let malformed = diesel::select("https://exa mple.com".into_sql::<Text>()).get_result::<text::As>(&mut conn);
+//! assert!(malformed
+//!     .unwrap_err()
+//!     .to_string()
+//!     .ends_with(r#""https://exa mple.com" is not a url: invalid international domain name"#));
+//! ```
+
+crate::wrap! {
+    target = ::url::Url;
+    sql_type = Text;
+    pub mod text;
+    fn to_sql<str>(self, out) {
+        let text: &str = self.0.as_str();
+        text.to_sql(out)
+    }
+    fn from_sql<&str>(bytes) {
+        ::url::Url::parse(bytes)
+            .map(As)
+            .map_err(|e| format!("{bytes:?} is not a url: {e}").into())
+    }
+}
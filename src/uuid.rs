@@ -0,0 +1,96 @@
+//! Wrappers for [`uuid::Uuid`](::uuid::Uuid).
+//!
+//! [`hyphenated_text`] stores a `Uuid` inside a `Text` column in its hyphenated lowercase form (`67e55044-10b1-426f-9247-bb680e5fe0c8`).
+//! Reading is case insensitive and also accepts the other formats understood by `Uuid::try_parse` (simple, braced and urn),
+//! so existing columns written by other tools can be read as well.
+//! Text that isn't a uuid produces a deserialization error.
+//!
+//! [`binary`] stores it as its 16 bytes in big endian order inside a `Binary` column (`BLOB` on SQLite, `BINARY(16)` on MySQL),
+//! reading anything but 16 bytes produces a deserialization error.
+//! [`native`] maps it to the native Postgres `uuid` type (requires the `postgres` feature).
+//! Both keep the uuid's byte order, so ordering by the column orders by the uuid.
+//!
+//! # Example:
+#![cfg_attr(feature = "sqlite", doc = "```")]
+#![cfg_attr(not(feature = "sqlite"), doc = "```ignore")]
+//! use diesel::prelude::*;
+//! use diesel::sql_types::{Binary, Text};
+//! use diesel_as_wrap::uuid::{binary, hyphenated_text};
+//! use uuid::Uuid;
+//!
+//! let mut conn = SqliteConnection::establish(":memory:").unwrap();
+//!
+//! let id = Uuid::from_u128(0x67e5504410b1426f9247bb680e5fe0c8);
+//! let stored: String = diesel::select(hyphenated_text::As(id).into_sql::<Text>()).get_result(&mut conn).unwrap();
+//! assert_eq!(stored, "67e55044-10b1-426f-9247-bb680e5fe0c8");
+//!
+//! let loaded: hyphenated_text::As = diesel::select("67E55044-10B1-426F-9247-BB680E5FE0C8".into_sql::<Text>())
+//!     .get_result(&mut conn)
+//!     .unwrap();
+//! assert_eq!(loaded.0, id);
+//!
+//! let bytes: Vec<u8> = diesel::select(binary::As(id).into_sql::<Binary>()).get_result(&mut conn).unwrap();
+//! assert_eq!(bytes, id.as_bytes());
+//! let loaded: binary::As = diesel::select(bytes.into_sql::<Binary>()).get_result(&mut conn).unwrap();
+//! assert_eq!(loaded.0, id);
+//!
+//! let truncated = diesel::select(vec![0x67_u8, 0xe5].into_sql::<Binary>()).get_result::<binary::As>(&mut conn);
+//! assert!(truncated.unwrap_err().to_string().ends_with("2 bytes are not a uuid, expected 16"));
+//! ```
+
+crate::wrap! {
+    target = ::uuid::Uuid;
+    sql_type = Text;
+    pub mod hyphenated_text;
+    fn buffered_sql<String>(self, buffer) {
+        use std::fmt::Write;
+        write!(buffer, "{}", self.0.hyphenated())?;
+        Ok(())
+    }
+    fn from_sql<&str>(bytes) {
+        ::uuid::Uuid::try_parse(bytes)
+            .map(As)
+            .map_err(|e| format!("{bytes:?} is not a uuid: {e}").into())
+    }
+}
+
+crate::wrap! {
+    target = ::uuid::Uuid;
+    sql_type = Binary;
+    pub mod binary;
+    fn to_sql<[u8]>(self, out) {
+        let bytes: &[u8] = self.0.as_bytes();
+        bytes.to_sql(out)
+    }
+    fn from_sql<&[u8]>(bytes) {
+        ::uuid::Uuid::from_slice(bytes)
+            .map(As)
+            .map_err(|_| format!("{} bytes are not a uuid, expected 16", bytes.len()).into())
+    }
+}
+
+#[cfg(feature = "postgres")]
+crate::wrap! {
+    target = ::uuid::Uuid;
+    sql_type = diesel::sql_types::Uuid;
+    pub mod native;
+    fn to_sql<::uuid::Uuid>(self, out) {
+        let value: &::uuid::Uuid = &self.0;
+        value.to_sql(out)
+    }
+    fn from_sql<::uuid::Uuid>(bytes) {
+        <::uuid::Uuid>::from_sql(bytes).map(As)
+    }
+}
+
+#[cfg(feature = "postgres")]
+crate::assert_wrapper_backends!(binary, [diesel::pg::Pg]);
+
+#[cfg(feature = "postgres")]
+crate::assert_wrapper_backends!(native, [diesel::pg::Pg]);
+
+#[cfg(feature = "mysql")]
+crate::assert_wrapper_backends!(binary, [diesel::mysql::Mysql]);
+
+#[cfg(feature = "sqlite")]
+crate::assert_wrapper_backends!(binary, [diesel::sqlite::Sqlite]);
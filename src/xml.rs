@@ -0,0 +1,159 @@
+//! Wrapper for the Postgres `xml` type.
+//!
+//! [`Xml`] is the sql type of `xml` columns, use it inside `table!` (`body -> diesel_as_wrap::xml::Xml`).\
+//! [`document`] maps an `xml` column to an [`XmlDocument`], the text of a well formed xml document.
+//! Documents are checked with [`roxmltree`](::roxmltree) on construction and on read,
+//! so values that aren't well formed documents (for example content fragments without a single root element) produce a deserialization error.
+//! [`XmlDocument::parse`] returns the parsed tree for reading the document.
+//!
+//! # Example:
+#![cfg_attr(feature = "postgres", doc = "```")]
+#![cfg_attr(not(feature = "postgres"), doc = "```ignore")]
+//! # use diesel::prelude::*;
+//! # mod schema {
+//! #     diesel::table! {
+//! #         use diesel::sql_types::*;
+//! #         use diesel_as_wrap::xml::Xml;
+//! #
+//! #         invoices (id) {
+//! #             id -> Integer,
+//! #             body -> Xml,
+//! #             signature -> Nullable<Xml>,
+//! #         }
+//! #     }
+//! # }
+//! use diesel_as_wrap::xml::{document, XmlDocument};
+//!
+//! #[derive(Debug, Queryable, Selectable, Insertable)]
+//! #[diesel(table_name = schema::invoices)]
+//! #[diesel(check_for_backend(diesel::pg::Pg))]
+//! pub struct Invoice {
+//!     id: i32,
+//!     #[diesel(deserialize_as = document::As)]
+//!     #[diesel(serialize_as = document::As)]
+//!     body: XmlDocument,
+//!     #[diesel(deserialize_as = document::AsOption)]
+//!     #[diesel(serialize_as = document::AsOption)]
+//!     signature: Option<XmlDocument>,
+//! }
+//!
+//! let body = XmlDocument::new("<invoice><total>42</total></invoice>".to_owned()).unwrap();
+//! assert_eq!(body.parse().root_element().tag_name().name(), "invoice");
+//! assert!(XmlDocument::new("<invoice>".to_owned()).is_err());
+//! ```
+//!
+//! The binary format is the text of the document:
+#![cfg_attr(feature = "postgres", doc = "```")]
+#![cfg_attr(not(feature = "postgres"), doc = "```ignore")]
+//! # use diesel::deserialize::FromSql;
+//! # use diesel::pg::{Pg, PgMetadataLookup, PgTypeMetadata, PgValue};
+//! # use diesel::query_builder::bind_collector::RawBytesBindCollector;
+//! # use diesel::query_builder::BindCollector;
+//! # use diesel::serialize::ToSql;
+//! # use diesel::sql_types::HasSqlType;
+//! # struct Types;
+//! # impl PgMetadataLookup for Types {
+//! #     fn lookup_type(&mut self, _: &str, _: Option<&str>) -> PgTypeMetadata {
+//! #         PgTypeMetadata::new(100_000, 100_001)
+//! #     }
+//! # }
+//! # fn encode<ST, T: ToSql<ST, Pg>>(value: &T) -> Option<Vec<u8>> where Pg: HasSqlType<ST> {
+//! #     let mut collector = RawBytesBindCollector::<Pg>::new();
+//! #     collector.push_bound_value::<ST, T>(value, &mut Types).unwrap();
+//! #     collector.binds.pop().unwrap()
+//! # }
+//! # fn decode<ST, T: FromSql<ST, Pg>>(bytes: Option<&[u8]>) -> diesel::deserialize::Result<T> where Pg: HasSqlType<ST> {
+//! #     let oid = std::num::NonZeroU32::new(<Pg as HasSqlType<ST>>::metadata(&mut Types).oid().unwrap()).unwrap();
+//! #     T::from_nullable_sql(bytes.map(|bytes| PgValue::new(bytes, &oid)))
+//! # }
+//! use diesel::sql_types::Nullable;
+//! use diesel_as_wrap::xml::{document, Xml, XmlDocument};
+//!
+//! let body = XmlDocument::new("<invoice><total>42</total></invoice>".to_owned()).unwrap();
+//! let bytes = encode::<Xml, _>(&document::As(body.clone())).unwrap();
+//! assert_eq!(bytes, b"<invoice><total>42</total></invoice>");
+//! assert_eq!(decode::<Xml, document::As>(Some(&bytes)).unwrap().0, body);
+//! assert!(decode::<Nullable<Xml>, document::AsOption>(None).unwrap().0.is_none());
+//!
+//! // a content fragment without a single root element
+//! assert!(decode::<Xml, document::As>(Some(b"<total>42</total><total>43</total>")).is_err());
+//! assert!(decode::<Xml, document::As>(Some(b"<invoice>")).is_err());
+//! assert!(decode::<Xml, document::As>(Some(b"<invoice>\xff</invoice>")).is_err());
+//! ```
+
+use std::fmt;
+use std::io::Write;
+
+use diesel::deserialize::{self, FromSql};
+use diesel::pg::{Pg, PgValue};
+use diesel::serialize::{self, IsNull, Output, ToSql};
+
+///
+/// The sql type of the Postgres `xml` type.
+///
+#[derive(Debug, Clone, Copy, Default, diesel::sql_types::SqlType, diesel::query_builder::QueryId)]
+#[diesel(postgres_type(oid = 142, array_oid = 143))]
+pub struct Xml;
+
+impl ToSql<Xml, Pg> for str {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        out.write_all(self.as_bytes())?;
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Xml, Pg> for String {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        Ok(String::from_utf8(bytes.as_bytes().to_vec())?)
+    }
+}
+
+///
+/// The text of a well formed xml document.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct XmlDocument(String);
+
+impl XmlDocument {
+    /// Checks that the text is a well formed xml document.
+    pub fn new(text: String) -> Result<Self, ::roxmltree::Error> {
+        ::roxmltree::Document::parse(&text)?;
+        Ok(XmlDocument(text))
+    }
+
+    /// The text of the document.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Parses the document into a tree borrowing from the text.
+    pub fn parse(&self) -> ::roxmltree::Document<'_> {
+        ::roxmltree::Document::parse(&self.0).expect("XmlDocument is checked to be well formed")
+    }
+}
+
+impl fmt::Display for XmlDocument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<XmlDocument> for String {
+    fn from(document: XmlDocument) -> Self {
+        document.0
+    }
+}
+
+crate::wrap! {
+    target = crate::xml::XmlDocument;
+    sql_type = crate::xml::Xml;
+    pub mod document;
+    fn to_sql<str>(self, out) {
+        let text: &str = self.0.as_str();
+        text.to_sql(out)
+    }
+    fn from_sql<String>(bytes) {
+        let text = String::from_sql(bytes)?;
+        crate::xml::XmlDocument::new(text).map(As).map_err(Into::into)
+    }
+}